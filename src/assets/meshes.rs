@@ -88,8 +88,9 @@ pub fn create_enemy_bullet_mesh() -> Mesh {
     arrow_mesh
 }
 
-/// Creates a polygon mesh for asteroids with the specified number of faces (outline only)
-pub fn create_asteroid_mesh(faces: u32, radius: f32) -> Mesh {
+/// Creates a polygon mesh for asteroids with the specified number of faces (outline only).
+/// The outline jitter comes from `rng`, normally `GameRng::cosmetic`.
+pub fn create_asteroid_mesh(faces: u32, radius: f32, rng: &mut fastrand::Rng) -> Mesh {
     let mut asteroid_mesh = Mesh::new(
         bevy::render::render_resource::PrimitiveTopology::LineList,
         bevy::render::render_asset::RenderAssetUsages::RENDER_WORLD,
@@ -109,7 +110,7 @@ pub fn create_asteroid_mesh(faces: u32, radius: f32) -> Mesh {
 
         // Add some randomness to the radius for irregular asteroid shape
         // Constrain the random factor to prevent degenerate triangles
-        let random_factor = 0.8 + fastrand::f32() * 0.4; // 0.8 to 1.2 multiplier (more conservative)
+        let random_factor = 0.8 + rng.f32() * 0.4; // 0.8 to 1.2 multiplier (more conservative)
         let vertex_radius = radius * random_factor;
 
         let x = angle.cos() * vertex_radius;
@@ -271,77 +272,65 @@ pub fn create_button_outline_mesh(width: f32, height: f32) -> Mesh {
     button_mesh
 }
 
-/// Creates a ship-like mesh for enemy entities
-pub fn create_enemy_ship_mesh(enemy_type: &crate::components::EnemyType) -> Mesh {
-    let mut ship_mesh = Mesh::new(
+/// Creates a ship-like mesh for enemy entities from their archetype's outline
+pub fn create_enemy_ship_mesh(shape: &crate::resources::EnemyShape) -> Mesh {
+    create_outline_mesh(&shape.vertices, &shape.lines)
+}
+
+/// Creates the campaign beacon: a hexagon with a smaller one inside (outline only)
+pub fn create_beacon_mesh(radius: f32) -> Mesh {
+    let mut vertices = Vec::new();
+    let mut lines = Vec::new();
+    for (ring, ring_radius) in [radius, radius * 0.5].into_iter().enumerate() {
+        let first = (ring * 6) as u32;
+        for i in 0..6 {
+            let angle = i as f32 / 6.0 * std::f32::consts::TAU;
+            vertices.push([angle.cos() * ring_radius, angle.sin() * ring_radius]);
+            lines.extend([first + i, first + (i + 1) % 6]);
+        }
+    }
+    create_outline_mesh(&vertices, &lines)
+}
+
+/// Line mesh through `vertices`, drawing one line per pair of `lines` indices
+pub fn create_outline_mesh(vertices: &[[f32; 2]], lines: &[u32]) -> Mesh {
+    let mut outline_mesh = Mesh::new(
         bevy::render::render_resource::PrimitiveTopology::LineList,
         bevy::render::render_asset::RenderAssetUsages::RENDER_WORLD,
     );
 
-    let vertices = match enemy_type {
-        crate::components::EnemyType::Hunter => {
-            // Arrow-like shape - made 1.5x larger
-            vec![
-                [0.0, 18.0, 0.0],    // Front tip
-                [-12.0, -12.0, 0.0], // Left wing
-                [12.0, -12.0, 0.0],  // Right wing
-                [0.0, -6.0, 0.0],    // Center back
-            ]
-        }
-        crate::components::EnemyType::Bomber => {
-            // Wider, bulkier shape - made 1.5x larger
-            vec![
-                [0.0, 15.0, 0.0],    // Front
-                [-18.0, -9.0, 0.0],  // Left wing
-                [18.0, -9.0, 0.0],   // Right wing
-                [-9.0, -15.0, 0.0],  // Left back
-                [9.0, -15.0, 0.0],   // Right back
-            ]
-        }
-        crate::components::EnemyType::Interceptor => {
-            // Sleek, narrow shape - made 1.5x larger
-            vec![
-                [0.0, 22.5, 0.0],    // Front tip
-                [-7.5, -7.5, 0.0],   // Left side
-                [7.5, -7.5, 0.0],    // Right side
-                [0.0, -18.0, 0.0],   // Back
-            ]
-        }
-    };
-
-    let indices = match enemy_type {
-        crate::components::EnemyType::Hunter => vec![
-            0, 1, 1, 3, 3, 2, 2, 0, // Main body outline
-        ],
-        crate::components::EnemyType::Bomber => vec![
-            0, 1, 1, 3, 3, 4, 4, 2, 2, 0, // Main body
-            3, 4, // Connect back wings
-        ],
-        crate::components::EnemyType::Interceptor => vec![
-            0, 1, 1, 3, 3, 2, 2, 0, // Diamond shape
-        ],
-    };
+    let vertices: Vec<[f32; 3]> = vertices.iter().map(|&[x, y]| [x, y, 0.0]).collect();
+    let indices = lines.to_vec();
 
     let normals = vec![[0.0, 0.0, 1.0]; vertices.len()];
     let uvs: Vec<[f32; 2]> = vertices.iter().map(|_| [0.5, 0.5]).collect();
 
-    ship_mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-    ship_mesh.insert_indices(bevy::render::mesh::Indices::U32(indices));
-    ship_mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-    ship_mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    outline_mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    outline_mesh.insert_indices(bevy::render::mesh::Indices::U32(indices));
+    outline_mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    outline_mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
 
-    ship_mesh
+    outline_mesh
 }
 
 /// Creates a massive boss mesh
-pub fn create_boss_mesh(boss_type: &crate::components::BossType, size_multiplier: f32) -> Mesh {
-    match boss_type {
-        crate::components::BossType::GiantAsteroid => {
+pub fn create_boss_mesh(
+    shape: &crate::resources::BossShape,
+    size_multiplier: f32,
+    rng: &mut fastrand::Rng,
+) -> Mesh {
+    match shape {
+        crate::resources::BossShape::Asteroid { segments, radius } => {
             // Create a large, irregular asteroid
-            create_asteroid_mesh(15, 80.0 * size_multiplier)
+            create_asteroid_mesh(*segments, radius * size_multiplier, rng)
         }
-        crate::components::BossType::AlienMothership => {
-            create_mothership_mesh(size_multiplier)
+        crate::resources::BossShape::Mothership => create_mothership_mesh(size_multiplier),
+        crate::resources::BossShape::Outline { vertices, lines } => {
+            let scaled: Vec<[f32; 2]> = vertices
+                .iter()
+                .map(|&[x, y]| [x * size_multiplier, y * size_multiplier])
+                .collect();
+            create_outline_mesh(&scaled, lines)
         }
     }
 }
@@ -1,5 +1,7 @@
 pub mod meshes;
 pub mod meshes_powerups;
+pub mod tuning_file;
 
 pub use meshes::*;
 pub use meshes_powerups::*;
+pub use tuning_file::*;
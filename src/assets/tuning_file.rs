@@ -0,0 +1,34 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+
+/// Raw text of a tuning file. Parsing is left to the resource the file
+/// describes, so a broken file is reported with the same errors as at startup.
+#[derive(Asset, TypePath, Debug)]
+pub struct TuningFile(pub String);
+
+#[derive(Default)]
+pub struct TuningFileLoader;
+
+impl AssetLoader for TuningFileLoader {
+    type Asset = TuningFile;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<TuningFile, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        Ok(TuningFile(text))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
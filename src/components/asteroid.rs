@@ -1,13 +1,28 @@
+use crate::components::{Collider, CollisionLayers};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Clone, Debug, PartialEq)]
+#[derive(Component, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum AsteroidType {
+    #[default]
     Normal,
     Ice,    // Shatters into more pieces
     Metal,  // Requires more hits, gives more points
     Crystal, // Rare, gives power-ups when destroyed
 }
 
+impl AsteroidType {
+    /// Stable name for save files, the same one serde uses
+    pub fn key(&self) -> &'static str {
+        match self {
+            AsteroidType::Normal => "Normal",
+            AsteroidType::Ice => "Ice",
+            AsteroidType::Metal => "Metal",
+            AsteroidType::Crystal => "Crystal",
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Asteroid {
     pub size: u32,
@@ -38,6 +53,13 @@ impl Asteroid {
         }
     }
 
+    pub fn collider(&self) -> Collider {
+        Collider::circle(self.size as f32 * 5.0 + 5.0).with_layers(
+            CollisionLayers::ASTEROID,
+            CollisionLayers::PLAYER | CollisionLayers::PLAYER_BULLET | CollisionLayers::LASER,
+        )
+    }
+
     pub fn fragment_count(&self) -> u32 {
         match self.asteroid_type {
             AsteroidType::Normal => if self.size >= 5 { 3 } else { 2 },
@@ -1,92 +1,142 @@
+use crate::components::{Collider, CollisionLayers};
+use crate::resources::{AttackOrder, AttackPattern, BossDefinition, BossPhase, DifficultySettings};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Id of a boss definition in `tuning/bosses/`, e.g. "GiantAsteroid"
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BossType(pub String);
+
+impl fmt::Display for BossType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
 
-#[derive(Component, Clone, Debug, PartialEq)]
-pub enum BossType {
-    GiantAsteroid,   // Huge asteroid with multiple phases
-    AlienMothership, // Alien ship with different attack patterns
+/// A boss's progress through its fight, kept with a suspended run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BossState {
+    pub phase: u32,
+    pub phase_health: u32,
+    pub attack_timer: Duration,
+    pub transition_timer: Option<Duration>,
+    pub health_multiplier: f32,
+    pub next_attack: usize,
 }
 
 #[derive(Component)]
 pub struct Boss {
     pub boss_type: BossType,
+    pub definition: Arc<BossDefinition>,
     pub phase: u32,
     pub max_phases: u32,
     pub phase_health: u32,
-    pub max_phase_health: u32,
     pub attack_timer: Timer,
     pub phase_transition_timer: Option<Timer>,
     pub size_multiplier: f32,
+    health_multiplier: f32,
+    // Position in the phase's attack list for `AttackOrder::Sequence`
+    next_attack: usize,
 }
 
 impl Boss {
-    pub fn new(boss_type: BossType) -> Self {
-        let (max_phases, phase_health, size_multiplier) = match boss_type {
-            BossType::GiantAsteroid => (4, 30, 3.5), // More phases and health
-            BossType::AlienMothership => (5, 25, 3.0), // Even more phases
-        };
-
+    pub fn new(definition: Arc<BossDefinition>) -> Self {
+        let first = &definition.phases[0];
         Self {
-            boss_type,
+            boss_type: definition.id.clone(),
             phase: 1,
-            max_phases,
-            phase_health,
-            max_phase_health: phase_health,
-            attack_timer: Timer::from_seconds(1.5, TimerMode::Repeating), // Faster initial attacks
+            max_phases: definition.phases.len() as u32,
+            phase_health: first.health,
+            attack_timer: Timer::from_seconds(first.attack_interval, TimerMode::Repeating),
             phase_transition_timer: None,
-            size_multiplier,
+            size_multiplier: definition.size,
+            health_multiplier: 1.0,
+            next_attack: 0,
+            definition,
         }
     }
 
-    pub fn new_with_difficulty(boss_type: BossType, difficulty: &crate::resources::DifficultySettings) -> Self {
-        let (max_phases, base_phase_health, size_multiplier) = match boss_type {
-            BossType::GiantAsteroid => (4, 30, 3.5), // More phases and health
-            BossType::AlienMothership => (5, 25, 3.0), // Even more phases
-        };
-
-        let phase_health = (base_phase_health as f32 * difficulty.boss_health_multiplier).round() as u32;
+    pub fn new_with_difficulty(
+        definition: Arc<BossDefinition>,
+        difficulty: &DifficultySettings,
+    ) -> Self {
+        let mut boss = Self::new(definition);
+        boss.health_multiplier = difficulty.boss_health_multiplier;
+        boss.phase_health = boss.scaled_health();
+        boss
+    }
 
-        Self {
-            boss_type,
-            phase: 1,
-            max_phases,
-            phase_health,
-            max_phase_health: phase_health,
-            attack_timer: Timer::from_seconds(1.5, TimerMode::Repeating), // Faster initial attacks
-            phase_transition_timer: None,
-            size_multiplier,
+    pub fn state(&self) -> BossState {
+        BossState {
+            phase: self.phase,
+            phase_health: self.phase_health,
+            attack_timer: self.attack_timer.elapsed(),
+            transition_timer: self
+                .phase_transition_timer
+                .as_ref()
+                .map(|timer| timer.elapsed()),
+            health_multiplier: self.health_multiplier,
+            next_attack: self.next_attack,
         }
     }
 
+    /// A boss of `definition` in the middle of its fight. A phase the
+    /// definition no longer has falls back to its last one.
+    pub fn from_state(definition: Arc<BossDefinition>, state: &BossState) -> Self {
+        let mut boss = Self::new(definition);
+        boss.phase = state.phase.clamp(1, boss.max_phases);
+        boss.phase_health = state.phase_health;
+        boss.health_multiplier = state.health_multiplier;
+        boss.next_attack = state.next_attack;
+        boss.attack_timer =
+            Timer::from_seconds(boss.current_phase().attack_interval, TimerMode::Repeating);
+        boss.attack_timer.set_elapsed(state.attack_timer);
+        boss.phase_transition_timer = state.transition_timer.map(|elapsed| {
+            let mut timer = Timer::from_seconds(boss.definition.transition_time, TimerMode::Once);
+            timer.set_elapsed(elapsed);
+            timer
+        });
+        boss
+    }
+
+    /// The definition of the phase the boss is in
+    pub fn current_phase(&self) -> &BossPhase {
+        &self.definition.phases[self.phase as usize - 1]
+    }
+
+    fn scaled_health(&self) -> u32 {
+        ((self.current_phase().health as f32 * self.health_multiplier).round() as u32).max(1)
+    }
+
     pub fn points_value(&self) -> u32 {
-        let base_points = match self.boss_type {
-            BossType::GiantAsteroid => 1500, // Increased rewards
-            BossType::AlienMothership => 2000,
-        };
-        base_points * self.phase
+        self.current_phase().points
+    }
+
+    pub fn collider(&self) -> Collider {
+        Collider::circle(60.0 * self.size_multiplier)
+            .with_layers(CollisionLayers::BOSS, CollisionLayers::PLAYER_BULLET)
     }
 
     pub fn get_color(&self) -> Color {
-        let intensity = 2.5 + (self.phase as f32 * 0.7); // More dramatic phase changes
-        match self.boss_type {
-            BossType::GiantAsteroid => {
-                Color::srgb(intensity * 1.4, intensity * 0.8, intensity * 0.4)
-            }
-            BossType::AlienMothership => {
-                Color::srgb(intensity * 0.8, intensity * 1.5, intensity * 2.2)
-            }
-        }
+        let [r, g, b] = self.current_phase().color;
+        Color::srgb(r, g, b)
     }
 
     pub fn advance_phase(&mut self) -> bool {
         if self.phase < self.max_phases {
             self.phase += 1;
-            self.phase_health = self.max_phase_health + (self.phase * 5); // Increase health each phase
-            self.phase_transition_timer = Some(Timer::from_seconds(2.5, TimerMode::Once));
-
-            // Dramatically increase attack speed with each phase
-            let new_attack_interval = 1.5 / (self.phase as f32 * 0.8 + 1.0);
+            self.phase_health = self.scaled_health();
+            self.phase_transition_timer = Some(Timer::from_seconds(
+                self.definition.transition_time,
+                TimerMode::Once,
+            ));
             self.attack_timer =
-                Timer::from_seconds(new_attack_interval.max(0.3), TimerMode::Repeating);
+                Timer::from_seconds(self.current_phase().attack_interval, TimerMode::Repeating);
+            self.next_attack = 0;
 
             true
         } else {
@@ -97,67 +147,43 @@ impl Boss {
     pub fn is_in_transition(&self) -> bool {
         self.phase_transition_timer
             .as_ref()
-            .map_or(false, |timer| !timer.finished())
+            .is_some_and(|timer| !timer.finished())
     }
 
-    /// Get the current attack pattern based on boss type and phase
-    pub fn get_current_attack_pattern(&self) -> AttackPattern {
-        match self.boss_type {
-            BossType::GiantAsteroid => {
-                match self.phase {
-                    1 => AttackPattern::AsteroidRain,
-                    2 => AttackPattern::CircularShot,
-                    3 => AttackPattern::SpawnMinions,
-                    _ => AttackPattern::TargetedBarrage, // Final phase
-                }
+    /// Picks the next attack of the current phase. A weighted phase with a
+    /// single attack takes no random number.
+    pub fn next_attack_pattern(&mut self, rng: &mut fastrand::Rng) -> AttackPattern {
+        let definition = self.definition.clone();
+        let phase = &definition.phases[self.phase as usize - 1];
+
+        let attack = match phase.order {
+            AttackOrder::Sequence => {
+                let attack = &phase.attacks[self.next_attack % phase.attacks.len()];
+                self.next_attack = (self.next_attack + 1) % phase.attacks.len();
+                attack
             }
-            BossType::AlienMothership => {
-                match self.phase {
-                    1 => AttackPattern::TargetedBarrage,
-                    2 => AttackPattern::SpawnMinions,
-                    3 => AttackPattern::CircularShot,
-                    4 => AttackPattern::AsteroidRain,
-                    _ => AttackPattern::TargetedBarrage, // Final desperate attacks
+            AttackOrder::Weighted => {
+                let candidates: Vec<_> = phase
+                    .attacks
+                    .iter()
+                    .filter(|attack| attack.weight > 0.0)
+                    .collect();
+                let mut chosen = candidates[candidates.len() - 1];
+                if candidates.len() > 1 {
+                    let total: f32 = candidates.iter().map(|attack| attack.weight).sum();
+                    let mut roll = rng.f32() * total;
+                    for attack in &candidates {
+                        if roll < attack.weight {
+                            chosen = attack;
+                            break;
+                        }
+                        roll -= attack.weight;
+                    }
                 }
+                chosen
             }
-        }
-    }
-
-    /// Returns the movement pattern for the boss
-    pub fn get_movement_speed(&self) -> f32 {
-        match self.boss_type {
-            BossType::GiantAsteroid => 20.0 + (self.phase as f32 * 5.0), // Gradually faster
-            BossType::AlienMothership => 30.0 + (self.phase as f32 * 8.0), // More agile
-        }
-    }
-}
-
-#[derive(Component)]
-pub struct BossAttackPattern {
-    pub pattern_type: AttackPattern,
-    pub pattern_timer: Timer,
-}
-
-#[derive(Clone, Debug, PartialEq)]
-pub enum AttackPattern {
-    CircularShot,    // Shoots bullets in all directions
-    TargetedBarrage, // Multiple shots at player
-    SpawnMinions,    // Spawns enemy ships
-    AsteroidRain,    // Spawns asteroids around the boss
-}
-
-impl BossAttackPattern {
-    pub fn new(pattern_type: AttackPattern) -> Self {
-        let duration = match pattern_type {
-            AttackPattern::CircularShot => 1.0,
-            AttackPattern::TargetedBarrage => 3.0,
-            AttackPattern::SpawnMinions => 2.0,
-            AttackPattern::AsteroidRain => 4.0,
         };
 
-        Self {
-            pattern_type,
-            pattern_timer: Timer::from_seconds(duration, TimerMode::Once),
-        }
+        attack.pattern.clone()
     }
 }
@@ -1,4 +1,18 @@
+use crate::components::{Collider, CollisionLayers};
 use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct Bullet;
+
+impl Bullet {
+    // Thin capsule along the arrow mesh
+    pub fn collider() -> Collider {
+        Collider::capsule(3.0, 1.0).with_layers(
+            CollisionLayers::PLAYER_BULLET,
+            CollisionLayers::ASTEROID
+                | CollisionLayers::ENEMY
+                | CollisionLayers::BOSS
+                | CollisionLayers::ENEMY_BULLET,
+        )
+    }
+}
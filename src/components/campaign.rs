@@ -0,0 +1,16 @@
+use crate::components::{Collider, CollisionLayers};
+use bevy::prelude::*;
+
+/// The point a "Protect" campaign level asks the player to defend. Its
+/// `Health` drops whenever an asteroid or enemy bullet reaches it.
+#[derive(Component)]
+pub struct Beacon;
+
+impl Beacon {
+    pub fn collider(radius: f32) -> Collider {
+        Collider::circle(radius).with_layers(
+            CollisionLayers::NONE,
+            CollisionLayers::ASTEROID | CollisionLayers::ENEMY_BULLET,
+        )
+    }
+}
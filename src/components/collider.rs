@@ -0,0 +1,255 @@
+use bevy::prelude::*;
+use std::ops::{BitAnd, BitOr};
+
+/// Collision layer bits. A collider sits on its `layers` and only reports
+/// hits against colliders on one of the layers in its `mask`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CollisionLayers(pub u32);
+
+impl CollisionLayers {
+    pub const NONE: Self = Self(0);
+    pub const PLAYER: Self = Self(1 << 0);
+    pub const ASTEROID: Self = Self(1 << 1);
+    pub const ENEMY: Self = Self(1 << 2);
+    pub const BOSS: Self = Self(1 << 3);
+    pub const PLAYER_BULLET: Self = Self(1 << 4);
+    pub const ENEMY_BULLET: Self = Self(1 << 5);
+    pub const POWER_UP: Self = Self(1 << 6);
+    pub const LASER: Self = Self(1 << 7);
+
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for CollisionLayers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitAnd for CollisionLayers {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+/// Shape in the entity's local space; scale, rotation and translation come
+/// from its `Transform`. A non-uniform scale grows the rounding radius by the
+/// larger axis.
+#[derive(Clone, Debug)]
+pub enum ColliderShape {
+    Circle {
+        radius: f32,
+    },
+    /// Segment along the local Y axis, rounded by `radius`
+    Capsule {
+        half_length: f32,
+        radius: f32,
+    },
+    /// Convex polygon, vertices in winding order
+    ConvexPolygon {
+        points: Vec<Vec2>,
+    },
+}
+
+/// Collision shape and layers, attached when an entity is spawned
+#[derive(Component, Clone, Debug)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    pub layers: CollisionLayers,
+    pub mask: CollisionLayers,
+}
+
+impl Collider {
+    pub fn circle(radius: f32) -> Self {
+        Self::from_shape(ColliderShape::Circle { radius })
+    }
+
+    pub fn capsule(half_length: f32, radius: f32) -> Self {
+        Self::from_shape(ColliderShape::Capsule {
+            half_length,
+            radius,
+        })
+    }
+
+    pub fn convex_polygon(points: Vec<Vec2>) -> Self {
+        Self::from_shape(ColliderShape::ConvexPolygon { points })
+    }
+
+    fn from_shape(shape: ColliderShape) -> Self {
+        Self {
+            shape,
+            layers: CollisionLayers::NONE,
+            mask: CollisionLayers::NONE,
+        }
+    }
+
+    pub fn with_layers(mut self, layers: CollisionLayers, mask: CollisionLayers) -> Self {
+        self.layers = layers;
+        self.mask = mask;
+        self
+    }
+
+    /// Radius of a circle around the entity's position that contains the
+    /// placed shape
+    pub fn bounding_radius(&self, transform: &Transform) -> f32 {
+        let local = match &self.shape {
+            ColliderShape::Circle { radius } => *radius,
+            ColliderShape::Capsule {
+                half_length,
+                radius,
+            } => half_length + radius,
+            ColliderShape::ConvexPolygon { points } => points
+                .iter()
+                .map(|point| point.length())
+                .fold(0.0, f32::max),
+        };
+        local * max_scale(transform)
+    }
+
+    /// Exact overlap test between two placed shapes. Layers are filtered by
+    /// the broad phase, so they are not checked again here.
+    pub fn intersects(
+        &self,
+        transform: &Transform,
+        other: &Collider,
+        other_transform: &Transform,
+    ) -> bool {
+        let (core, radius) = self.world_core(transform);
+        let (other_core, other_radius) = other.world_core(other_transform);
+        core_distance(&core, &other_core) <= radius + other_radius
+    }
+
+    // Every shape is a point, segment or polygon "core" grown by a radius
+    fn world_core(&self, transform: &Transform) -> (Core, f32) {
+        let center = transform.translation.truncate();
+        let scale = transform.scale.truncate();
+        let to_world =
+            |point: Vec2| center + (transform.rotation * (point * scale).extend(0.0)).truncate();
+        let grow = max_scale(transform);
+
+        match &self.shape {
+            ColliderShape::Circle { radius } => (Core::Point(center), radius * grow),
+            ColliderShape::Capsule {
+                half_length,
+                radius,
+            } => (
+                Core::Segment([
+                    to_world(Vec2::new(0.0, -half_length)),
+                    to_world(Vec2::new(0.0, *half_length)),
+                ]),
+                radius * grow,
+            ),
+            ColliderShape::ConvexPolygon { points } => (
+                Core::Polygon(points.iter().map(|point| to_world(*point)).collect()),
+                0.0,
+            ),
+        }
+    }
+}
+
+fn max_scale(transform: &Transform) -> f32 {
+    transform.scale.x.abs().max(transform.scale.y.abs())
+}
+
+enum Core {
+    Point(Vec2),
+    Segment([Vec2; 2]),
+    Polygon(Vec<Vec2>),
+}
+
+impl Core {
+    fn points(&self) -> &[Vec2] {
+        match self {
+            Core::Point(point) => std::slice::from_ref(point),
+            Core::Segment(ends) => ends,
+            Core::Polygon(points) => points,
+        }
+    }
+
+    // Edges as segments; a point is a zero-length edge and a polygon is closed
+    fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let points = self.points();
+        let count = match self {
+            Core::Point(_) | Core::Segment(_) => 1,
+            Core::Polygon(points) => points.len(),
+        };
+        (0..count).map(move |index| (points[index], points[(index + 1) % points.len()]))
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        let Core::Polygon(points) = self else {
+            return false;
+        };
+        if points.len() < 3 {
+            return false;
+        }
+
+        // Inside a convex polygon the point is on the same side of every edge
+        let mut sign = 0.0;
+        for (start, end) in self.edges() {
+            let side = (end - start).perp_dot(point - start);
+            if side != 0.0 {
+                if sign * side < 0.0 {
+                    return false;
+                }
+                sign = side;
+            }
+        }
+        true
+    }
+}
+
+// Distance between two cores; zero when they overlap
+fn core_distance(a: &Core, b: &Core) -> f32 {
+    if b.points().iter().any(|point| a.contains(*point))
+        || a.points().iter().any(|point| b.contains(*point))
+    {
+        return 0.0;
+    }
+
+    a.edges()
+        .flat_map(|edge_a| {
+            b.edges()
+                .map(move |edge_b| segment_distance(edge_a, edge_b))
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn segment_distance((a_start, a_end): (Vec2, Vec2), (b_start, b_end): (Vec2, Vec2)) -> f32 {
+    if segments_cross(a_start, a_end, b_start, b_end) {
+        return 0.0;
+    }
+
+    point_segment_distance(a_start, b_start, b_end)
+        .min(point_segment_distance(a_end, b_start, b_end))
+        .min(point_segment_distance(b_start, a_start, a_end))
+        .min(point_segment_distance(b_end, a_start, a_end))
+}
+
+fn segments_cross(a_start: Vec2, a_end: Vec2, b_start: Vec2, b_end: Vec2) -> bool {
+    let a = a_end - a_start;
+    let b = b_end - b_start;
+    let side_b_start = a.perp_dot(b_start - a_start);
+    let side_b_end = a.perp_dot(b_end - a_start);
+    let side_a_start = b.perp_dot(a_start - b_start);
+    let side_a_end = b.perp_dot(a_end - b_start);
+
+    side_b_start * side_b_end < 0.0 && side_a_start * side_a_end < 0.0
+}
+
+fn point_segment_distance(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0.0 {
+        return point.distance(start);
+    }
+
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
@@ -1,100 +1,79 @@
+use crate::components::{Collider, CollisionLayers};
+use crate::resources::{DifficultySettings, EnemyArchetype};
 use bevy::prelude::*;
-
-#[derive(Component, Clone, Debug, PartialEq)]
-pub enum EnemyType {
-    Hunter,   // Basic AI ship that hunts the player
-    Bomber,   // Shoots explosive projectiles
-    Interceptor, // Fast ship that tries to ram the player
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// Id of an enemy archetype in `enemies.json`, e.g. "Hunter"
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnemyType(pub String);
+
+impl fmt::Display for EnemyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
 #[derive(Component)]
 pub struct Enemy {
     pub enemy_type: EnemyType,
+    pub archetype: Arc<EnemyArchetype>,
     pub speed: f32,
     pub last_shot_time: f32,
     pub shot_cooldown: f32,
 }
 
 impl Enemy {
-    pub fn new(enemy_type: EnemyType) -> Self {
-        let (speed, shot_cooldown) = match enemy_type {
-            EnemyType::Hunter => (80.0, 1.5),   // Faster shooting
-            EnemyType::Bomber => (60.0, 2.5),   // Slower but powerful
-            EnemyType::Interceptor => (150.0, 4.0), // Fast but shoots rarely
-        };
-
+    pub fn new(archetype: Arc<EnemyArchetype>) -> Self {
         Self {
-            enemy_type,
-            speed,
+            enemy_type: archetype.id.clone(),
+            speed: archetype.speed,
             last_shot_time: 0.0,
-            shot_cooldown,
+            shot_cooldown: archetype.shot_cooldown,
+            archetype,
         }
     }
 
-    pub fn new_with_difficulty(enemy_type: EnemyType, difficulty: &crate::resources::DifficultySettings) -> Self {
-        let (base_speed, shot_cooldown) = match enemy_type {
-            EnemyType::Hunter => (80.0, 1.5),   // Faster shooting
-            EnemyType::Bomber => (60.0, 2.5),   // Slower but powerful
-            EnemyType::Interceptor => (150.0, 4.0), // Fast but shoots rarely
-        };
-
-        Self {
-            enemy_type,
-            speed: base_speed * difficulty.enemy_speed_multiplier,
-            last_shot_time: 0.0,
-            shot_cooldown: shot_cooldown / difficulty.enemy_speed_multiplier.max(0.5), // Faster shooting on higher difficulty
-        }
+    pub fn new_with_difficulty(archetype: Arc<EnemyArchetype>, difficulty: &DifficultySettings) -> Self {
+        let mut enemy = Self::new(archetype);
+        enemy.speed *= difficulty.enemy_speed_multiplier;
+        enemy.shot_cooldown /= difficulty.enemy_speed_multiplier.max(0.5); // Faster shooting on higher difficulty
+        enemy
     }
 
     pub fn points_value(&self) -> u32 {
-        match self.enemy_type {
-            EnemyType::Hunter => 150,    // Increased points
-            EnemyType::Bomber => 250,    // Higher reward for tougher enemy
-            EnemyType::Interceptor => 200, // Fast and dangerous
-        }
+        self.archetype.points
     }
 
     pub fn max_health(&self) -> u32 {
-        match self.enemy_type {
-            EnemyType::Hunter => 2,
-            EnemyType::Bomber => 4,      // Increased health for bomber
-            EnemyType::Interceptor => 1, // Fast but fragile
-        }
+        self.archetype.health
+    }
+
+    /// Never below 1, however low the multiplier, so the enemy can still be killed
+    pub fn max_health_with_difficulty(&self, difficulty: &DifficultySettings) -> u32 {
+        ((self.archetype.health as f32 * difficulty.enemy_health_multiplier).round() as u32).max(1)
     }
 
-    pub fn max_health_with_difficulty(&self, difficulty: &crate::resources::DifficultySettings) -> u32 {
-        let base_health = match self.enemy_type {
-            EnemyType::Hunter => 2,
-            EnemyType::Bomber => 4,      // Increased health for bomber
-            EnemyType::Interceptor => 1, // Fast but fragile
-        };
-        (base_health as f32 * difficulty.enemy_health_multiplier).round() as u32
+    pub fn collider(&self) -> Collider {
+        Collider::circle(self.archetype.collider_radius)
+            .with_layers(CollisionLayers::ENEMY, CollisionLayers::PLAYER_BULLET)
     }
 
     pub fn get_color(&self) -> Color {
-        match self.enemy_type {
-            EnemyType::Hunter => Color::srgb(5.0, 1.0, 1.0),   // Much brighter red with bloom
-            EnemyType::Bomber => Color::srgb(5.0, 3.0, 1.0),   // Much brighter orange with bloom
-            EnemyType::Interceptor => Color::srgb(1.0, 5.0, 1.0), // Much brighter green with bloom
-        }
+        let [r, g, b] = self.archetype.color;
+        Color::srgb(r, g, b)
     }
 
     /// Get the preferred engagement range for this enemy type
     pub fn get_engagement_range(&self) -> f32 {
-        match self.enemy_type {
-            EnemyType::Hunter => 300.0,    // Medium range hunter
-            EnemyType::Bomber => 400.0,    // Long range bomber
-            EnemyType::Interceptor => 150.0, // Close range interceptor
-        }
+        self.archetype.engagement_range
     }
 
     /// Get the evasion tendency for this enemy type
     pub fn get_evasion_factor(&self) -> f32 {
-        match self.enemy_type {
-            EnemyType::Hunter => 0.3,      // Moderate evasion
-            EnemyType::Bomber => 0.1,      // Low evasion, relies on armor
-            EnemyType::Interceptor => 0.7, // High evasion, hit and run
-        }
+        self.archetype.evasion
     }
 }
 
@@ -105,14 +84,23 @@ pub struct EnemyBullet {
 }
 
 impl EnemyBullet {
-    pub fn new(enemy_type: &EnemyType) -> Self {
-        let (damage, is_explosive) = match enemy_type {
-            EnemyType::Hunter => (1, false),
-            EnemyType::Bomber => (2, true),
-            EnemyType::Interceptor => (1, false),
-        };
+    pub fn new(archetype: &EnemyArchetype) -> Self {
+        Self {
+            damage: archetype.bullet.damage,
+            is_explosive: archetype.bullet.explosive,
+        }
+    }
 
-        Self { damage, is_explosive }
+    pub fn color(archetype: &EnemyArchetype) -> Color {
+        let [r, g, b] = archetype.bullet.color;
+        Color::srgb(r, g, b)
+    }
+
+    pub fn collider() -> Collider {
+        Collider::circle(7.0).with_layers(
+            CollisionLayers::ENEMY_BULLET,
+            CollisionLayers::PLAYER | CollisionLayers::PLAYER_BULLET,
+        )
     }
 }
 
@@ -141,7 +129,7 @@ pub struct AIBehavior {
     pub state: AIState,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum AIState {
     Hunting,     // Actively pursuing the player
     Attacking,   // In combat range, aggressive
@@ -160,47 +148,15 @@ impl AIBehavior {
         }
     }
 
-    /// Update AI behavior based on distance to player and enemy type
-    pub fn update_behavior(&mut self, distance_to_player: f32, enemy_type: &EnemyType, player_velocity: Vec2) {
-        let engagement_range = match enemy_type {
-            EnemyType::Hunter => 300.0,
-            EnemyType::Bomber => 400.0,
-            EnemyType::Interceptor => 150.0,
-        };
-
-        // State machine logic based on distance and enemy type
-        self.state = match enemy_type {
-            EnemyType::Hunter => {
-                if distance_to_player > engagement_range * 1.5 {
-                    AIState::Hunting
-                } else if distance_to_player > engagement_range * 0.8 {
-                    AIState::Attacking
-                } else if player_velocity.length() > 100.0 {
-                    AIState::Evading
-                } else {
-                    AIState::Circling
-                }
-            },
-            EnemyType::Bomber => {
-                if distance_to_player > engagement_range {
-                    AIState::Hunting
-                } else if distance_to_player > engagement_range * 0.6 {
-                    AIState::Attacking
-                } else {
-                    AIState::Retreating // Bombers prefer long range
-                }
-            },
-            EnemyType::Interceptor => {
-                if distance_to_player > engagement_range * 2.0 {
-                    AIState::Ambushing
-                } else if distance_to_player > engagement_range {
-                    AIState::Hunting
-                } else if distance_to_player < engagement_range * 0.5 {
-                    AIState::Retreating
-                } else {
-                    AIState::Attacking
-                }
-            }
-        };
+    /// Update AI behavior from the archetype's rules, based on distance to player
+    pub fn update_behavior(&mut self, distance_to_player: f32, archetype: &EnemyArchetype, player_velocity: Vec2) {
+        let player_speed = player_velocity.length();
+        if let Some(rule) = archetype
+            .ai
+            .iter()
+            .find(|rule| rule.matches(distance_to_player, archetype.engagement_range, player_speed))
+        {
+            self.state = rule.state.clone();
+        }
     }
 }
@@ -20,7 +20,6 @@ impl Health {
         self.current == 0
     }
 
-    #[allow(dead_code)]
     pub fn is_dead(&self) -> bool {
         self.current == 0
     }
@@ -1,3 +1,4 @@
+use crate::resources::{DifficultyField, DifficultyId, InputAction};
 use bevy::prelude::*;
 
 #[derive(Component, Clone)]
@@ -9,6 +10,18 @@ pub struct DifficultyMenu;
 #[derive(Component, Clone)]
 pub struct GameOverMenu;
 
+#[derive(Component, Clone)]
+pub struct LevelSelectMenu;
+
+#[derive(Component, Clone)]
+pub struct LeaderboardMenu;
+
+#[derive(Component, Clone)]
+pub struct StatsMenu;
+
+#[derive(Component, Clone)]
+pub struct AchievementsMenu;
+
 #[derive(Component)]
 pub struct MenuButton {
     #[allow(dead_code)]
@@ -19,16 +32,42 @@ pub struct MenuButton {
 pub enum MenuAction {
     #[allow(dead_code)]
     StartGame,
+    /// Pick up the suspended run
+    Continue,
     DifficultySelect,
+    Campaign,
+    StartLevel(String),
+    NextLevel,
+    LevelSelect,
+    TitleScreen,
+    Leaderboards,
+    NextLeaderboard,
+    Stats,
+    Achievements,
     Settings,
-    SetDifficulty(String), // difficulty name
+    SetDifficulty(DifficultyId),
+    CustomDifficulty,
+    EditSeed,
+    /// Leave the run report for the game-over buttons
+    GameOverOptions,
+    /// Write the run report next to the save file
+    ExportReport,
     PlayAgain,
+    WatchReplay,
     QuitToMenu,
     Exit,
     Resume,
     MainMenu,
+    /// Suspend the run to disk and leave for the main menu
+    SaveAndQuit,
     BloomToggle,
     VsyncToggle,
+    Controls,
+    /// Wait for a key to bind to the action; clicked again, stop waiting
+    Rebind(InputAction),
+    ResetControls,
+    /// Switch the gamepad stick between rotating and aiming the ship
+    StickModeToggle,
     Back,
 }
 
@@ -38,3 +77,41 @@ pub struct GameUI;
 
 #[derive(Component)]
 pub struct MenuText;
+
+// Shows the seed typed on the difficulty menu
+#[derive(Component)]
+pub struct SeedEntryText;
+
+// Name being typed for a new high score on the game-over screen
+#[derive(Component)]
+pub struct NameEntryText;
+
+// The board shown on the leaderboard screen
+#[derive(Component)]
+pub struct LeaderboardText;
+
+// Keys bound to an action on the Controls page
+#[derive(Component)]
+pub struct ControlsBindingText(pub InputAction);
+
+// Gamepad stick mode on the Controls page
+#[derive(Component)]
+pub struct ControlsStickText;
+
+// Hint or prompt line under the bindings on the Controls page
+#[derive(Component)]
+pub struct ControlsStatusText;
+
+// Custom difficulty editor: a track to click or drag along, its knob and
+// the value shown next to it
+#[derive(Component)]
+pub struct DifficultySlider {
+    pub field: DifficultyField,
+    pub width: f32,
+}
+
+#[derive(Component)]
+pub struct DifficultySliderKnob(pub DifficultyField);
+
+#[derive(Component)]
+pub struct DifficultySliderText(pub DifficultyField);
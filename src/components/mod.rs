@@ -3,6 +3,8 @@ pub mod boss;
 pub mod bullet;
 pub mod bullet_lifecycle;
 pub mod button_with_text;
+pub mod campaign;
+pub mod collider;
 pub mod enemy;
 pub mod health;
 pub mod invincibility;
@@ -19,6 +21,8 @@ pub use boss::*;
 pub use bullet::*;
 pub use bullet_lifecycle::*;
 pub use button_with_text::*;
+pub use campaign::*;
+pub use collider::*;
 pub use enemy::*;
 pub use health::*;
 pub use invincibility::*;
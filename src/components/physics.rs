@@ -5,3 +5,25 @@ pub struct Velocity(pub Vec2);
 
 #[derive(Component)]
 pub struct Wraparound;
+
+// Simulated pose before and after the latest fixed tick. Rendering blends
+// between the two; gameplay systems always see `current` in `Transform`.
+#[derive(Component, Clone, Copy)]
+pub struct TransformHistory {
+    pub previous: (Vec3, Quat),
+    pub current: (Vec3, Quat),
+    // Simulated scale; per-frame effects like the pulse only change the
+    // rendered one, so colliders stay the same size every tick
+    pub scale: Vec3,
+}
+
+impl TransformHistory {
+    pub fn new(transform: &Transform) -> Self {
+        let pose = (transform.translation, transform.rotation);
+        Self {
+            previous: pose,
+            current: pose,
+            scale: transform.scale,
+        }
+    }
+}
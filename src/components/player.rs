@@ -1,4 +1,20 @@
+use crate::components::{Collider, CollisionLayers};
 use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct Player;
+
+impl Player {
+    // Matches the triangle ship mesh
+    pub fn collider() -> Collider {
+        Collider::convex_polygon(vec![
+            Vec2::new(0.0, 15.0),
+            Vec2::new(-10.0, -15.0),
+            Vec2::new(10.0, -15.0),
+        ])
+        .with_layers(
+            CollisionLayers::PLAYER,
+            CollisionLayers::ASTEROID | CollisionLayers::ENEMY_BULLET | CollisionLayers::POWER_UP,
+        )
+    }
+}
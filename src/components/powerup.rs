@@ -1,6 +1,9 @@
+use crate::components::{Collider, CollisionLayers};
+use bevy::ecs::entity::EntityHashMap;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Clone, Debug, PartialEq)]
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum PowerUpType {
     MultiShot,
     RapidFire,
@@ -9,11 +12,31 @@ pub enum PowerUpType {
     LaserBeam,
 }
 
+impl PowerUpType {
+    /// Stable name for save files, the same one serde uses
+    pub fn key(&self) -> &'static str {
+        match self {
+            PowerUpType::MultiShot => "MultiShot",
+            PowerUpType::RapidFire => "RapidFire",
+            PowerUpType::PiercingBullets => "PiercingBullets",
+            PowerUpType::ExplosiveBullets => "ExplosiveBullets",
+            PowerUpType::LaserBeam => "LaserBeam",
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct PowerUp {
     pub power_type: PowerUpType,
 }
 
+impl PowerUp {
+    // Generous pickup area around the icon
+    pub fn collider() -> Collider {
+        Collider::circle(20.0).with_layers(CollisionLayers::POWER_UP, CollisionLayers::PLAYER)
+    }
+}
+
 #[derive(Component)]
 pub struct PowerUpEffect {
     pub power_type: PowerUpType,
@@ -71,8 +94,10 @@ impl ExplosiveBullet {
 pub struct LaserBeam {
     pub damage_per_second: f32,
     pub max_range: f32,
-    #[allow(dead_code)]
     pub width: f32,
+    // Damage dealt to each target but not yet paid out as a whole point, so
+    // the total doesn't depend on the tick rate
+    owed: EntityHashMap<f32>,
 }
 
 impl LaserBeam {
@@ -81,8 +106,25 @@ impl LaserBeam {
             damage_per_second,
             max_range,
             width,
+            owed: EntityHashMap::default(),
         }
     }
+
+    /// Burns `target` for `seconds` and returns the whole points of damage
+    /// that adds up to
+    pub fn burn(&mut self, target: Entity, seconds: f32) -> u32 {
+        let owed = self.owed.entry(target).or_default();
+        *owed += self.damage_per_second * seconds;
+        let whole = owed.floor();
+        *owed -= whole;
+        whole as u32
+    }
+
+    // The beam mesh is centred on the entity, so the capsule is too
+    pub fn collider(&self) -> Collider {
+        Collider::capsule(self.max_range / 2.0, self.width / 2.0)
+            .with_layers(CollisionLayers::LASER, CollisionLayers::ASTEROID)
+    }
 }
 
 // Power-up spawn component
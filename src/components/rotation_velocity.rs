@@ -11,9 +11,9 @@ impl RotationVelocity {
         Self { angular_velocity }
     }
 
-    pub fn random_slow() -> Self {
+    pub fn random_slow(rng: &mut fastrand::Rng) -> Self {
         // Random slow rotation between -1.0 and 1.0 radians per second
-        let angular_velocity = (fastrand::f32() - 0.5) * 2.0;
+        let angular_velocity = (rng.f32() - 0.5) * 2.0;
         Self { angular_velocity }
     }
 }
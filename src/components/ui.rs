@@ -6,6 +6,17 @@ pub struct ScoreText;
 #[derive(Component)]
 pub struct LivesText;
 
+// Combo count and multiplier under the lives, with a bar that empties as
+// the combo window runs out
+#[derive(Component)]
+pub struct ComboText;
+
+#[derive(Component)]
+pub struct ComboBar;
+
+#[derive(Component)]
+pub struct ComboBarFill;
+
 #[derive(Component)]
 pub struct HeartUI {
     pub heart_index: usize,
@@ -23,6 +34,24 @@ pub struct BossHealthBarFill;
 #[derive(Component)]
 pub struct BossPhaseText;
 
+// Campaign objective progress at the top of the screen
+#[derive(Component)]
+pub struct ObjectiveText;
+
+// "Wave N" banner shown between authored waves
+#[derive(Component)]
+pub struct WaveBanner;
+
+// Playback status line shown while watching a replay
+#[derive(Component)]
+pub struct ReplayHudText;
+
+// On-screen message from `ShowToast`, removed when the timer runs out
+#[derive(Component)]
+pub struct Toast {
+    pub timer: Timer,
+}
+
 // Menu UI components
 #[derive(Component, Clone)]
 pub struct MenuUI;
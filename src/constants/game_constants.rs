@@ -1,6 +1,10 @@
 pub const PLAYER_SPEED: f32 = 400.0;
 pub const PLAYER_ROTATION_SPEED: f32 = 3.0; // radians per second
+pub const PLAYER_FRICTION: f32 = 0.995; // fraction of velocity kept per 1/60 s
 pub const BULLET_SPEED: f32 = 400.0;
 pub const ASTEROID_SPEED: f32 = 50.0;
 #[allow(dead_code)]
 pub const ASTEROID_ROTATION_SPEED: f32 = 0.5; // radians per second
+pub const COMBO_WINDOW: f32 = 3.0; // seconds after a kill for the next one to extend the combo
+pub const COMBO_KILLS_PER_STEP: u32 = 5; // kills needed for each +1 to the score multiplier
+pub const MAX_SCORE_MULTIPLIER: u32 = 5;
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+/// What dealt a hit, used for scoring and by anything listening to kills
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageKind {
+    Bullet,
+    Laser,
+    Explosion,
+}
+
+impl DamageKind {
+    /// Points for a hit on an asteroid that doesn't destroy it
+    pub fn chip_score(self) -> u32 {
+        match self {
+            DamageKind::Bullet | DamageKind::Explosion => 5,
+            DamageKind::Laser => 1,
+        }
+    }
+}
+
+/// Damage requested by a weapon. Weapons only emit these; `apply_damage`
+/// is the one place that changes `Health`.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: u32,
+    /// Bullet or beam that dealt the damage, if it was an entity
+    pub source: Option<Entity>,
+    pub kind: DamageKind,
+}
+
+/// An asteroid, enemy or boss ran out of health. `handle_destroyed` scores
+/// it, spawns drops and fragments, and despawns it.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Destroyed {
+    pub entity: Entity,
+    pub position: Vec3,
+    // What finished it off
+    pub source: Option<Entity>,
+    pub kind: DamageKind,
+}
@@ -0,0 +1,153 @@
+// Gameplay events for anything that wants to follow a run (stats, audio,
+// analytics, achievements) without touching the systems that cause them.
+//
+// Every event names the entity it is about, where it happened and how much
+// score it earned (`score_delta`, 0 when it earned none). Apart from
+// `RunStarted` and `RunEnded` (sent on entering and leaving
+// `GameState::Playing`) they are sent from `FixedUpdate`, so a listener in
+// `Update` may see several ticks' worth at once.
+
+use crate::components::{AsteroidType, BossType, EnemyType, PowerUpType};
+use crate::events::DamageKind;
+use crate::resources::DifficultyId;
+use bevy::prelude::*;
+
+/// An asteroid was destroyed by a weapon (not by ramming the ship)
+#[derive(Event, Clone, Debug)]
+pub struct AsteroidDestroyed {
+    pub entity: Entity,
+    pub asteroid_type: AsteroidType,
+    pub size: u32,
+    pub position: Vec3,
+    pub score_delta: u32,
+    pub cause: DamageKind,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct EnemyKilled {
+    pub entity: Entity,
+    pub enemy_type: EnemyType,
+    pub position: Vec3,
+    pub score_delta: u32,
+    pub cause: DamageKind,
+}
+
+/// A boss lost all health in one phase and moved on to `phase`
+#[derive(Event, Clone, Debug)]
+pub struct BossPhaseChanged {
+    pub entity: Entity,
+    pub boss_type: BossType,
+    pub phase: u32,
+    pub position: Vec3,
+    pub score_delta: u32,
+}
+
+/// A boss lost all health in its last phase
+#[derive(Event, Clone, Debug)]
+pub struct BossDefeated {
+    pub entity: Entity,
+    pub boss_type: BossType,
+    pub position: Vec3,
+    pub score_delta: u32,
+    pub cause: DamageKind,
+}
+
+/// What hit the ship
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hazard {
+    Asteroid,
+    EnemyBullet,
+}
+
+impl Hazard {
+    /// Stable name for the death counts in save files
+    pub fn key(self) -> &'static str {
+        match self {
+            Hazard::Asteroid => "Asteroid",
+            Hazard::EnemyBullet => "EnemyBullet",
+        }
+    }
+}
+
+/// The ship was hit while not invincible. `entity` is the ship and `source`
+/// the asteroid or bullet that hit it.
+#[derive(Event, Clone, Debug)]
+pub struct PlayerHit {
+    pub entity: Entity,
+    pub source: Entity,
+    pub hazard: Hazard,
+    pub position: Vec3,
+    pub score_delta: u32,
+}
+
+/// The ship lost a life; `game_over` is set when it was the last one
+#[derive(Event, Clone, Debug)]
+pub struct LifeLost {
+    pub entity: Entity,
+    pub hazard: Hazard,
+    pub lives_remaining: u32,
+    pub game_over: bool,
+    pub position: Vec3,
+    pub score_delta: u32,
+}
+
+/// `entity` is the pickup, which is despawned right after
+#[derive(Event, Clone, Debug)]
+pub struct PowerUpCollected {
+    pub entity: Entity,
+    pub power_type: PowerUpType,
+    pub position: Vec3,
+    pub score_delta: u32,
+}
+
+/// The ship fired a bullet; `entity` is the bullet
+#[derive(Event, Clone, Debug)]
+pub struct ShotFired {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub score_delta: u32,
+}
+
+/// A power-up effect ran out. `entity` is the effect and `position` where
+/// the ship was at the time (the origin if it is gone).
+#[derive(Event, Clone, Debug)]
+pub struct PowerUpExpired {
+    pub entity: Entity,
+    pub power_type: PowerUpType,
+    pub position: Vec3,
+    pub score_delta: u32,
+}
+
+/// A run began, including replays and seeking back in a replay. `entity` is
+/// the freshly spawned ship.
+#[derive(Event, Clone, Debug)]
+pub struct RunStarted {
+    pub entity: Entity,
+    pub difficulty: DifficultyId,
+    pub seed: u64,
+    pub replay: bool,
+    pub position: Vec3,
+    pub score_delta: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The last life was lost, or a campaign objective was failed
+    GameOver,
+    /// Every wave, or the campaign objective, was completed
+    Cleared,
+    /// The run was left from the pause menu, or a replay ended or was restarted
+    Quit,
+}
+
+/// A run left `GameState::Playing`. The ship is gone after a game over, so
+/// `entity` and `position` are only set when it was still alive.
+#[derive(Event, Clone, Debug)]
+pub struct RunEnded {
+    pub entity: Option<Entity>,
+    pub outcome: RunOutcome,
+    pub final_score: u32,
+    pub replay: bool,
+    pub position: Option<Vec3>,
+    pub score_delta: u32,
+}
@@ -0,0 +1,8 @@
+use crate::components::MenuAction;
+use bevy::prelude::*;
+
+/// A menu button was clicked, or pressed with a gamepad while focused
+#[derive(Event, Clone, Debug)]
+pub struct MenuButtonPressed {
+    pub action: MenuAction,
+}
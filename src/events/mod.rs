@@ -0,0 +1,9 @@
+pub mod damage;
+pub mod gameplay;
+pub mod menu;
+pub mod toast;
+
+pub use damage::*;
+pub use gameplay::*;
+pub use menu::*;
+pub use toast::*;
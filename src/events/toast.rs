@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+/// Shows a short message at the top of the screen, whatever state the game is in
+#[derive(Event, Clone, Debug)]
+pub struct ShowToast {
+    pub message: String,
+    pub kind: ToastKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+impl ShowToast {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            kind: ToastKind::Info,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            kind: ToastKind::Error,
+        }
+    }
+}
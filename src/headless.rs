@@ -0,0 +1,92 @@
+use crate::resources::*;
+use bevy::{input::InputPlugin, prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use std::time::Duration;
+
+/// Runs the full gameplay schedule without a window or renderer.
+///
+/// Uses `MinimalPlugins` plus the few plugins the gameplay systems depend on
+/// (states, input and mesh/material asset storage). Every update advances
+/// simulated time by exactly one gameplay tick, so runs go as fast as the CPU
+/// allows and start directly in `GameState::Playing`.
+pub struct HeadlessPlugin {
+    pub max_ticks: Option<u32>,
+    pub timestep: Duration,
+}
+
+#[derive(Resource)]
+pub struct HeadlessRun {
+    pub ticks: u32,
+    pub max_ticks: Option<u32>,
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            InputPlugin,
+            AssetPlugin::default(),
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(self.timestep))
+        .insert_resource(HeadlessRun {
+            ticks: 0,
+            max_ticks: self.max_ticks,
+        })
+        .insert_state(GameState::Playing)
+        .add_systems(Update, headless_tick_system)
+        .add_systems(OnEnter(GameState::GameOver), headless_game_over_system);
+    }
+}
+
+// Count simulated ticks and stop once the optional limit is reached
+pub fn headless_tick_system(
+    mut run: ResMut<HeadlessRun>,
+    score: Res<GameScore>,
+    mut exit: EventWriter<AppExit>,
+) {
+    run.ticks += 1;
+
+    if run
+        .max_ticks
+        .is_some_and(|max_ticks| run.ticks >= max_ticks)
+    {
+        println!(
+            "Headless run stopped after {} ticks. Score: {}",
+            run.ticks, score.score
+        );
+        exit.write(AppExit::Success);
+    }
+}
+
+// Report the result and exit when the simulated run ends; a replay whose
+// score does not match the recording exits with an error
+pub fn headless_game_over_system(
+    run: Res<HeadlessRun>,
+    score: Res<GameScore>,
+    verification: Option<Res<ReplayVerification>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    println!(
+        "Headless run finished after {} ticks. Final Score: {}",
+        run.ticks, score.score
+    );
+
+    match verification {
+        Some(verification) if !verification.matches() => {
+            println!(
+                "Replay diverged: recorded score {}, replayed score {}",
+                verification.expected_score, verification.actual_score
+            );
+            exit.write(AppExit::error());
+        }
+        Some(_) => {
+            println!("Replay verified");
+            exit.write(AppExit::Success);
+        }
+        None => {
+            exit.write(AppExit::Success);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+pub mod assets;
+pub mod components;
+pub mod constants;
+pub mod events;
+pub mod headless;
+pub mod plugins;
+pub mod resources;
+pub mod systems;
+
+pub use plugins::*;
@@ -1,17 +1,44 @@
 use bevy::prelude::*;
+use rusty_asteroid::RustyAsteroidPlugin;
+use rusty_asteroid::headless::HeadlessPlugin;
+use rusty_asteroid::resources::*;
+use rusty_asteroid::systems::*;
+use std::path::{Path, PathBuf};
 
-mod assets;
-mod components;
-mod constants;
-mod resources;
-mod systems;
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let mut app = App::new();
 
-use resources::*;
-use systems::*;
+    // A fixed seed makes every run reproducible, e.g. `--seed 12345`
+    let mut game_rng = GameRng::default();
+    game_rng.requested_seed = arg_value(&args, "--seed").and_then(|value| value.parse().ok());
 
-fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
+    // Every run is recorded; `--record run.rpl` picks the file explicitly
+    let replay_recorder = ReplayRecorder {
+        output_path: arg_value(&args, "--record").map(PathBuf::from),
+        ..default()
+    };
+
+    // Save and settings files go to the platform data directory unless
+    // `--data-dir path` says otherwise
+    if let Some(dir) = arg_value(&args, "--data-dir") {
+        set_data_dir(dir);
+    }
+
+    // Gameplay is simulated in fixed ticks, independent of the display rate
+    let (settings, storage_errors) = GameSettings::load();
+    let timestep = settings.simulation.timestep();
+
+    let headless = args.iter().any(|arg| arg == "--headless");
+    if headless {
+        // No window or renderer: step the gameplay schedule as fast as possible
+        let max_ticks = arg_value(&args, "--ticks").and_then(|value| value.parse().ok());
+        app.add_plugins(HeadlessPlugin {
+            max_ticks,
+            timestep,
+        });
+    } else {
+        app.add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "Asteroid Game".into(),
                 resolution: (800.0, 600.0).into(),
@@ -19,133 +46,66 @@ fn main() {
             }),
             ..default()
         }))
-        .init_state::<GameState>()
-        .insert_resource(GameScore { score: 0 })
-        .insert_resource(PlayerLives::default())
-        .insert_resource(DifficultySettings::default())
-        .insert_resource(PlayerPowerUps::default())
-        .insert_resource(PowerUpSpawnTimer::default())
-        .insert_resource(GameSettings::load())
-        .insert_resource(SaveData::load())
-        .insert_resource(AsteroidSizeGenerator::default())
-        .insert_resource(AsteroidTypeGenerator::default())
-        .insert_resource(EnemySpawnTimer::default())
-        .insert_resource(BossSpawnManager::default())
-        .insert_resource(GamePhaseManager::default())
-        .insert_resource(AsteroidSpawnTimer {
-            timer: Timer::from_seconds(2.0, TimerMode::Repeating),
-        })
-        .insert_resource(FireTimer {
-            timer: Timer::from_seconds(0.05, TimerMode::Repeating),
-        })
         // Startup systems
         .add_systems(Startup, setup_camera)
-        // Menu state systems
-        .add_systems(OnEnter(GameState::MainMenu), setup_main_menu_styled)
-        .add_systems(OnExit(GameState::MainMenu), cleanup_styled_menu)
-        .add_systems(OnEnter(GameState::Settings), setup_settings_menu)
-        .add_systems(OnExit(GameState::Settings), cleanup_settings_menu)
-        .add_systems(
-            OnEnter(GameState::DifficultySelect),
-            setup_difficulty_menu_styled,
-        )
-        .add_systems(OnExit(GameState::DifficultySelect), cleanup_styled_menu)
-        .add_systems(OnEnter(GameState::Paused), setup_pause_menu)
-        .add_systems(OnExit(GameState::Paused), cleanup_pause_menu)
-        // Game state systems
-        .add_systems(
-            OnEnter(GameState::Playing),
-            (
-                setup_game,
-                reset_game_resources,
-                reset_powerups_system,
-                reset_game_phase_system,
-            ),
-        )
-        .add_systems(OnExit(GameState::Playing), cleanup_all_entities)
-        .add_systems(
-            Update,
-            (
-                player_movement,
-                enhanced_player_shoot,
-                move_entities,
-                rotate_entities,
-                wrap_around,
-            )
-                .run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            Update,
-            (
-                collision_system,
-                laser_collision_system, // New laser collision system
-                player_asteroid_collision_system,
-                player_enemy_bullet_collision_system, // New player vs enemy bullet collision
-                bullet_enemy_collision_system,        // New bullet vs enemy collision
-                bullet_boss_collision_system,         // New bullet vs boss collision
-                bullet_bullet_collision_system,       // New bullet vs bullet collision
-            )
-                .run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            Update,
-            (
-                invincibility_visual_system,
-                update_bullet_lifecycle,
-                despawn_asteroids,
-                spawn_asteroids,
-            )
-                .run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            Update,
-            (
-                spawn_enemy_system,        // New enemy spawning
-                enemy_ai_system,           // New enemy AI
-                enemy_shooting_system,     // New enemy shooting
-                boss_spawn_system,         // New boss spawning
-                boss_ai_system,            // New boss AI
-                game_phase_manager_system, // Game phase management
-                pulsing_effect_system,     // New pulsing effect for visibility
-            )
-                .run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            Update,
-            (
-                spawn_powerup_system,      // New power-up spawning
-                powerup_collection_system, // New power-up collection
-                powerup_effect_system,     // New power-up effect management
-                update_score_display,
-                update_lives_display,
-                update_heart_display,
-                update_powerup_display,  // New power-up UI
-                spawn_boss_health_bar,   // Boss health bar spawning
-                update_boss_health_bar,  // Boss health bar updates
-                despawn_boss_health_bar, // Boss health bar cleanup
-                save_game_progress,      // Save system
-            )
-                .run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            Update,
-            pause_input_system.run_if(in_state(GameState::Playing).or(in_state(GameState::Paused))),
-        )
-        // Game Over state systems
-        .add_systems(OnEnter(GameState::GameOver), setup_game_over_menu_styled)
-        .add_systems(OnExit(GameState::GameOver), cleanup_styled_menu)
-        // Menu interaction systems (run in all menu states)
-        .add_systems(
-            Update,
-            mesh_menu_button_system.run_if(
-                in_state(GameState::MainMenu)
-                    .or(in_state(GameState::DifficultySelect))
-                    .or(in_state(GameState::GameOver))
-                    .or(in_state(GameState::Paused))
-                    .or(in_state(GameState::Settings)),
-            ),
-        )
-        .add_systems(Update, apply_graphics_settings)
-        .add_systems(OnEnter(GameState::GameOver), save_on_game_over)
-        .run();
+        .add_systems(Update, apply_graphics_settings);
+    }
+
+    app.insert_resource(game_rng)
+        .insert_resource(replay_recorder)
+        .insert_resource(settings)
+        .insert_resource(StorageErrors(storage_errors))
+        .add_plugins(RustyAsteroidPlugin);
+
+    // `--waves training` plays `assets/tuning/waves/training.json` instead
+    // of the endless spawners
+    if let Some(name) = arg_value(&args, "--waves") {
+        app.insert_resource(ActiveWaves {
+            path: wave_file_path(name),
+        });
+    }
+
+    // `--level ore_run` plays a campaign level, on the level's difficulty
+    if let Some(level) = arg_value(&args, "--level") {
+        start_campaign_level(app.world_mut(), level);
+    }
+
+    // `--stress 5000` fills the playfield to benchmark the collision broad phase
+    if let Some(count) = arg_value(&args, "--stress").and_then(|value| value.parse().ok()) {
+        app.insert_resource(StressTest::new(count));
+    }
+
+    // `--headless --replay run.rpl` re-simulates a recording and checks its score
+    if let Some(path) = arg_value(&args, "--replay").filter(|_| headless) {
+        match Replay::load(Path::new(path)) {
+            // A different build can't be expected to reproduce the score
+            Ok(replay) if !replay.matches_game_version() => {
+                eprintln!(
+                    "Replay {} was recorded by version {}, this is {}",
+                    path, replay.game_version, GAME_VERSION
+                );
+                std::process::exit(1);
+            }
+            // Nor can edited tuning files
+            Ok(replay) if !replay.matches_tuning(loaded_tuning_fingerprint(app.world())) => {
+                eprintln!("Replay {} was recorded with different tuning files", path);
+                std::process::exit(1);
+            }
+            Ok(replay) => start_replay_playback(app.world_mut(), replay),
+            Err(error) => {
+                eprintln!("Failed to load replay {}: {}", path, error);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    app.run();
+}
+
+// Returns the value following `flag` on the command line, e.g. `--ticks 600`
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
 }
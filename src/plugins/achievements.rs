@@ -0,0 +1,31 @@
+use crate::headless::HeadlessRun;
+use crate::plugins::FrameSet;
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Achievements from `achievements.json`, unlocked from the run and lifetime
+/// stats and kept in `SaveData`. Nothing unlocks without `PersistencePlugin`,
+/// which keeps the stats, nor in headless runs and replays.
+pub struct AchievementPlugin;
+
+impl Plugin for AchievementPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<Achievements>() {
+            app.insert_resource(Achievements::load());
+        }
+
+        app.add_systems(
+            Update,
+            check_achievements
+                .after(track_game_stats)
+                .in_set(FrameSet::Persistence)
+                .run_if(
+                    in_state(GameState::Playing)
+                        .and(resource_exists::<RunStats>)
+                        .and(not(resource_exists::<HeadlessRun>))
+                        .and(not(resource_exists::<ReplayPlayback>)),
+                ),
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use crate::plugins::{FrameSet, GameplaySet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Boss fights, the game phases that lead up to them and the boss health bar
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<BossDefinitions>() {
+            let bosses = BossDefinitions::load(app.world().resource::<EnemyArchetypes>());
+            app.insert_resource(bosses);
+        }
+
+        app.add_systems(
+            OnEnter(GameState::Playing),
+            (reset_boss_spawner, reset_game_phase_system),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                boss_spawn_system.run_if(not(resource_exists::<WaveRunner>)),
+                boss_ai_system,
+                game_phase_manager_system,
+            )
+                .chain()
+                .in_set(GameplaySet::Bosses),
+        )
+        .add_systems(Update, boss_phase_color_system.in_set(FrameSet::Effects))
+        .add_systems(
+            Update,
+            (
+                spawn_boss_health_bar,
+                update_boss_health_bar,
+                despawn_boss_health_bar,
+            )
+                .chain()
+                .in_set(FrameSet::Ui),
+        );
+    }
+}
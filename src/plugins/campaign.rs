@@ -0,0 +1,28 @@
+use crate::plugins::{FrameSet, GameplaySet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Handcrafted levels from `campaign.json`. A level plays its own wave file
+/// with an objective on top, and completing it earns stars and unlocks the
+/// next one.
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<Campaign>() {
+            app.insert_resource(Campaign::load());
+        }
+
+        app.add_systems(OnEnter(GameState::Playing), start_campaign_attempt)
+            .add_systems(OnExit(GameState::Playing), end_campaign_attempt)
+            .add_systems(
+                FixedUpdate,
+                (protect_beacon_system, update_campaign_objective)
+                    .chain()
+                    .in_set(GameplaySet::Objectives)
+                    .run_if(resource_exists::<CampaignRun>),
+            )
+            .add_systems(Update, update_objective_text.in_set(FrameSet::Ui));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::plugins::GameplaySet;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Collision tests between weapons, ships and asteroids, and the damage
+/// pipeline that scores and despawns what they destroy
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                collision_system,
+                laser_collision_system,
+                player_asteroid_collision_system,
+                player_enemy_bullet_collision_system,
+                bullet_enemy_collision_system,
+                bullet_boss_collision_system,
+                bullet_bullet_collision_system,
+            )
+                .chain()
+                .in_set(GameplaySet::Collision),
+        )
+        .add_systems(
+            FixedUpdate,
+            (update_combo, apply_damage, handle_destroyed)
+                .chain()
+                .in_set(GameplaySet::Damage),
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::events::*;
+use crate::plugins::{FrameSet, GameplaySet, SimulationSet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+/// State, shared resources, events and the schedule every other plugin slots
+/// into: the gameplay tick and its stages, movement, asteroid spawning, run
+/// setup and teardown, and the score and lives HUD.
+///
+/// Resources the app already holds are kept, so an embedding app can insert
+/// its own `GameSettings`, `GameRng` or `DifficultySettings` beforehand.
+pub struct CorePlugin;
+
+impl Plugin for CorePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<GameSettings>() {
+            let (settings, errors) = GameSettings::load();
+            app.insert_resource(settings);
+            app.world_mut()
+                .get_resource_or_init::<StorageErrors>()
+                .0
+                .extend(errors);
+        }
+        if !app.world().contains_resource::<EnemyArchetypes>() {
+            app.insert_resource(EnemyArchetypes::load());
+        }
+        if !app.world().contains_resource::<SpawnTables>() {
+            app.insert_resource(SpawnTables::load());
+        }
+        let spawn_tables = app.world().resource::<SpawnTables>().clone();
+        // Gameplay is simulated in fixed ticks, independent of the display rate
+        let timestep = app.world().resource::<GameSettings>().simulation.timestep();
+
+        app.init_state::<GameState>()
+            .add_sub_state::<PauseState>()
+            .add_sub_state::<MainMenuState>()
+            .add_sub_state::<GameOverState>()
+            .insert_resource(Time::<Fixed>::from_duration(timestep))
+            .init_resource::<PlayfieldBounds>()
+            .init_resource::<SpatialGrid>()
+            .init_resource::<GameRng>()
+            .init_resource::<RunClock>()
+            .init_resource::<PlayerInput>()
+            .init_resource::<ActionState>()
+            .init_resource::<GameScore>()
+            .init_resource::<PlayerLives>()
+            .init_resource::<DifficultySettings>()
+            .init_resource::<PlayerPowerUps>()
+            .init_resource::<BossSpawnManager>()
+            .init_resource::<GamePhaseManager>()
+            .insert_resource(AsteroidSizeGenerator::from_table(
+                &spawn_tables.asteroid_sizes,
+            ))
+            .insert_resource(AsteroidTypeGenerator::from_table(
+                &spawn_tables.asteroid_types,
+            ))
+            .init_resource::<AsteroidSpawnTimer>()
+            .add_event::<DamageEvent>()
+            .add_event::<Destroyed>()
+            // Gameplay events anyone can subscribe to
+            .add_event::<AsteroidDestroyed>()
+            .add_event::<EnemyKilled>()
+            .add_event::<BossPhaseChanged>()
+            .add_event::<BossDefeated>()
+            .add_event::<PlayerHit>()
+            .add_event::<LifeLost>()
+            .add_event::<PowerUpCollected>()
+            .add_event::<ShotFired>()
+            .add_event::<PowerUpExpired>()
+            .add_event::<RunStarted>()
+            .add_event::<RunEnded>()
+            .add_event::<ShowToast>()
+            // Gameplay runs on fixed ticks in one fixed order so a replay
+            // re-simulates it exactly
+            .configure_sets(FixedUpdate, SimulationSet.run_if(gameplay_running))
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameplaySet::Input,
+                    GameplaySet::Controls,
+                    GameplaySet::Movement,
+                    GameplaySet::Collision,
+                    GameplaySet::Damage,
+                    GameplaySet::Spawning,
+                    GameplaySet::Enemies,
+                    GameplaySet::Bosses,
+                    GameplaySet::PowerUps,
+                    GameplaySet::Objectives,
+                )
+                    .chain()
+                    .in_set(SimulationSet),
+            )
+            .configure_sets(
+                Update,
+                (FrameSet::Effects, FrameSet::Ui, FrameSet::Persistence)
+                    .chain()
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_systems(
+                PreUpdate,
+                sync_playfield_bounds.run_if(
+                    not(in_state(GameState::Playing)).and(not(resource_exists::<ReplayPlayback>)),
+                ),
+            )
+            .add_systems(PreUpdate, update_action_state.after(InputSystem))
+            // Rendered frames show entities between the last two gameplay ticks
+            .add_systems(
+                RunFixedMainLoop,
+                restore_simulated_transforms.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            )
+            .add_systems(
+                RunFixedMainLoop,
+                interpolate_transforms.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+            )
+            .add_systems(FixedFirst, record_previous_transforms)
+            .add_systems(FixedLast, record_current_transforms)
+            .add_systems(
+                FixedFirst,
+                begin_stress_tick.run_if(resource_exists::<StressTest>),
+            )
+            .add_systems(
+                FixedLast,
+                end_stress_tick.run_if(resource_exists::<StressTest>),
+            )
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (
+                    setup_game,
+                    reset_game_resources,
+                    announce_run_started
+                        .after(setup_game)
+                        .after(reset_game_resources),
+                ),
+            )
+            .add_systems(
+                OnExit(GameState::Playing),
+                (announce_run_ended, cleanup_all_entities).chain(),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    tick_run_clock,
+                    maintain_stress_population.run_if(resource_exists::<StressTest>),
+                )
+                    .chain()
+                    .in_set(GameplaySet::Input),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    move_entities,
+                    rotate_entities,
+                    wrap_around,
+                    rebuild_spatial_grid,
+                )
+                    .chain()
+                    .in_set(GameplaySet::Movement),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    update_bullet_lifecycle,
+                    despawn_asteroids,
+                    // A wave file spawns asteroids itself
+                    spawn_asteroids.run_if(not(resource_exists::<WaveRunner>)),
+                )
+                    .chain()
+                    .in_set(GameplaySet::Spawning),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_score_display,
+                    update_combo_display,
+                    update_lives_display,
+                    update_heart_display,
+                )
+                    .chain()
+                    .in_set(FrameSet::Ui),
+            )
+            // Toasts show in menus and while paused too
+            .add_systems(Update, (show_toasts, expire_toasts).chain());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::plugins::{FrameSet, GameplaySet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Enemy ships: spawning, AI and shooting
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        let enemy_spawn =
+            EnemySpawnTimer::from_table(&app.world().resource::<SpawnTables>().enemy_spawn);
+
+        app.insert_resource(enemy_spawn)
+            .add_systems(OnEnter(GameState::Playing), reset_enemy_spawner)
+            .add_systems(
+                FixedUpdate,
+                (
+                    spawn_enemy_system.run_if(not(resource_exists::<WaveRunner>)),
+                    enemy_ai_system,
+                    enemy_shooting_system,
+                )
+                    .chain()
+                    .in_set(GameplaySet::Enemies),
+            )
+            .add_systems(Update, pulsing_effect_system.in_set(FrameSet::Effects));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::plugins::*;
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+
+/// The whole game, minus windowing and rendering (add `DefaultPlugins` or
+/// `HeadlessPlugin` first).
+///
+/// Individual plugins can be switched off or swapped for your own, e.g.
+/// `RustyAsteroidPlugin.build().disable::<PowerUpPlugin>()`. `CorePlugin`
+/// sets up the state, resources and `GameplaySet` stages the others rely on,
+/// so it should always stay.
+pub struct RustyAsteroidPlugin;
+
+impl PluginGroup for RustyAsteroidPlugin {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(CorePlugin)
+            .add(PlayerPlugin)
+            .add(CombatPlugin)
+            .add(EnemyPlugin)
+            .add(BossPlugin)
+            .add(PowerUpPlugin)
+            .add(WavePlugin)
+            .add(CampaignPlugin)
+            .add(MenuPlugin)
+            .add(ReplayPlugin)
+            .add(PersistencePlugin)
+            .add(AchievementPlugin)
+            .add(TuningPlugin)
+    }
+}
@@ -0,0 +1,130 @@
+use crate::events::MenuButtonPressed;
+use crate::resources::*;
+use crate::systems::*;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+/// Main, level select, leaderboard, stats, achievements, settings, controls, difficulty and
+/// game over menus, plus the pause overlay
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<DifficultyPresets>() {
+            app.insert_resource(DifficultyPresets::load());
+        }
+
+        app.add_event::<MenuButtonPressed>()
+            .init_resource::<MenuFocus>()
+            .init_resource::<SeedEntry>()
+            .init_resource::<LeaderboardView>()
+            .init_resource::<LastReplay>()
+            // Menu state systems
+            .add_systems(OnEnter(MainMenuState::Title), setup_main_menu_styled)
+            .add_systems(OnExit(MainMenuState::Title), cleanup_styled_menu)
+            .add_systems(OnEnter(MainMenuState::LevelSelect), setup_level_select_menu)
+            .add_systems(OnExit(MainMenuState::LevelSelect), cleanup_styled_menu)
+            .add_systems(OnEnter(MainMenuState::Leaderboard), setup_leaderboard_menu)
+            .add_systems(OnExit(MainMenuState::Leaderboard), cleanup_styled_menu)
+            .add_systems(OnEnter(MainMenuState::Stats), setup_stats_menu)
+            .add_systems(OnExit(MainMenuState::Stats), cleanup_styled_menu)
+            .add_systems(
+                OnEnter(MainMenuState::Achievements),
+                setup_achievements_menu,
+            )
+            .add_systems(OnExit(MainMenuState::Achievements), cleanup_styled_menu)
+            .add_systems(
+                Update,
+                update_leaderboard_text.run_if(in_state(MainMenuState::Leaderboard)),
+            )
+            .add_systems(OnEnter(GameState::Settings), setup_settings_menu)
+            .add_systems(OnExit(GameState::Settings), cleanup_settings_menu)
+            .add_systems(OnEnter(GameState::Controls), setup_controls_menu)
+            .add_systems(
+                OnExit(GameState::Controls),
+                (cleanup_settings_menu, cancel_rebinding),
+            )
+            .add_systems(
+                OnEnter(GameState::DifficultySelect),
+                setup_difficulty_menu_styled,
+            )
+            .add_systems(OnExit(GameState::DifficultySelect), cleanup_styled_menu)
+            .add_systems(
+                OnEnter(GameState::CustomDifficulty),
+                setup_custom_difficulty_menu,
+            )
+            .add_systems(OnExit(GameState::CustomDifficulty), cleanup_styled_menu)
+            .add_systems(
+                Update,
+                custom_difficulty_slider_system.run_if(in_state(GameState::CustomDifficulty)),
+            )
+            .add_systems(
+                Update,
+                seed_entry_system.run_if(in_state(GameState::DifficultySelect)),
+            )
+            // The game-over pages come after the OnEnter(GameState::GameOver)
+            // systems, which build the report and check the leaderboard
+            .add_systems(OnEnter(GameOverState::Report), setup_run_report_menu)
+            .add_systems(OnExit(GameOverState::Report), cleanup_styled_menu)
+            .add_systems(OnEnter(GameOverState::Options), setup_game_over_menu_styled)
+            .add_systems(
+                Update,
+                name_entry_system.run_if(in_state(GameOverState::Options)),
+            )
+            .add_systems(OnExit(GameState::GameOver), cleanup_styled_menu)
+            // Pause overlay systems (the run stays in GameState::Playing)
+            .add_systems(
+                OnEnter(PauseState::Paused),
+                (setup_pause_menu, pause_virtual_time),
+            )
+            .add_systems(OnExit(PauseState::Paused), cleanup_pause_menu)
+            .add_systems(OnEnter(PauseState::Settings), setup_settings_menu)
+            .add_systems(OnExit(PauseState::Settings), cleanup_settings_menu)
+            .add_systems(OnEnter(PauseState::Controls), setup_controls_menu)
+            .add_systems(
+                OnExit(PauseState::Controls),
+                (cleanup_settings_menu, cancel_rebinding),
+            )
+            // The key waited for is taken before it turns into actions
+            .add_systems(
+                PreUpdate,
+                rebind_key_system
+                    .run_if(resource_exists::<Rebinding>)
+                    .after(InputSystem)
+                    .before(update_action_state),
+            )
+            .add_systems(
+                Update,
+                update_controls_text
+                    .run_if(in_state(GameState::Controls).or(in_state(PauseState::Controls))),
+            )
+            .add_systems(OnEnter(PauseState::Running), resume_virtual_time)
+            .add_systems(OnExit(GameState::Playing), resume_virtual_time)
+            .add_systems(
+                Update,
+                pause_input_system.run_if(in_state(GameState::Playing)),
+            )
+            // Menu interaction systems (run in all menu states)
+            .add_systems(
+                Update,
+                (
+                    menu_pointer_system,
+                    menu_gamepad_system,
+                    highlight_menu_focus,
+                    mesh_menu_button_system,
+                )
+                    .chain()
+                    .run_if(
+                        in_state(GameState::MainMenu)
+                            .or(in_state(GameState::DifficultySelect))
+                            .or(in_state(GameState::CustomDifficulty))
+                            .or(in_state(GameState::GameOver))
+                            .or(in_state(GameState::Settings))
+                            .or(in_state(GameState::Controls))
+                            .or(in_state(PauseState::Paused))
+                            .or(in_state(PauseState::Settings))
+                            .or(in_state(PauseState::Controls)),
+                    ),
+            );
+    }
+}
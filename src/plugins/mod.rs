@@ -0,0 +1,31 @@
+pub mod achievements;
+pub mod boss;
+pub mod campaign;
+pub mod combat;
+pub mod core;
+pub mod enemy;
+pub mod game;
+pub mod menu;
+pub mod persistence;
+pub mod player;
+pub mod powerup;
+pub mod replay;
+pub mod sets;
+pub mod tuning;
+pub mod waves;
+
+pub use self::core::*;
+pub use achievements::*;
+pub use boss::*;
+pub use campaign::*;
+pub use combat::*;
+pub use enemy::*;
+pub use game::*;
+pub use menu::*;
+pub use persistence::*;
+pub use player::*;
+pub use powerup::*;
+pub use replay::*;
+pub use sets::*;
+pub use tuning::*;
+pub use waves::*;
@@ -0,0 +1,73 @@
+use crate::headless::HeadlessRun;
+use crate::plugins::{FrameSet, GameplaySet, SimulationSet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Save file: high scores and leaderboards, lifetime stats and progress.
+/// Nothing is saved in headless runs or while watching a replay. Also puts a
+/// suspended run back when it is continued.
+pub struct PersistencePlugin;
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<SaveData>() {
+            let (save_data, errors) = SaveData::load();
+            app.insert_resource(save_data);
+            app.world_mut()
+                .get_resource_or_init::<StorageErrors>()
+                .0
+                .extend(errors);
+        }
+
+        app.init_resource::<StorageErrors>()
+            .init_resource::<RunStats>()
+            .add_systems(Startup, report_storage_errors)
+            .add_systems(OnEnter(GameState::Playing), reset_run_stats)
+            .add_systems(
+                FixedUpdate,
+                tally_run_stats
+                    .in_set(SimulationSet)
+                    .after(GameplaySet::Objectives),
+            )
+            .add_systems(
+                Update,
+                (save_game_progress, track_game_stats)
+                    .chain()
+                    .in_set(FrameSet::Persistence)
+                    .run_if(
+                        not(resource_exists::<HeadlessRun>)
+                            .and(not(resource_exists::<ReplayPlayback>)),
+                    ),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                (record_campaign_result, save_on_game_over, check_leaderboard)
+                    .chain()
+                    .run_if(
+                        not(resource_exists::<HeadlessRun>)
+                            .and(not(resource_exists::<ReplayVerification>)),
+                    ),
+            )
+            // The last tick before a pause or quit may not have been flushed yet
+            .add_systems(
+                OnExit(GameState::Playing),
+                track_game_stats.run_if(
+                    not(resource_exists::<HeadlessRun>).and(not(resource_exists::<ReplayPlayback>)),
+                ),
+            )
+            // Replays get a report too; it only reads the save data
+            .add_systems(OnEnter(GameState::GameOver), build_run_report)
+            .add_systems(OnExit(GameState::GameOver), save_pending_high_score)
+            // Before the first gameplay tick of the resumed run, once its
+            // OnEnter setup has spawned the ship and reset the resources
+            .add_systems(
+                RunFixedMainLoop,
+                (resume_run_state, resume_run_entities)
+                    .chain()
+                    .run_if(resource_exists::<ResumeRun>.and(in_state(GameState::Playing)))
+                    .before(restore_simulated_transforms)
+                    .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::plugins::{FrameSet, GameplaySet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Ship controls from the bound actions, movement and firing
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FireTimer>()
+            // A watched replay supplies the controls instead
+            .add_systems(
+                PreUpdate,
+                read_player_input
+                    .run_if(not(resource_exists::<ReplayPlayback>))
+                    .after(update_action_state),
+            )
+            .add_systems(OnEnter(GameState::Playing), reset_fire_timer)
+            .add_systems(
+                FixedUpdate,
+                (player_movement, enhanced_player_shoot)
+                    .chain()
+                    .in_set(GameplaySet::Controls),
+            )
+            .add_systems(
+                Update,
+                invincibility_visual_system.in_set(FrameSet::Effects),
+            );
+    }
+}
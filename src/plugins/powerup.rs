@@ -0,0 +1,28 @@
+use crate::plugins::{FrameSet, GameplaySet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Power-up pickups, their timed effects and the HUD listing them
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        let powerup_spawn =
+            PowerUpSpawnTimer::from_table(&app.world().resource::<SpawnTables>().powerup_spawn);
+
+        app.insert_resource(powerup_spawn)
+            .add_systems(OnEnter(GameState::Playing), reset_powerups_system)
+            .add_systems(
+                FixedUpdate,
+                (
+                    spawn_powerup_system.run_if(not(resource_exists::<WaveRunner>)),
+                    powerup_collection_system,
+                    powerup_effect_system,
+                )
+                    .chain()
+                    .in_set(GameplaySet::PowerUps),
+            )
+            .add_systems(Update, update_powerup_display.in_set(FrameSet::Ui));
+    }
+}
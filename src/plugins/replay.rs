@@ -0,0 +1,45 @@
+use crate::plugins::GameplaySet;
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Records every run's input and plays recordings back, with seeking
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>()
+            .init_resource::<LastReplay>()
+            .add_systems(
+                PreUpdate,
+                replay_controls_system
+                    .run_if(resource_exists::<ReplayPlayback>.and(in_state(PauseState::Running)))
+                    .after(update_action_state)
+                    .before(read_player_input),
+            )
+            .add_systems(
+                RunFixedMainLoop,
+                replay_seek_system
+                    .run_if(resource_exists::<ReplayPlayback>.and(in_state(GameState::Playing)))
+                    .after(restore_simulated_transforms)
+                    .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            )
+            .add_systems(OnEnter(GameState::Playing), begin_replay_run)
+            .add_systems(OnExit(GameState::Playing), finish_replay_run)
+            .add_systems(
+                FixedUpdate,
+                (
+                    record_replay_input.run_if(not(resource_exists::<ReplayPlayback>)),
+                    apply_replay_input.run_if(resource_exists::<ReplayPlayback>),
+                )
+                    .chain()
+                    .after(tick_run_clock)
+                    .in_set(GameplaySet::Input),
+            )
+            .add_systems(
+                Update,
+                (update_replay_hud, end_finished_replay)
+                    .run_if(resource_exists::<ReplayPlayback>.and(in_state(GameState::Playing))),
+            );
+    }
+}
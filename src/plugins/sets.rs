@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+/// Everything simulated in one gameplay tick (`FixedUpdate`). Runs only while
+/// `gameplay_running` holds, checked once per tick for the whole set.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationSet;
+
+/// Stages of a gameplay tick, run one after another in this order inside
+/// `SimulationSet`. Each stage belongs to one plugin (apart from `Input`,
+/// shared by the run clock and replay recording), so a replacement plugin
+/// only needs to put its systems in the same stage to keep replays exact.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameplaySet {
+    /// Run clock, replay input and other per-tick bookkeeping
+    Input,
+    /// Ship steering and firing
+    Controls,
+    /// Velocities applied, wraparound, broad phase rebuilt
+    Movement,
+    /// Overlap tests that send `DamageEvent`s
+    Collision,
+    /// Damage applied, destroyed entities scored and despawned
+    Damage,
+    /// Bullet lifetimes and asteroid spawning
+    Spawning,
+    Enemies,
+    Bosses,
+    PowerUps,
+    /// Campaign objectives checked against everything above
+    Objectives,
+}
+
+/// Per-frame work in `Update` while the run is not paused, in this order
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameSet {
+    /// Blinking, pulsing and other visual feedback
+    Effects,
+    /// HUD text and bars
+    Ui,
+    /// Saving progress and stats
+    Persistence,
+}
@@ -0,0 +1,30 @@
+use crate::assets::{TuningFile, TuningFileLoader};
+use crate::resources::TuningFiles;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Loads the tuning files under `assets/tuning/` through the asset server and
+/// applies edits to them while the game runs. Watching needs Bevy's
+/// `file_watcher` feature (on by default through this crate's `hot_reload`).
+/// Files that don't parse are reported with a toast and change nothing.
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<TuningFile>()
+            .init_asset_loader::<TuningFileLoader>()
+            .add_systems(Startup, load_tuning_files)
+            .add_systems(
+                Update,
+                (
+                    reload_difficulty_presets,
+                    reload_enemy_archetypes,
+                    reload_boss_definitions,
+                    reload_spawn_tables,
+                    reload_campaign,
+                    reload_achievements,
+                )
+                    .run_if(resource_exists::<TuningFiles>),
+            );
+    }
+}
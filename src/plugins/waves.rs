@@ -0,0 +1,24 @@
+use crate::plugins::{FrameSet, GameplaySet};
+use crate::resources::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+/// Authored runs from a wave file (`ActiveWaves`). While one plays, its
+/// `WaveRunner` spawns everything and the timed spawners stay idle.
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), start_wave_runner)
+            .add_systems(OnExit(GameState::Playing), end_wave_run)
+            .add_systems(
+                FixedUpdate,
+                (advance_waves, spawn_wave_contents)
+                    .chain()
+                    .after(spawn_asteroids)
+                    .in_set(GameplaySet::Spawning)
+                    .run_if(resource_exists::<WaveRunner>),
+            )
+            .add_systems(Update, update_wave_banner.in_set(FrameSet::Ui));
+    }
+}
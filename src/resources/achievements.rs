@@ -0,0 +1,297 @@
+use crate::components::{AsteroidType, BossType, EnemyType, PowerUpType};
+use crate::resources::{
+    BossDefinitions, DifficultyId, EnemyArchetypes, LifetimeStats, StatTotals, read_tuning_file,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Asset path of the achievement list
+pub const ACHIEVEMENTS_PATH: &str = "tuning/achievements.json";
+
+// Shipped achievements, used when the file is missing or broken
+const BUILT_IN_ACHIEVEMENTS: &str = include_str!("../../assets/tuning/achievements.json");
+
+/// A count kept in `StatTotals`. The types narrow a breakdown down to one
+/// kind; left out, every kind counts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "stat")]
+pub enum Stat {
+    Asteroids {
+        #[serde(default)]
+        asteroid_type: Option<AsteroidType>,
+    },
+    Enemies {
+        #[serde(default)]
+        enemy_type: Option<EnemyType>,
+    },
+    Bosses {
+        #[serde(default)]
+        boss: Option<BossType>,
+    },
+    BossPhases,
+    FlawlessBossPhases,
+    ShotsFired,
+    ShotsHit,
+    Deaths,
+    PowerUps {
+        #[serde(default)]
+        power_type: Option<PowerUpType>,
+    },
+}
+
+// One kind from a breakdown, or all of them
+fn count_of(counts: &BTreeMap<String, u32>, kind: Option<String>) -> u32 {
+    match kind {
+        Some(kind) => counts.get(&kind).copied().unwrap_or(0),
+        None => counts.values().sum(),
+    }
+}
+
+impl Stat {
+    /// The count in `totals`, keyed the way `StatTotals` records it
+    pub fn count(&self, totals: &StatTotals) -> u32 {
+        match self {
+            Stat::Asteroids { asteroid_type } => count_of(
+                &totals.asteroids,
+                asteroid_type.as_ref().map(|kind| kind.key().to_string()),
+            ),
+            Stat::Enemies { enemy_type } => count_of(
+                &totals.enemies,
+                enemy_type.as_ref().map(|kind| kind.to_string()),
+            ),
+            Stat::Bosses { boss } => {
+                count_of(&totals.bosses, boss.as_ref().map(|kind| kind.to_string()))
+            }
+            Stat::BossPhases => totals.boss_phases,
+            Stat::FlawlessBossPhases => totals.flawless_boss_phases,
+            Stat::ShotsFired => totals.shots_fired,
+            Stat::ShotsHit => totals.shots_hit,
+            Stat::Deaths => count_of(&totals.deaths, None),
+            Stat::PowerUps { power_type } => count_of(
+                &totals.power_ups,
+                power_type.as_ref().map(|kind| kind.key().to_string()),
+            ),
+        }
+    }
+}
+
+/// One requirement of an achievement. All of an achievement's conditions
+/// have to hold at the same time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum AchievementCondition {
+    /// At least `count` of `stat` in the run being played
+    Run {
+        #[serde(flatten)]
+        stat: Stat,
+        count: u32,
+    },
+    /// At least `count` of `stat` over every run, this one included
+    Lifetime {
+        #[serde(flatten)]
+        stat: Stat,
+        count: u32,
+    },
+    /// The run is on this difficulty
+    Difficulty { difficulty: DifficultyId },
+    /// The run's score has reached `score`
+    Score { score: u32 },
+    /// The run's best combo has reached `combo`
+    Combo { combo: u32 },
+    /// At least `percent` of the run's shots have hit, once `min_shots`
+    /// have been fired
+    Accuracy { percent: f32, min_shots: u32 },
+}
+
+/// What the conditions are checked against
+pub struct AchievementContext<'a> {
+    pub run: &'a StatTotals,
+    pub lifetime: &'a LifetimeStats,
+    pub difficulty: DifficultyId,
+    pub score: u32,
+    pub max_combo: u32,
+}
+
+impl AchievementCondition {
+    pub fn is_met(&self, context: &AchievementContext) -> bool {
+        match self {
+            AchievementCondition::Run { stat, count } => stat.count(context.run) >= *count,
+            AchievementCondition::Lifetime { stat, count } => {
+                stat.count(&context.lifetime.totals) >= *count
+            }
+            AchievementCondition::Difficulty { difficulty } => context.difficulty == *difficulty,
+            AchievementCondition::Score { score } => context.score >= *score,
+            AchievementCondition::Combo { combo } => context.max_combo >= *combo,
+            AchievementCondition::Accuracy { percent, min_shots } => {
+                context.run.shots_fired >= *min_shots
+                    && context
+                        .run
+                        .accuracy()
+                        .is_some_and(|accuracy| accuracy * 100.0 >= *percent)
+            }
+        }
+    }
+
+    // The types the condition names, for checking against the tuning files
+    fn boss(&self) -> Option<&BossType> {
+        match self {
+            AchievementCondition::Run {
+                stat: Stat::Bosses { boss },
+                ..
+            }
+            | AchievementCondition::Lifetime {
+                stat: Stat::Bosses { boss },
+                ..
+            } => boss.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn enemy_type(&self) -> Option<&EnemyType> {
+        match self {
+            AchievementCondition::Run {
+                stat: Stat::Enemies { enemy_type },
+                ..
+            }
+            | AchievementCondition::Lifetime {
+                stat: Stat::Enemies { enemy_type },
+                ..
+            } => enemy_type.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub conditions: Vec<AchievementCondition>,
+}
+
+impl Achievement {
+    pub fn is_met(&self, context: &AchievementContext) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.is_met(context))
+    }
+
+    /// Lifetime count so far and the count needed, for an achievement that
+    /// is earned over several runs
+    pub fn progress(&self, lifetime: &LifetimeStats) -> Option<(u32, u32)> {
+        self.conditions
+            .iter()
+            .find_map(|condition| match condition {
+                AchievementCondition::Lifetime { stat, count } => {
+                    Some((stat.count(&lifetime.totals).min(*count), *count))
+                }
+                _ => None,
+            })
+    }
+}
+
+#[derive(Deserialize)]
+struct AchievementsFile {
+    achievements: Vec<Achievement>,
+}
+
+/// Every achievement, from `achievements.json`, in gallery order. Unlocks
+/// are kept in `SaveData::achievements` by id.
+#[derive(Resource, Clone, Debug)]
+pub struct Achievements {
+    achievements: Vec<Achievement>,
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+impl Achievements {
+    pub fn built_in() -> Self {
+        Self::parse(BUILT_IN_ACHIEVEMENTS).expect("built-in achievements.json is valid")
+    }
+
+    pub fn load() -> Self {
+        match read_tuning_file(ACHIEVEMENTS_PATH) {
+            Some(data) => Self::parse(&data).unwrap_or_else(|error| {
+                warn!("Ignoring {}: {}", ACHIEVEMENTS_PATH, error);
+                Self::built_in()
+            }),
+            None => Self::built_in(),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let file: AchievementsFile =
+            serde_json::from_str(data).map_err(|error| error.to_string())?;
+
+        let mut achievements: Vec<Achievement> = Vec::new();
+        for achievement in file.achievements {
+            if achievements.iter().any(|known| known.id == achievement.id) {
+                return Err(format!("achievement {} is defined twice", achievement.id));
+            }
+            if achievement.conditions.is_empty() {
+                return Err(format!("achievement {} has no conditions", achievement.id));
+            }
+            for condition in &achievement.conditions {
+                let valid = match condition {
+                    AchievementCondition::Run { count, .. }
+                    | AchievementCondition::Lifetime { count, .. } => *count > 0,
+                    AchievementCondition::Difficulty { difficulty } => {
+                        *difficulty != DifficultyId::Custom
+                    }
+                    AchievementCondition::Accuracy { percent, .. } => {
+                        (0.0..=100.0).contains(percent)
+                    }
+                    AchievementCondition::Score { .. } | AchievementCondition::Combo { .. } => true,
+                };
+                if !valid {
+                    return Err(format!(
+                        "achievement {} has a condition that can't be met",
+                        achievement.id
+                    ));
+                }
+            }
+            achievements.push(achievement);
+        }
+
+        Ok(Self { achievements })
+    }
+
+    /// Checks that the bosses and enemies the conditions name exist
+    pub fn check_names(
+        &self,
+        bosses: &BossDefinitions,
+        enemies: &EnemyArchetypes,
+    ) -> Result<(), String> {
+        for achievement in &self.achievements {
+            for condition in &achievement.conditions {
+                if let Some(boss) = condition.boss()
+                    && bosses.get(boss).is_none()
+                {
+                    return Err(format!(
+                        "achievement {} refers to unknown boss {}",
+                        achievement.id, boss
+                    ));
+                }
+                if let Some(enemy_type) = condition.enemy_type()
+                    && enemies.get(enemy_type).is_none()
+                {
+                    return Err(format!(
+                        "achievement {} refers to unknown enemy {}",
+                        achievement.id, enemy_type
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Achievement> {
+        self.achievements.iter()
+    }
+}
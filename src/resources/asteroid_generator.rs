@@ -1,3 +1,4 @@
+use crate::resources::{AsteroidSizeTable, SpawnTables};
 use bevy::prelude::*;
 
 #[derive(Resource)]
@@ -27,8 +28,17 @@ impl AsteroidSizeGenerator {
         Self { min_size, weights }
     }
 
-    pub fn generate(&self) -> u32 {
-        let roll = fastrand::f32();
+    pub fn from_table(table: &AsteroidSizeTable) -> Self {
+        Self::new(
+            table.min,
+            table.max,
+            table.rarity_factor,
+            table.base_probability,
+        )
+    }
+
+    pub fn generate(&self, rng: &mut fastrand::Rng) -> u32 {
+        let roll = rng.f32();
 
         for (i, &cumulative_prob) in self.weights.iter().enumerate() {
             if roll < cumulative_prob {
@@ -42,6 +52,6 @@ impl AsteroidSizeGenerator {
 
 impl Default for AsteroidSizeGenerator {
     fn default() -> Self {
-        Self::new(1, 10, 2.1, 0.35)
+        Self::from_table(&SpawnTables::built_in().asteroid_sizes)
     }
 }
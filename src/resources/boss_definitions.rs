@@ -0,0 +1,270 @@
+use crate::components::{BossType, EnemyType};
+use crate::resources::{EnemyArchetypes, tuning_file_path};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Asset path of the boss definitions, one `.json` file per boss
+pub const BOSSES_DIR: &str = "tuning/bosses";
+
+// Shipped bosses, used when the directory is missing or any file in it is broken
+const BUILT_IN_BOSSES: [&str; 2] = [
+    include_str!("../../assets/tuning/bosses/giant_asteroid.json"),
+    include_str!("../../assets/tuning/bosses/alien_mothership.json"),
+];
+
+/// Everything that makes one boss, as written in `tuning/bosses/*.json`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BossDefinition {
+    pub id: BossType,
+    /// Shown above the health bar
+    pub name: String,
+    /// The boss appears once the score reaches this, at most once per run
+    pub spawn_score: u32,
+    /// Scales the mesh and the collider
+    pub size: f32,
+    pub shape: BossShape,
+    /// Seconds the boss stays still and invulnerable between phases
+    pub transition_time: f32,
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum BossShape {
+    /// A jagged asteroid; `radius` is multiplied by the boss size
+    Asteroid {
+        segments: u32,
+        radius: f32,
+    },
+    Mothership,
+    /// Points in local space, multiplied by the boss size, and pairs of
+    /// indices to draw lines between
+    Outline {
+        vertices: Vec<[f32; 2]>,
+        lines: Vec<u32>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BossPhase {
+    pub health: u32,
+    /// Score for clearing this phase
+    pub points: u32,
+    /// Seconds between attacks
+    pub attack_interval: f32,
+    pub color: [f32; 3],
+    pub movement: BossMovement,
+    pub order: AttackOrder,
+    pub attacks: Vec<BossAttack>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "style")]
+pub enum BossMovement {
+    /// Close in or back off to `distance` from the player, then circle
+    Orbit {
+        speed: f32,
+        distance: f32,
+        erratic: f32,
+    },
+    /// Head straight for the player
+    Chase { speed: f32, erratic: f32 },
+}
+
+/// How the next attack is picked from a phase's list
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttackOrder {
+    /// Each attack in turn, starting over at the end
+    Sequence,
+    /// A random attack, by `weight`
+    Weighted,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BossAttack {
+    /// Only used with `AttackOrder::Weighted`
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+    #[serde(flatten)]
+    pub pattern: AttackPattern,
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+/// One attack and its parameters. Bullet speeds are multiples of the ship's
+/// bullet speed; spreads of bullets are in radians, of spawns in units.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum AttackPattern {
+    /// Bullets in all directions
+    CircularShot {
+        bullet_count: u32,
+        speed: f32,
+        damage: u32,
+        explosive: bool,
+        color: [f32; 3],
+    },
+    /// A fan of bullets aimed at the player
+    TargetedBarrage {
+        bullet_count: u32,
+        spread: f32,
+        speed: f32,
+        damage: u32,
+        explosive: bool,
+        color: [f32; 3],
+    },
+    /// Enemy ships next to the boss; `enemy` defaults to the `boss_minion`
+    /// from `enemies.json`
+    SpawnMinions {
+        count: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        enemy: Option<EnemyType>,
+        spread: f32,
+        health: u32,
+    },
+    /// Small asteroids next to the boss, thrown at the player
+    AsteroidRain { count: u32, spread: f32, speed: f32 },
+}
+
+/// All boss definitions, ordered by spawn score.
+///
+/// Like `enemies.json`, a directory with any broken file is rejected as a
+/// whole and the built-in bosses are used instead.
+#[derive(Resource, Clone, Debug, Serialize)]
+pub struct BossDefinitions {
+    bosses: Vec<Arc<BossDefinition>>,
+}
+
+impl Default for BossDefinitions {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+impl BossDefinitions {
+    pub fn built_in() -> Self {
+        Self::parse(BUILT_IN_BOSSES).expect("built-in boss files are valid")
+    }
+
+    /// Falls back to the built-in bosses when a file is broken or spawns
+    /// an enemy that `archetypes` doesn't have
+    pub fn load(archetypes: &EnemyArchetypes) -> Self {
+        let bosses = Self::read_dir(BOSSES_DIR).and_then(|bosses| match bosses {
+            Some(bosses) => bosses.check_ids(archetypes).map(|()| Some(bosses)),
+            None => Ok(None),
+        });
+        match bosses {
+            Ok(Some(bosses)) => bosses,
+            Ok(None) => Self::built_in(),
+            Err(error) => {
+                warn!("Ignoring {}: {}", BOSSES_DIR, error);
+                Self::built_in()
+            }
+        }
+    }
+
+    // `None` when there is no directory or nothing in it
+    fn read_dir(dir: &str) -> Result<Option<Self>, String> {
+        let Ok(entries) = std::fs::read_dir(tuning_file_path(dir)) else {
+            return Ok(None);
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        if paths.is_empty() {
+            return Ok(None);
+        }
+        paths.sort();
+
+        let mut files = Vec::new();
+        for path in &paths {
+            let data = std::fs::read_to_string(path)
+                .map_err(|error| format!("{}: {}", path.display(), error))?;
+            files.push(data);
+        }
+        Self::parse(files.iter().map(String::as_str)).map(Some)
+    }
+
+    pub fn parse<'a>(files: impl IntoIterator<Item = &'a str>) -> Result<Self, String> {
+        let mut bosses: Vec<Arc<BossDefinition>> = Vec::new();
+        for data in files {
+            let boss: BossDefinition =
+                serde_json::from_str(data).map_err(|error| error.to_string())?;
+            if bosses.iter().any(|known| known.id == boss.id) {
+                return Err(format!("boss {} is defined twice", boss.id));
+            }
+            if boss.phases.is_empty() {
+                return Err(format!("boss {} has no phases", boss.id));
+            }
+            // Written so that NaN fails too
+            if !(boss.size > 0.0 && boss.size.is_finite()) {
+                return Err(format!("boss {} has a broken size", boss.id));
+            }
+            if !(boss.transition_time >= 0.0 && boss.transition_time.is_finite()) {
+                return Err(format!("boss {} has a broken transition time", boss.id));
+            }
+            for (index, phase) in boss.phases.iter().enumerate() {
+                let valid = phase.health > 0
+                    && phase.attack_interval > 0.0
+                    && phase.attack_interval.is_finite()
+                    && match phase.order {
+                        AttackOrder::Sequence => !phase.attacks.is_empty(),
+                        AttackOrder::Weighted => phase.attacks.iter().any(|a| a.weight > 0.0),
+                    }
+                    && phase.attacks.iter().all(|attack| {
+                        !matches!(
+                            attack.pattern,
+                            AttackPattern::SpawnMinions { health: 0, .. }
+                        )
+                    });
+                if !valid {
+                    return Err(format!("boss {} phase {} is broken", boss.id, index + 1));
+                }
+            }
+            if let BossShape::Outline { vertices, lines } = &boss.shape
+                && (lines.len() % 2 != 0 || lines.iter().any(|&i| i as usize >= vertices.len()))
+            {
+                return Err(format!("boss {} has a broken shape", boss.id));
+            }
+            bosses.push(Arc::new(boss));
+        }
+
+        // Stable, so bosses sharing a score keep their file order
+        bosses.sort_by_key(|boss| boss.spawn_score);
+        Ok(Self { bosses })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<BossDefinition>> {
+        self.bosses.iter()
+    }
+
+    pub fn get(&self, id: &BossType) -> Option<&Arc<BossDefinition>> {
+        self.bosses.iter().find(|boss| boss.id == *id)
+    }
+
+    /// Checks that every enemy the bosses spawn exists
+    pub fn check_ids(&self, archetypes: &EnemyArchetypes) -> Result<(), String> {
+        for boss in &self.bosses {
+            for (index, phase) in boss.phases.iter().enumerate() {
+                for attack in &phase.attacks {
+                    if let AttackPattern::SpawnMinions {
+                        enemy: Some(enemy), ..
+                    } = &attack.pattern
+                        && archetypes.get(enemy).is_none()
+                    {
+                        return Err(format!(
+                            "boss {} phase {} spawns unknown enemy {}",
+                            boss.id,
+                            index + 1,
+                            enemy
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
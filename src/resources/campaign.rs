@@ -0,0 +1,306 @@
+use crate::components::{AsteroidType, BossType};
+use crate::resources::{BossDefinitions, DifficultyId, SaveData, read_tuning_file, wave_file_path};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Asset path of the campaign level list
+pub const CAMPAIGN_PATH: &str = "tuning/campaign.json";
+
+// Shipped levels, used when the file is missing or broken
+const BUILT_IN_CAMPAIGN: &str = include_str!("../../assets/tuning/campaign.json");
+
+/// What a level asks of the player. Losing the last life always fails it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Objective {
+    /// Stay alive for this long
+    Survive { seconds: f32 },
+    /// Shoot down `count` asteroids, only of `asteroid_type` when given
+    DestroyAsteroids {
+        count: u32,
+        #[serde(default)]
+        asteroid_type: Option<AsteroidType>,
+    },
+    /// Beat this boss
+    DefeatBoss { boss: BossType },
+    /// Keep a beacon at `position` alive for `seconds`. Every asteroid or
+    /// enemy bullet that reaches it costs health.
+    Protect {
+        position: [f32; 2],
+        radius: f32,
+        health: u32,
+        seconds: f32,
+    },
+}
+
+/// Score and hit limit for an extra star
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StarThreshold {
+    pub score: u32,
+    pub max_hits: u32,
+}
+
+impl StarThreshold {
+    pub fn is_met(&self, score: u32, hits: u32) -> bool {
+        score >= self.score && hits <= self.max_hits
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CampaignLevel {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Wave file in `assets/tuning/waves/` that spawns the level
+    pub waves: String,
+    #[serde(default = "default_difficulty")]
+    pub difficulty: DifficultyId,
+    pub objective: Objective,
+    pub two_stars: StarThreshold,
+    pub three_stars: StarThreshold,
+}
+
+fn default_difficulty() -> DifficultyId {
+    DifficultyId::Normal
+}
+
+impl CampaignLevel {
+    /// Asset path of the level's wave file
+    pub fn wave_path(&self) -> String {
+        wave_file_path(&self.waves)
+    }
+
+    /// Stars for completing the level with `score` after taking `hits`
+    pub fn stars(&self, score: u32, hits: u32) -> u8 {
+        1 + self.two_stars.is_met(score, hits) as u8 + self.three_stars.is_met(score, hits) as u8
+    }
+
+    /// One line describing the objective, for the level list
+    pub fn objective_summary(&self) -> String {
+        match &self.objective {
+            Objective::Survive { seconds } => format!("Survive {:.0} seconds", seconds),
+            Objective::DestroyAsteroids {
+                count,
+                asteroid_type: Some(asteroid_type),
+            } => format!("Destroy {} {:?} asteroids", count, asteroid_type),
+            Objective::DestroyAsteroids { count, .. } => format!("Destroy {} asteroids", count),
+            Objective::DefeatBoss { boss } => format!("Defeat {}", boss),
+            Objective::Protect { seconds, .. } => {
+                format!("Protect the beacon for {:.0} seconds", seconds)
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CampaignFile {
+    levels: Vec<CampaignLevel>,
+}
+
+/// The campaign's levels in play order, from `campaign.json`. Each level
+/// unlocks once the one before it has been completed.
+#[derive(Resource, Clone, Debug)]
+pub struct Campaign {
+    levels: Vec<Arc<CampaignLevel>>,
+}
+
+impl Default for Campaign {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+impl Campaign {
+    pub fn built_in() -> Self {
+        Self::parse(BUILT_IN_CAMPAIGN).expect("built-in campaign.json is valid")
+    }
+
+    pub fn load() -> Self {
+        match read_tuning_file(CAMPAIGN_PATH) {
+            Some(data) => Self::parse(&data).unwrap_or_else(|error| {
+                warn!("Ignoring {}: {}", CAMPAIGN_PATH, error);
+                Self::built_in()
+            }),
+            None => Self::built_in(),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let file: CampaignFile = serde_json::from_str(data).map_err(|error| error.to_string())?;
+
+        let mut levels: Vec<Arc<CampaignLevel>> = Vec::new();
+        for level in file.levels {
+            if levels.iter().any(|known| known.id == level.id) {
+                return Err(format!("level {} is defined twice", level.id));
+            }
+            if level.difficulty == DifficultyId::Custom {
+                return Err(format!(
+                    "level {} can't use the Custom difficulty",
+                    level.id
+                ));
+            }
+            let valid = match &level.objective {
+                Objective::Survive { seconds } => *seconds > 0.0,
+                Objective::DestroyAsteroids { count, .. } => *count > 0,
+                Objective::DefeatBoss { .. } => true,
+                Objective::Protect {
+                    radius,
+                    health,
+                    seconds,
+                    ..
+                } => *radius > 0.0 && *health > 0 && *seconds > 0.0,
+            };
+            if !valid {
+                return Err(format!(
+                    "level {} has an objective that can't be met",
+                    level.id
+                ));
+            }
+            levels.push(Arc::new(level));
+        }
+        if levels.is_empty() {
+            return Err("the campaign needs at least one level".to_string());
+        }
+
+        Ok(Self { levels })
+    }
+
+    /// Checks that the bosses the objectives name exist
+    pub fn check_bosses(&self, bosses: &BossDefinitions) -> Result<(), String> {
+        for level in &self.levels {
+            if let Objective::DefeatBoss { boss } = &level.objective
+                && bosses.get(boss).is_none()
+            {
+                return Err(format!(
+                    "level {} refers to unknown boss {}",
+                    level.id, boss
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn levels(&self) -> &[Arc<CampaignLevel>] {
+        &self.levels
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Arc<CampaignLevel>> {
+        self.levels.iter().find(|level| level.id == id)
+    }
+
+    /// The level played after `id`, if there is one
+    pub fn next_after(&self, id: &str) -> Option<&Arc<CampaignLevel>> {
+        let index = self.levels.iter().position(|level| level.id == id)?;
+        self.levels.get(index + 1)
+    }
+
+    /// The first level is always open; the rest need the previous one done
+    pub fn is_unlocked(&self, index: usize, save_data: &SaveData) -> bool {
+        index == 0
+            || self
+                .levels
+                .get(index - 1)
+                .is_some_and(|previous| save_data.level_stars(&previous.id) > 0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectiveStatus {
+    InProgress,
+    Complete,
+    Failed,
+}
+
+/// The campaign level being played and how the current attempt is going.
+/// Kept between attempts so "Play Again" retries the same level; leaving
+/// for endless mode removes it.
+#[derive(Resource, Clone, Debug)]
+pub struct CampaignRun {
+    pub level: Arc<CampaignLevel>,
+    pub status: ObjectiveStatus,
+    /// Asteroids destroyed that count towards the objective
+    pub destroyed: u32,
+    /// Times the ship was hit this attempt
+    pub hits: u32,
+    pub boss_defeated: bool,
+}
+
+impl CampaignRun {
+    pub fn new(level: Arc<CampaignLevel>) -> Self {
+        Self {
+            level,
+            status: ObjectiveStatus::InProgress,
+            destroyed: 0,
+            hits: 0,
+            boss_defeated: false,
+        }
+    }
+
+    /// Start a fresh attempt at the same level
+    pub fn reset(&mut self) {
+        *self = Self::new(self.level.clone());
+    }
+
+    pub fn record_asteroid(&mut self, asteroid_type: &AsteroidType) {
+        if let Objective::DestroyAsteroids {
+            asteroid_type: wanted,
+            ..
+        } = &self.level.objective
+            && wanted.as_ref().is_none_or(|wanted| wanted == asteroid_type)
+        {
+            self.destroyed += 1;
+        }
+    }
+
+    pub fn record_boss(&mut self, boss_type: &BossType) {
+        if let Objective::DefeatBoss { boss } = &self.level.objective
+            && boss == boss_type
+        {
+            self.boss_defeated = true;
+        }
+    }
+
+    /// Re-checks the objective after `elapsed` seconds of play.
+    /// `beacon_health` is the protected beacon's health, if it still stands.
+    pub fn update(&mut self, elapsed: f32, beacon_health: Option<u32>) -> ObjectiveStatus {
+        if self.status != ObjectiveStatus::InProgress {
+            return self.status;
+        }
+        self.status = match &self.level.objective {
+            Objective::Survive { seconds } if elapsed >= *seconds => ObjectiveStatus::Complete,
+            Objective::DestroyAsteroids { count, .. } if self.destroyed >= *count => {
+                ObjectiveStatus::Complete
+            }
+            Objective::DefeatBoss { .. } if self.boss_defeated => ObjectiveStatus::Complete,
+            Objective::Protect { .. } if beacon_health.is_none_or(|health| health == 0) => {
+                ObjectiveStatus::Failed
+            }
+            Objective::Protect { seconds, .. } if elapsed >= *seconds => ObjectiveStatus::Complete,
+            _ => ObjectiveStatus::InProgress,
+        };
+        self.status
+    }
+
+    /// Objective progress for the HUD
+    pub fn progress_text(&self, elapsed: f32, beacon_health: Option<u32>) -> String {
+        match &self.level.objective {
+            Objective::Survive { seconds } => {
+                format!("Survive: {:.0}s left", (seconds - elapsed).max(0.0).ceil())
+            }
+            Objective::DestroyAsteroids { count, .. } => {
+                format!("Destroyed: {} / {}", self.destroyed.min(*count), count)
+            }
+            Objective::DefeatBoss { boss } => format!("Defeat {}", boss),
+            Objective::Protect {
+                health, seconds, ..
+            } => format!(
+                "Beacon: {} / {}  -  {:.0}s left",
+                beacon_health.unwrap_or(0),
+                health,
+                (seconds - elapsed).max(0.0).ceil()
+            ),
+        }
+    }
+}
@@ -1,7 +1,66 @@
+use crate::resources::{GameSettings, read_tuning_file};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
 
-#[derive(Resource, Clone, Debug)]
+/// Asset path of the difficulty presets
+pub const DIFFICULTIES_PATH: &str = "tuning/difficulties.json";
+
+// Shipped presets, used when the file is missing or broken
+const BUILT_IN_DIFFICULTIES: &str = include_str!("../../assets/tuning/difficulties.json");
+
+// Parsed on first use; settings defaults and tuning reloads copy from it
+static BUILT_IN_PRESETS: LazyLock<DifficultyPresets> = LazyLock::new(|| DifficultyPresets {
+    presets: serde_json::from_str(BUILT_IN_DIFFICULTIES)
+        .expect("built-in difficulties.json is valid"),
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DifficultyId {
+    Easy,
+    Normal,
+    Hard,
+    Insane,
+    /// Edited in the menu and stored in `GameSettings`
+    Custom,
+}
+
+impl DifficultyId {
+    pub const ALL: [DifficultyId; 5] = [
+        DifficultyId::Easy,
+        DifficultyId::Normal,
+        DifficultyId::Hard,
+        DifficultyId::Insane,
+        DifficultyId::Custom,
+    ];
+
+    pub fn to_byte(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        Self::ALL.get(byte as usize).copied()
+    }
+
+    /// Stable name for save files, the same one serde uses
+    pub fn key(self) -> &'static str {
+        match self {
+            DifficultyId::Easy => "Easy",
+            DifficultyId::Normal => "Normal",
+            DifficultyId::Hard => "Hard",
+            DifficultyId::Insane => "Insane",
+            DifficultyId::Custom => "Custom",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.key() == key)
+    }
+}
+
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DifficultySettings {
+    pub id: DifficultyId,
     pub name: String,
     pub asteroid_spawn_rate: f32, // seconds between spawns
     pub asteroid_speed_multiplier: f32,
@@ -14,65 +73,229 @@ pub struct DifficultySettings {
 }
 
 impl DifficultySettings {
-    pub fn easy() -> Self {
+    /// Starting point for the Custom difficulty: Normal, renamed
+    pub fn custom() -> Self {
         Self {
-            name: "Easy".to_string(),
-            asteroid_spawn_rate: 2.0,
-            asteroid_speed_multiplier: 1.0,
-            player_speed_multiplier: 1.0,
-            bullet_speed_multiplier: 1.0,
-            enemy_health_multiplier: 0.7, // 30% less health
-            enemy_speed_multiplier: 0.8,  // 20% slower
-            enemy_damage_multiplier: 0.8, // 20% less damage
-            boss_health_multiplier: 0.7,  // 30% less health
+            id: DifficultyId::Custom,
+            name: "Custom".to_string(),
+            ..BUILT_IN_PRESETS.get(DifficultyId::Normal).clone()
         }
     }
 
-    pub fn normal() -> Self {
-        Self {
-            name: "Normal".to_string(),
-            asteroid_spawn_rate: 0.75,
-            asteroid_speed_multiplier: 2.0,
-            player_speed_multiplier: 0.6,
-            bullet_speed_multiplier: 0.8,
-            enemy_health_multiplier: 1.0, // Normal health
-            enemy_speed_multiplier: 1.0,  // Normal speed
-            enemy_damage_multiplier: 1.0, // Normal damage
-            boss_health_multiplier: 1.0,  // Normal health
+    pub fn value(&self, field: DifficultyField) -> f32 {
+        match field {
+            DifficultyField::AsteroidSpawnRate => self.asteroid_spawn_rate,
+            DifficultyField::AsteroidSpeed => self.asteroid_speed_multiplier,
+            DifficultyField::PlayerSpeed => self.player_speed_multiplier,
+            DifficultyField::BulletSpeed => self.bullet_speed_multiplier,
+            DifficultyField::EnemyHealth => self.enemy_health_multiplier,
+            DifficultyField::EnemySpeed => self.enemy_speed_multiplier,
+            DifficultyField::EnemyDamage => self.enemy_damage_multiplier,
+            DifficultyField::BossHealth => self.boss_health_multiplier,
         }
     }
 
-    pub fn hard() -> Self {
-        Self {
-            name: "Hard".to_string(),
-            asteroid_spawn_rate: 0.4,
-            asteroid_speed_multiplier: 2.5,
-            player_speed_multiplier: 0.5,
-            bullet_speed_multiplier: 0.7,
-            enemy_health_multiplier: 1.3, // 30% more health
-            enemy_speed_multiplier: 1.2,  // 20% faster
-            enemy_damage_multiplier: 1.3, // 30% more damage
-            boss_health_multiplier: 1.4,  // 40% more health
+    /// Sets a value, clamped to the field's range and snapped to its step
+    pub fn set_value(&mut self, field: DifficultyField, value: f32) {
+        let (min, _, step) = field.range();
+        let snapped = ((value - min) / step).round() * step + min;
+        self.set_clamped(field, snapped);
+    }
+
+    fn set_clamped(&mut self, field: DifficultyField, value: f32) {
+        let (min, max, _) = field.range();
+        let value = if value.is_finite() {
+            value.clamp(min, max)
+        } else {
+            (min + max) * 0.5
+        };
+        match field {
+            DifficultyField::AsteroidSpawnRate => self.asteroid_spawn_rate = value,
+            DifficultyField::AsteroidSpeed => self.asteroid_speed_multiplier = value,
+            DifficultyField::PlayerSpeed => self.player_speed_multiplier = value,
+            DifficultyField::BulletSpeed => self.bullet_speed_multiplier = value,
+            DifficultyField::EnemyHealth => self.enemy_health_multiplier = value,
+            DifficultyField::EnemySpeed => self.enemy_speed_multiplier = value,
+            DifficultyField::EnemyDamage => self.enemy_damage_multiplier = value,
+            DifficultyField::BossHealth => self.boss_health_multiplier = value,
         }
     }
 
-    pub fn insane() -> Self {
-        Self {
-            name: "Insane".to_string(),
-            asteroid_spawn_rate: 0.2,
-            asteroid_speed_multiplier: 3.0,
-            player_speed_multiplier: 0.4,
-            bullet_speed_multiplier: 0.6,
-            enemy_health_multiplier: 1.6, // 60% more health
-            enemy_speed_multiplier: 1.5,  // 50% faster
-            enemy_damage_multiplier: 1.5, // 50% more damage
-            boss_health_multiplier: 1.8,  // 80% more health
+    /// Every value back inside its slider range, so a hand-edited file can't
+    /// produce zero spawn timers or negative health
+    pub fn sanitize(&mut self) {
+        for field in DifficultyField::ALL {
+            self.set_clamped(field, self.value(field));
         }
     }
+
+    /// Whether every value is inside its slider range, as `sanitize` leaves
+    /// them. Fails for NaN too.
+    pub fn is_sane(&self) -> bool {
+        DifficultyField::ALL.into_iter().all(|field| {
+            let (min, max, _) = field.range();
+            (min..=max).contains(&self.value(field))
+        })
+    }
 }
 
 impl Default for DifficultySettings {
     fn default() -> Self {
-        Self::normal()
+        BUILT_IN_PRESETS.get(DifficultyId::Normal).clone()
+    }
+}
+
+/// One tunable value of `DifficultySettings`, as shown in the Custom editor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DifficultyField {
+    AsteroidSpawnRate,
+    AsteroidSpeed,
+    PlayerSpeed,
+    BulletSpeed,
+    EnemyHealth,
+    EnemySpeed,
+    EnemyDamage,
+    BossHealth,
+}
+
+impl DifficultyField {
+    pub const ALL: [DifficultyField; 8] = [
+        DifficultyField::AsteroidSpawnRate,
+        DifficultyField::AsteroidSpeed,
+        DifficultyField::PlayerSpeed,
+        DifficultyField::BulletSpeed,
+        DifficultyField::EnemyHealth,
+        DifficultyField::EnemySpeed,
+        DifficultyField::EnemyDamage,
+        DifficultyField::BossHealth,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DifficultyField::AsteroidSpawnRate => "Asteroid spawn (s)",
+            DifficultyField::AsteroidSpeed => "Asteroid speed",
+            DifficultyField::PlayerSpeed => "Ship speed",
+            DifficultyField::BulletSpeed => "Bullet speed",
+            DifficultyField::EnemyHealth => "Enemy health",
+            DifficultyField::EnemySpeed => "Enemy speed",
+            DifficultyField::EnemyDamage => "Enemy damage",
+            DifficultyField::BossHealth => "Boss health",
+        }
+    }
+
+    /// Minimum, maximum and step of the field's slider
+    pub fn range(self) -> (f32, f32, f32) {
+        match self {
+            DifficultyField::AsteroidSpawnRate => (0.1, 3.0, 0.05),
+            DifficultyField::AsteroidSpeed => (0.5, 4.0, 0.1),
+            DifficultyField::PlayerSpeed => (0.2, 1.5, 0.05),
+            DifficultyField::BulletSpeed => (0.3, 1.5, 0.05),
+            DifficultyField::EnemyHealth
+            | DifficultyField::EnemyDamage
+            | DifficultyField::BossHealth => (0.25, 3.0, 0.05),
+            DifficultyField::EnemySpeed => (0.5, 2.5, 0.05),
+        }
+    }
+}
+
+/// A preset as written in `difficulties.json`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DifficultyPreset {
+    #[serde(flatten)]
+    pub settings: DifficultySettings,
+    /// Button colour on the difficulty menu (HDR, so above 1.0 glows)
+    pub menu_color: [f32; 3],
+}
+
+/// Difficulty presets shown on the difficulty menu, in file order.
+///
+/// Every id except `Custom` is always present: ids missing from the file are
+/// filled in from the built-in presets, so `get` never falls back silently.
+#[derive(Resource, Clone, Debug)]
+pub struct DifficultyPresets {
+    presets: Vec<DifficultyPreset>,
+}
+
+impl Default for DifficultyPresets {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+impl DifficultyPresets {
+    pub fn built_in() -> Self {
+        BUILT_IN_PRESETS.clone()
+    }
+
+    pub fn load() -> Self {
+        match read_tuning_file(DIFFICULTIES_PATH) {
+            Some(data) => Self::parse(&data).unwrap_or_else(|error| {
+                warn!("Ignoring {}: {}", DIFFICULTIES_PATH, error);
+                Self::built_in()
+            }),
+            None => Self::built_in(),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<Self, serde_json::Error> {
+        let mut presets: Vec<DifficultyPreset> = serde_json::from_str(data)?;
+
+        // Custom lives in the settings; keep the first entry for each id
+        let mut seen = Vec::new();
+        presets.retain(|preset| {
+            let id = preset.settings.id;
+            let keep = id != DifficultyId::Custom && !seen.contains(&id);
+            seen.push(id);
+            keep
+        });
+        for preset in presets.iter_mut() {
+            preset.settings.sanitize();
+        }
+
+        for built_in in BUILT_IN_PRESETS.iter() {
+            if !presets
+                .iter()
+                .any(|preset| preset.settings.id == built_in.settings.id)
+            {
+                presets.push(built_in.clone());
+            }
+        }
+
+        Ok(Self { presets })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DifficultyPreset> {
+        self.presets.iter()
+    }
+
+    /// The preset for `id`. `Custom` is not a preset; it is read from
+    /// `GameSettings` instead (see `resolve`), and maps to Normal here.
+    pub fn get(&self, id: DifficultyId) -> &DifficultySettings {
+        let id = if id == DifficultyId::Custom {
+            DifficultyId::Normal
+        } else {
+            id
+        };
+        self.presets
+            .iter()
+            .map(|preset| &preset.settings)
+            .find(|settings| settings.id == id)
+            .expect("every preset id is filled in on load")
+    }
+
+    /// Display name of `id`; Custom is always called Custom
+    pub fn name(&self, id: DifficultyId) -> &str {
+        match id {
+            DifficultyId::Custom => DifficultyId::Custom.key(),
+            id => &self.get(id).name,
+        }
+    }
+
+    /// Settings to play `id` with, taking Custom from the player's settings
+    pub fn resolve(&self, id: DifficultyId, settings: &GameSettings) -> DifficultySettings {
+        match id {
+            DifficultyId::Custom => settings.custom_difficulty.clone(),
+            id => self.get(id).clone(),
+        }
     }
 }
@@ -0,0 +1,231 @@
+use crate::components::{AIState, EnemyType};
+use crate::resources::read_tuning_file;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Asset path of the enemy archetypes
+pub const ENEMIES_PATH: &str = "tuning/enemies.json";
+
+// Shipped archetypes, used when the file is missing or broken
+const BUILT_IN_ENEMIES: &str = include_str!("../../assets/tuning/enemies.json");
+
+/// Everything that makes one kind of enemy ship, as written in `enemies.json`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemyArchetype {
+    pub id: EnemyType,
+    pub speed: f32,
+    /// Seconds between shots
+    pub shot_cooldown: f32,
+    pub health: u32,
+    pub points: u32,
+    pub color: [f32; 3],
+    /// Distance the AI rules and circling are measured against
+    pub engagement_range: f32,
+    /// 0..1, how hard the ship dodges and how much faster it attacks
+    pub evasion: f32,
+    /// Radius of the ship's hitbox
+    pub collider_radius: f32,
+    pub bullet: EnemyBulletDef,
+    pub shape: EnemyShape,
+    /// Checked in order every AI update; the first matching rule sets the state
+    pub ai: Vec<AIRule>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemyBulletDef {
+    pub damage: u32,
+    pub explosive: bool,
+    pub color: [f32; 3],
+}
+
+/// Ship outline: points in local space and pairs of indices to draw lines between
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemyShape {
+    pub vertices: Vec<[f32; 2]>,
+    pub lines: Vec<u32>,
+}
+
+/// Picks `state` when every condition given holds. Distances are in
+/// multiples of the archetype's `engagement_range`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AIRule {
+    pub state: AIState,
+    /// Player further away than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beyond: Option<f32>,
+    /// Player closer than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub within: Option<f32>,
+    /// Player moving faster than this, in units per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_speed_above: Option<f32>,
+}
+
+impl AIRule {
+    pub fn matches(&self, distance: f32, engagement_range: f32, player_speed: f32) -> bool {
+        self.beyond
+            .is_none_or(|factor| distance > engagement_range * factor)
+            && self
+                .within
+                .is_none_or(|factor| distance < engagement_range * factor)
+            && self
+                .player_speed_above
+                .is_none_or(|speed| player_speed > speed)
+    }
+}
+
+/// Which enemies spawn once the score reaches `min_score`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpawnTier {
+    pub min_score: u32,
+    pub enemies: Vec<WeightedEnemy>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeightedEnemy {
+    pub id: EnemyType,
+    pub weight: f32,
+}
+
+#[derive(Deserialize)]
+struct EnemiesFile {
+    archetypes: Vec<EnemyArchetype>,
+    spawn_tiers: Vec<SpawnTier>,
+    boss_minion: EnemyType,
+}
+
+/// Enemy archetypes and the score-based spawn table.
+///
+/// A file that fails to parse or refers to an unknown archetype is rejected
+/// as a whole, so lookups by id never miss.
+#[derive(Resource, Clone, Debug, Serialize)]
+pub struct EnemyArchetypes {
+    archetypes: Vec<Arc<EnemyArchetype>>,
+    spawn_tiers: Vec<SpawnTier>,
+    boss_minion: EnemyType,
+}
+
+impl Default for EnemyArchetypes {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+impl EnemyArchetypes {
+    pub fn built_in() -> Self {
+        Self::parse(BUILT_IN_ENEMIES).expect("built-in enemies.json is valid")
+    }
+
+    pub fn load() -> Self {
+        match read_tuning_file(ENEMIES_PATH) {
+            Some(data) => Self::parse(&data).unwrap_or_else(|error| {
+                warn!("Ignoring {}: {}", ENEMIES_PATH, error);
+                Self::built_in()
+            }),
+            None => Self::built_in(),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let file: EnemiesFile = serde_json::from_str(data).map_err(|error| error.to_string())?;
+
+        let mut archetypes: Vec<Arc<EnemyArchetype>> = Vec::new();
+        for archetype in file.archetypes {
+            if archetypes.iter().any(|known| known.id == archetype.id) {
+                return Err(format!("enemy {} is defined twice", archetype.id));
+            }
+            let vertex_count = archetype.shape.vertices.len() as u32;
+            if archetype.shape.lines.len() % 2 != 0
+                || archetype
+                    .shape
+                    .lines
+                    .iter()
+                    .any(|&index| index >= vertex_count)
+            {
+                return Err(format!("enemy {} has a broken shape", archetype.id));
+            }
+            // Written so that NaN fails too
+            let positive = |value: f32| value > 0.0 && value.is_finite();
+            if !(positive(archetype.speed)
+                && positive(archetype.shot_cooldown)
+                && positive(archetype.collider_radius)
+                && (0.0..=1.0).contains(&archetype.evasion))
+            {
+                return Err(format!("enemy {} has broken numbers", archetype.id));
+            }
+            if archetype.ai.is_empty() {
+                return Err(format!("enemy {} has no AI rules", archetype.id));
+            }
+            archetypes.push(Arc::new(archetype));
+        }
+
+        let known = |id: &EnemyType| archetypes.iter().any(|archetype| archetype.id == *id);
+        let mut spawn_tiers = file.spawn_tiers;
+        spawn_tiers.sort_by_key(|tier| tier.min_score);
+        if spawn_tiers.first().is_none_or(|tier| tier.min_score > 0) {
+            return Err("spawn_tiers must start at min_score 0".to_string());
+        }
+        for tier in &spawn_tiers {
+            if tier.enemies.iter().all(|enemy| enemy.weight <= 0.0) {
+                return Err(format!("spawn tier {} has no weights", tier.min_score));
+            }
+            if let Some(enemy) = tier.enemies.iter().find(|enemy| !known(&enemy.id)) {
+                return Err(format!("spawn tier refers to unknown enemy {}", enemy.id));
+            }
+        }
+        if !known(&file.boss_minion) {
+            return Err(format!(
+                "boss_minion refers to unknown enemy {}",
+                file.boss_minion
+            ));
+        }
+
+        Ok(Self {
+            archetypes,
+            spawn_tiers,
+            boss_minion: file.boss_minion,
+        })
+    }
+
+    pub fn get(&self, id: &EnemyType) -> Option<&Arc<EnemyArchetype>> {
+        self.archetypes.iter().find(|archetype| archetype.id == *id)
+    }
+
+    pub fn boss_minion(&self) -> &Arc<EnemyArchetype> {
+        self.get(&self.boss_minion)
+            .expect("boss_minion is checked on load")
+    }
+
+    /// Weighted pick from the highest spawn tier the score has reached. A
+    /// tier with a single enemy takes no random number.
+    pub fn choose_spawn(&self, score: u32, rng: &mut fastrand::Rng) -> &Arc<EnemyArchetype> {
+        let tier = self
+            .spawn_tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_score <= score)
+            .expect("spawn_tiers start at 0");
+        let candidates: Vec<&WeightedEnemy> = tier
+            .enemies
+            .iter()
+            .filter(|enemy| enemy.weight > 0.0)
+            .collect();
+
+        let mut chosen = candidates[candidates.len() - 1];
+        if candidates.len() > 1 {
+            let total: f32 = candidates.iter().map(|enemy| enemy.weight).sum();
+            let mut roll = rng.f32() * total;
+            for enemy in &candidates {
+                if roll < enemy.weight {
+                    chosen = enemy;
+                    break;
+                }
+                roll -= enemy.weight;
+            }
+        }
+
+        self.get(&chosen.id)
+            .expect("spawn tiers are checked on load")
+    }
+}
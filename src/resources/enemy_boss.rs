@@ -1,5 +1,10 @@
 use bevy::prelude::*;
 use crate::components::*;
+use crate::resources::{
+    AsteroidTypeChance, BossDefinition, BossDefinitions, EnemySpawnTable, SpawnTables,
+};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Resource)]
 pub struct EnemySpawnTimer {
@@ -10,12 +15,24 @@ pub struct EnemySpawnTimer {
 
 impl Default for EnemySpawnTimer {
     fn default() -> Self {
+        Self::from_table(&SpawnTables::built_in().enemy_spawn)
+    }
+}
+
+impl EnemySpawnTimer {
+    pub fn from_table(table: &EnemySpawnTable) -> Self {
         Self {
-            timer: Timer::from_seconds(10.0, TimerMode::Repeating), // Every 10 seconds (faster)
+            timer: Timer::from_seconds(table.interval, TimerMode::Repeating),
             last_spawn_score: 0,
-            spawn_score_interval: 300, // Spawn enemy every 300 points (more frequent)
+            spawn_score_interval: table.score_interval,
         }
     }
+
+    /// Takes new intervals without restarting the current countdown
+    pub fn apply_table(&mut self, table: &EnemySpawnTable) {
+        self.timer.set_duration(Duration::from_secs_f32(table.interval));
+        self.spawn_score_interval = table.score_interval;
+    }
 }
 
 #[derive(Resource)]
@@ -34,18 +51,19 @@ impl Default for BossSpawnManager {
 }
 
 impl BossSpawnManager {
-    pub fn should_spawn_boss(&self, score: u32) -> Option<BossType> {
-        // Giant Asteroid boss at 2000 points
-        if score >= 2000 && !self.bosses_spawned.contains(&BossType::GiantAsteroid) && self.active_boss.is_none() {
-            return Some(BossType::GiantAsteroid);
-        }
-        
-        // Alien Mothership boss at 5000 points
-        if score >= 5000 && !self.bosses_spawned.contains(&BossType::AlienMothership) && self.active_boss.is_none() {
-            return Some(BossType::AlienMothership);
+    /// The first boss whose spawn score has been reached and that hasn't
+    /// appeared yet this run, unless a boss is already on screen
+    pub fn should_spawn_boss<'a>(
+        &self,
+        score: u32,
+        bosses: &'a BossDefinitions,
+    ) -> Option<&'a Arc<BossDefinition>> {
+        if self.active_boss.is_some() {
+            return None;
         }
-        
-        None
+        bosses
+            .iter()
+            .find(|boss| score >= boss.spawn_score && !self.bosses_spawned.contains(&boss.id))
     }
 
     pub fn mark_boss_spawned(&mut self, boss_type: BossType, entity: Entity) {
@@ -65,20 +83,22 @@ pub struct AsteroidTypeGenerator {
 
 impl Default for AsteroidTypeGenerator {
     fn default() -> Self {
-        Self {
-            weights: vec![
-                (AsteroidType::Normal, 0.6),   // 60% chance
-                (AsteroidType::Ice, 0.25),     // 25% chance
-                (AsteroidType::Metal, 0.12),   // 12% chance
-                (AsteroidType::Crystal, 0.03), // 3% chance
-            ],
-        }
+        Self::from_table(&SpawnTables::built_in().asteroid_types)
     }
 }
 
 impl AsteroidTypeGenerator {
-    pub fn generate(&self) -> AsteroidType {
-        let roll = fastrand::f32();
+    pub fn from_table(table: &[AsteroidTypeChance]) -> Self {
+        Self {
+            weights: table
+                .iter()
+                .map(|entry| (entry.asteroid_type.clone(), entry.chance))
+                .collect(),
+        }
+    }
+
+    pub fn generate(&self, rng: &mut fastrand::Rng) -> AsteroidType {
+        let roll = rng.f32();
         let mut cumulative = 0.0;
         
         for (asteroid_type, weight) in &self.weights {
@@ -4,3 +4,11 @@ use bevy::prelude::*;
 pub struct FireTimer {
     pub timer: Timer,
 }
+
+impl Default for FireTimer {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(0.05, TimerMode::Repeating),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Resource, Debug, Clone, PartialEq)]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GamePhase {
     Normal,        // Regular gameplay with asteroids and regular enemies
     BossEncounter, // Boss is present - reduced asteroid spawns, heightened tension
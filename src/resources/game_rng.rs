@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+// Mixed into the run seed so the cosmetic stream never mirrors the gameplay one
+const COSMETIC_STREAM_SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// All randomness for a run, seeded once when the run starts.
+///
+/// Gameplay rolls (spawns, drops, AI jitter) come from `gameplay`; purely visual
+/// rolls (particles, spin) come from `cosmetic`, so effects can change without
+/// shifting the gameplay sequence. A seed plus the same inputs replays a run.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    pub requested_seed: Option<u64>, // Fixed seed for the next run, random when None
+    pub gameplay: fastrand::Rng,
+    pub cosmetic: fastrand::Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            requested_seed: None,
+            gameplay: fastrand::Rng::with_seed(seed),
+            cosmetic: fastrand::Rng::with_seed(seed ^ COSMETIC_STREAM_SALT),
+        }
+    }
+
+    /// Seed of the current run
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Reseed both streams for a new run, using the requested seed if one is set
+    pub fn start_run(&mut self) {
+        let seed = self.requested_seed.unwrap_or_else(|| fastrand::u64(..));
+        self.reseed(seed);
+    }
+
+    /// Pick a suspended run's streams back up exactly where they were
+    pub fn resume(&mut self, seed: u64, gameplay_state: u64, cosmetic_state: u64) {
+        self.seed = seed;
+        self.gameplay = fastrand::Rng::with_seed(gameplay_state);
+        self.cosmetic = fastrand::Rng::with_seed(cosmetic_state);
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.gameplay = fastrand::Rng::with_seed(seed);
+        self.cosmetic = fastrand::Rng::with_seed(seed ^ COSMETIC_STREAM_SALT);
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(fastrand::u64(..))
+    }
+}
+
+/// Text entry state for the seed field on the difficulty menu
+#[derive(Resource, Default)]
+pub struct SeedEntry {
+    pub editing: bool,
+    pub text: String,
+}
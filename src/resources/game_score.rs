@@ -1,6 +1,65 @@
+use crate::constants::*;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Resource)]
+/// The run's score and kill combo. All points go through `add_kill` or
+/// `add_points` so the combo multiplier applies everywhere.
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct GameScore {
     pub score: u32,
+    /// Kills in a row, each within `COMBO_WINDOW` of the one before
+    pub combo: u32,
+    /// Longest combo this run
+    pub max_combo: u32,
+    /// Seconds left for the next kill to keep the combo going
+    pub combo_timer: f32,
+}
+
+impl GameScore {
+    /// Points are multiplied by 1, plus 1 for every `COMBO_KILLS_PER_STEP` kills in the combo
+    pub fn multiplier(&self) -> u32 {
+        (1 + self.combo / COMBO_KILLS_PER_STEP).min(MAX_SCORE_MULTIPLIER)
+    }
+
+    /// Scores a kill worth `points`: the combo grows first, then the
+    /// multiplier applies. Returns the points awarded.
+    pub fn add_kill(&mut self, points: u32) -> u32 {
+        self.combo += 1;
+        self.max_combo = self.max_combo.max(self.combo);
+        self.combo_timer = COMBO_WINDOW;
+        self.add_points(points)
+    }
+
+    /// Scores `points` times the multiplier without extending the combo.
+    /// Returns the points awarded.
+    pub fn add_points(&mut self, points: u32) -> u32 {
+        let awarded = points.saturating_mul(self.multiplier());
+        self.score = self.score.saturating_add(awarded);
+        awarded
+    }
+
+    /// Runs down the combo window; the combo ends when it does
+    pub fn tick_combo(&mut self, delta: f32) {
+        if self.combo == 0 {
+            return;
+        }
+        self.combo_timer -= delta;
+        if self.combo_timer <= 0.0 {
+            self.break_combo();
+        }
+    }
+
+    pub fn break_combo(&mut self) {
+        self.combo = 0;
+        self.combo_timer = 0.0;
+    }
+
+    /// How much of the combo window is left, from 1 down to 0
+    pub fn combo_fraction(&self) -> f32 {
+        (self.combo_timer / COMBO_WINDOW).clamp(0.0, 1.0)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
 }
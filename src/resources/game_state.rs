@@ -4,9 +4,10 @@ use bevy::prelude::*;
 pub enum GameState {
     MainMenu,
     Settings,
+    Controls,
     DifficultySelect,
+    CustomDifficulty,
     Playing,
-    Paused,
     GameOver,
 }
 
@@ -15,3 +16,47 @@ impl Default for GameState {
         Self::MainMenu
     }
 }
+
+/// In-run overlay state. Only exists while `GameState::Playing` is active, so
+/// pausing (or opening Settings or Controls from the pause menu) never leaves
+/// `Playing` and the run's entities and resources are kept untouched.
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Playing)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+    Settings,
+    Controls,
+}
+
+/// Screens of the main menu. Only exists while `GameState::MainMenu` is active.
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::MainMenu)]
+pub enum MainMenuState {
+    #[default]
+    Title,
+    LevelSelect,
+    Leaderboard,
+    Stats,
+    Achievements,
+}
+
+/// Pages of the game-over screen: the run report, then the buttons to play
+/// again or leave. Only exists while `GameState::GameOver` is active.
+#[derive(SubStates, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::GameOver)]
+pub enum GameOverState {
+    #[default]
+    Report,
+    Options,
+}
+
+/// Set when a run ends by clearing its waves or campaign objective, or by
+/// failing the objective, rather than by losing the last life or quitting.
+/// Removed when the next run starts.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunVerdict {
+    Cleared,
+    Failed,
+}
@@ -0,0 +1,460 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Keys an action can hold at once; binding another pushes the oldest out
+pub const MAX_KEYS_PER_ACTION: usize = 2;
+
+/// Something the player does, whatever key it is bound to. Systems read
+/// these from `ActionState` instead of `ButtonInput<KeyCode>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InputAction {
+    RotateLeft,
+    RotateRight,
+    Thrust,
+    Reverse,
+    Fire,
+    /// Opens the pause menu, and steps back out of it
+    Pause,
+    ReplayPause,
+    ReplaySpeed,
+    ReplaySeekBack,
+    ReplaySeekForward,
+}
+
+impl InputAction {
+    /// Every action, in the order the Controls page lists them
+    pub const ALL: [InputAction; 10] = [
+        InputAction::RotateLeft,
+        InputAction::RotateRight,
+        InputAction::Thrust,
+        InputAction::Reverse,
+        InputAction::Fire,
+        InputAction::Pause,
+        InputAction::ReplayPause,
+        InputAction::ReplaySpeed,
+        InputAction::ReplaySeekBack,
+        InputAction::ReplaySeekForward,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InputAction::RotateLeft => "Rotate left",
+            InputAction::RotateRight => "Rotate right",
+            InputAction::Thrust => "Thrust",
+            InputAction::Reverse => "Reverse",
+            InputAction::Fire => "Fire",
+            InputAction::Pause => "Pause",
+            InputAction::ReplayPause => "Replay: pause",
+            InputAction::ReplaySpeed => "Replay: speed",
+            InputAction::ReplaySeekBack => "Replay: seek back",
+            InputAction::ReplaySeekForward => "Replay: seek forward",
+        }
+    }
+
+    /// Gamepad buttons for the action, which aren't rebindable. Menus use
+    /// the D-pad and South as well.
+    pub fn gamepad_buttons(self) -> &'static [GamepadButton] {
+        match self {
+            InputAction::RotateLeft => &[GamepadButton::DPadLeft],
+            InputAction::RotateRight => &[GamepadButton::DPadRight],
+            InputAction::Thrust => &[GamepadButton::RightTrigger2, GamepadButton::DPadUp],
+            InputAction::Reverse => &[GamepadButton::LeftTrigger2, GamepadButton::DPadDown],
+            InputAction::Fire => &[GamepadButton::South],
+            InputAction::Pause => &[GamepadButton::Start],
+            InputAction::ReplayPause => &[GamepadButton::North],
+            InputAction::ReplaySpeed => &[GamepadButton::West],
+            InputAction::ReplaySeekBack => &[GamepadButton::LeftTrigger],
+            InputAction::ReplaySeekForward => &[GamepadButton::RightTrigger],
+        }
+    }
+
+    fn default_keys(self) -> Vec<KeyCode> {
+        match self {
+            InputAction::RotateLeft => vec![KeyCode::ArrowLeft, KeyCode::KeyA],
+            InputAction::RotateRight => vec![KeyCode::ArrowRight, KeyCode::KeyD],
+            InputAction::Thrust => vec![KeyCode::ArrowUp, KeyCode::KeyW],
+            InputAction::Reverse => vec![KeyCode::ArrowDown, KeyCode::KeyS],
+            InputAction::Fire => vec![KeyCode::Space],
+            InputAction::Pause => vec![KeyCode::Escape],
+            InputAction::ReplayPause => vec![KeyCode::KeyP],
+            InputAction::ReplaySpeed => vec![KeyCode::KeyF],
+            InputAction::ReplaySeekBack => vec![KeyCode::BracketLeft],
+            InputAction::ReplaySeekForward => vec![KeyCode::BracketRight],
+        }
+    }
+}
+
+/// Keys that can be bound. Settings store them by name, so this is also the
+/// list of names `settings.json` understands.
+pub const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Tab,
+    KeyCode::Escape,
+    KeyCode::Backspace,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::Backquote,
+    KeyCode::Backslash,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+];
+
+/// Name a key is stored under, e.g. `KeyA` or `ArrowLeft`
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS
+        .iter()
+        .copied()
+        .find(|key| key_name(*key) == name)
+}
+
+/// Short name for menus: `A` rather than `KeyA`, `Left` rather than `ArrowLeft`
+pub fn key_label(key: KeyCode) -> String {
+    let name = key_name(key);
+    for prefix in ["Key", "Digit", "Arrow"] {
+        if let Some(rest) = name.strip_prefix(prefix) {
+            return rest.to_string();
+        }
+    }
+    name
+}
+
+/// Which keys trigger each action, kept in `GameSettings::controls`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<InputAction, Vec<String>>")]
+#[serde(into = "BTreeMap<InputAction, Vec<String>>")]
+pub struct InputBindings {
+    keys: BTreeMap<InputAction, Vec<KeyCode>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            keys: InputAction::ALL
+                .into_iter()
+                .map(|action| (action, action.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+// Unknown key names are dropped and missing actions get their default keys,
+// so files from older builds still load
+impl From<BTreeMap<InputAction, Vec<String>>> for InputBindings {
+    fn from(names: BTreeMap<InputAction, Vec<String>>) -> Self {
+        let mut bindings = Self::default();
+        for (action, names) in names {
+            let keys: Vec<KeyCode> = names
+                .iter()
+                .filter_map(|name| {
+                    let key = parse_key(name);
+                    if key.is_none() {
+                        warn!("Ignoring unknown key {} bound to {:?}", name, action);
+                    }
+                    key
+                })
+                .take(MAX_KEYS_PER_ACTION)
+                .collect();
+            if !keys.is_empty() {
+                bindings.keys.insert(action, keys);
+            }
+        }
+        bindings
+    }
+}
+
+impl From<InputBindings> for BTreeMap<InputAction, Vec<String>> {
+    fn from(bindings: InputBindings) -> Self {
+        bindings
+            .keys
+            .into_iter()
+            .map(|(action, keys)| (action, keys.into_iter().map(key_name).collect()))
+            .collect()
+    }
+}
+
+impl InputBindings {
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The action `key` is bound to, if any
+    pub fn action_for(&self, key: KeyCode) -> Option<InputAction> {
+        self.keys
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    /// Makes `key` the first key of `action`, keeping the previous first key
+    /// as the second. A key bound to another action is refused, and that
+    /// action is returned.
+    pub fn bind(&mut self, action: InputAction, key: KeyCode) -> Result<(), InputAction> {
+        if let Some(other) = self.action_for(key).filter(|other| *other != action) {
+            return Err(other);
+        }
+        let keys = self.keys.entry(action).or_default();
+        keys.retain(|bound| *bound != key);
+        keys.insert(0, key);
+        keys.truncate(MAX_KEYS_PER_ACTION);
+        Ok(())
+    }
+
+    /// Pairs of actions sharing a key, which only a hand-edited file can have
+    pub fn conflicts(&self) -> Vec<(InputAction, InputAction, KeyCode)> {
+        let mut conflicts = Vec::new();
+        for (action, keys) in &self.keys {
+            for key in keys {
+                for (other, other_keys) in self.keys.range(action..).skip(1) {
+                    if other_keys.contains(key) {
+                        conflicts.push((*action, *other, *key));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// `Left / A` for the Controls page
+    pub fn label(&self, action: InputAction) -> String {
+        let keys = self.keys(action);
+        if keys.is_empty() {
+            return "(unbound)".to_string();
+        }
+        keys.iter()
+            .map(|key| key_label(*key))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+/// How the left stick steers the ship
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StickMode {
+    /// Left and right turn the ship, faster the further the stick goes
+    #[default]
+    Rotate,
+    /// The ship turns to face where the stick points
+    Aim,
+}
+
+impl StickMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            StickMode::Rotate => "ROTATE",
+            StickMode::Aim => "AIM",
+        }
+    }
+}
+
+/// Gamepad tuning, kept in `GameSettings::gamepad`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GamepadControls {
+    pub stick: StickMode,
+    /// Stick deflection (0 to 1) that still counts as centred
+    pub stick_deadzone: f32,
+    /// How far a trigger has to be pulled (0 to 1) to count as pressed
+    pub trigger_deadzone: f32,
+    /// Turn rate per unit of stick deflection in `Rotate` mode; above 1 the
+    /// ship turns at full speed before the stick is all the way over
+    pub sensitivity: f32,
+}
+
+impl Default for GamepadControls {
+    fn default() -> Self {
+        Self {
+            stick: StickMode::Rotate,
+            stick_deadzone: 0.2,
+            trigger_deadzone: 0.1,
+            sensitivity: 1.0,
+        }
+    }
+}
+
+impl GamepadControls {
+    pub const MAX_DEADZONE: f32 = 0.9;
+    pub const MIN_SENSITIVITY: f32 = 0.1;
+    pub const MAX_SENSITIVITY: f32 = 4.0;
+
+    /// Clamps values edited by hand
+    pub fn sanitize(&mut self) {
+        let defaults = Self::default();
+        self.stick_deadzone = clamp_or(
+            self.stick_deadzone,
+            0.0,
+            Self::MAX_DEADZONE,
+            defaults.stick_deadzone,
+        );
+        self.trigger_deadzone = clamp_or(
+            self.trigger_deadzone,
+            0.0,
+            Self::MAX_DEADZONE,
+            defaults.trigger_deadzone,
+        );
+        self.sensitivity = clamp_or(
+            self.sensitivity,
+            Self::MIN_SENSITIVITY,
+            Self::MAX_SENSITIVITY,
+            defaults.sensitivity,
+        );
+    }
+
+    /// The stick with the deadzone cut out and the rest rescaled to reach 1
+    /// again, or `None` while it is centred
+    pub fn stick(&self, raw: Vec2) -> Option<Vec2> {
+        let length = raw.length();
+        if length <= self.stick_deadzone {
+            return None;
+        }
+        let scaled = ((length - self.stick_deadzone) / (1.0 - self.stick_deadzone)).min(1.0);
+        Some(raw / length * scaled)
+    }
+
+    pub fn button_held(&self, gamepad: &Gamepad, button: GamepadButton) -> bool {
+        match button {
+            GamepadButton::LeftTrigger2 | GamepadButton::RightTrigger2 => {
+                gamepad.get(button).unwrap_or(0.0) > self.trigger_deadzone
+            }
+            _ => gamepad.pressed(button),
+        }
+    }
+}
+
+fn clamp_or(value: f32, min: f32, max: f32, default: f32) -> f32 {
+    if value.is_finite() {
+        value.clamp(min, max)
+    } else {
+        default
+    }
+}
+
+/// Actions held down and newly pressed this frame, filled from the key
+/// bindings and gamepads in `PreUpdate`
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ActionState {
+    pressed: BTreeSet<InputAction>,
+    just_pressed: BTreeSet<InputAction>,
+    held_last_frame: BTreeSet<InputAction>,
+    /// Analog turn rate from -1 (full right) to 1 (full left)
+    pub turn: f32,
+    /// Heading a stick in `Aim` mode points at, in radians counter-clockwise
+    /// from straight up
+    pub aim: Option<f32>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Forget last frame's actions before reading the devices again
+    pub fn clear(&mut self) {
+        self.held_last_frame = std::mem::take(&mut self.pressed);
+        self.just_pressed.clear();
+        self.turn = 0.0;
+        self.aim = None;
+    }
+
+    pub fn press(&mut self, action: InputAction) {
+        self.pressed.insert(action);
+    }
+
+    pub fn just_press(&mut self, action: InputAction) {
+        self.just_pressed.insert(action);
+    }
+
+    /// Held by a device that only reports what is down; counts as newly
+    /// pressed unless the action was already held last frame
+    pub fn hold(&mut self, action: InputAction) {
+        self.press(action);
+        if !self.held_last_frame.contains(&action) {
+            self.just_press(action);
+        }
+    }
+}
+
+/// The action waiting for a key on the Controls page
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rebinding(pub InputAction);
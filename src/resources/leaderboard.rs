@@ -0,0 +1,162 @@
+use crate::resources::{ActiveWaves, Campaign, CampaignRun, DifficultyId, DifficultyPresets};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Entries kept on each leaderboard
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// Longest name that can be typed in
+pub const MAX_NAME_LENGTH: usize = 12;
+
+/// One finished run on a leaderboard
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: u32,
+    /// When the run ended, in seconds since the Unix epoch
+    pub date: u64,
+    /// Length of the run in seconds
+    pub duration: f32,
+    pub seed: u64,
+    pub max_combo: u32,
+}
+
+/// What a run played: the first part of its board
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunMode {
+    Endless,
+    /// File stem of the wave file
+    Waves(String),
+    /// Id of the campaign level
+    Campaign(String),
+}
+
+/// Board a run is ranked on: its mode and difficulty. Boards are stored
+/// under `key()`, made of ids, so renaming a difficulty preset or a
+/// campaign level keeps its scores; `title()` gives the names to show.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeaderboardId {
+    pub mode: RunMode,
+    pub difficulty: DifficultyId,
+}
+
+impl LeaderboardId {
+    pub fn for_run(
+        campaign_run: Option<&CampaignRun>,
+        active_waves: Option<&ActiveWaves>,
+        difficulty: DifficultyId,
+    ) -> Self {
+        let mode = match (campaign_run, active_waves) {
+            (Some(campaign_run), _) => RunMode::Campaign(campaign_run.level.id.clone()),
+            (None, Some(active_waves)) => {
+                let stem = std::path::Path::new(&active_waves.path)
+                    .file_stem()
+                    .map_or_else(
+                        || active_waves.path.clone(),
+                        |stem| stem.to_string_lossy().into_owned(),
+                    );
+                RunMode::Waves(stem)
+            }
+            (None, None) => RunMode::Endless,
+        };
+        Self { mode, difficulty }
+    }
+
+    /// Key in `SaveData::leaderboards`, e.g. "endless/Hard",
+    /// "waves/training/Normal" or "campaign/ore_run/Normal"
+    pub fn key(&self) -> String {
+        let mode = match &self.mode {
+            RunMode::Endless => "endless".to_string(),
+            RunMode::Waves(stem) => format!("waves/{}", stem),
+            RunMode::Campaign(level) => format!("campaign/{}", level),
+        };
+        format!("{}/{}", mode, self.difficulty.key())
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        let (mode, difficulty) = key.rsplit_once('/')?;
+        let mode = match mode.split_once('/') {
+            None if mode == "endless" => RunMode::Endless,
+            Some(("waves", stem)) => RunMode::Waves(stem.to_string()),
+            Some(("campaign", level)) => RunMode::Campaign(level.to_string()),
+            _ => return None,
+        };
+        Some(Self {
+            mode,
+            difficulty: DifficultyId::from_key(difficulty)?,
+        })
+    }
+
+    /// Name to show, e.g. "Endless - Hard" or "Campaign: Ore Run - Normal".
+    /// A level no longer in the campaign shows its id.
+    pub fn title(&self, presets: &DifficultyPresets, campaign: &Campaign) -> String {
+        let mode = match &self.mode {
+            RunMode::Endless => "Endless".to_string(),
+            RunMode::Waves(stem) => format!("Waves: {}", stem),
+            RunMode::Campaign(id) => {
+                let name = campaign.get(id).map_or(id.as_str(), |level| &level.name);
+                format!("Campaign: {}", name)
+            }
+        };
+        format!("{} - {}", mode, presets.name(self.difficulty))
+    }
+}
+
+/// Title of a stored board. Keys this build can't read are shown as they are.
+pub fn leaderboard_title(key: &str, presets: &DifficultyPresets, campaign: &Campaign) -> String {
+    LeaderboardId::parse(key).map_or_else(|| key.to_string(), |id| id.title(presets, campaign))
+}
+
+/// Seconds since the Unix epoch, for entry dates
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// `YYYY-MM-DD` (UTC) for a time in seconds since the Unix epoch
+pub fn format_date(unix_secs: u64) -> String {
+    // Civil-from-days, counting in 400 year eras starting on 0000-03-01
+    let days = (unix_secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// `m:ss` for a run length in seconds
+pub fn format_duration(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// A score that made its leaderboard, waiting on the game-over screen for
+/// the player's name. It is saved when the name is confirmed, or with the
+/// name as typed when the screen is left.
+#[derive(Resource, Clone, Debug)]
+pub struct PendingHighScore {
+    /// Key of the board, see `LeaderboardId::key`
+    pub board: String,
+    /// The board's name as shown
+    pub title: String,
+    pub entry: LeaderboardEntry,
+    /// Place it will take, from 1
+    pub rank: usize,
+}
+
+/// Which board the leaderboard screen shows, wrapping around the saved ones
+#[derive(Resource, Default)]
+pub struct LeaderboardView {
+    pub index: usize,
+}
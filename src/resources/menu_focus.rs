@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+/// The menu button under the mouse or picked with a gamepad, drawn
+/// highlighted; a gamepad presses it with South
+#[derive(Resource, Default)]
+pub struct MenuFocus(pub Option<Entity>);
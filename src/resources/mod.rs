@@ -1,25 +1,67 @@
+pub mod achievements;
 pub mod asteroid_generator;
+pub mod boss_definitions;
+pub mod campaign;
 pub mod difficulty;
+pub mod enemy_archetypes;
 pub mod enemy_boss;
 pub mod fire_timer;
 pub mod game_phase;
+pub mod game_rng;
 pub mod game_score;
 pub mod game_state;
+pub mod input_bindings;
+pub mod leaderboard;
+pub mod menu_focus;
+pub mod player_input;
 pub mod player_lives;
+pub mod playfield;
 pub mod powerups;
+pub mod replay;
+pub mod run_clock;
+pub mod run_report;
+pub mod run_snapshot;
 pub mod save_data;
 pub mod settings;
+pub mod spatial_grid;
+pub mod spawn_tables;
 pub mod spawn_timer;
+pub mod stats;
+pub mod storage;
+pub mod stress_test;
+pub mod tuning;
+pub mod waves;
 
+pub use achievements::*;
 pub use asteroid_generator::*;
+pub use boss_definitions::*;
+pub use campaign::*;
 pub use difficulty::*;
+pub use enemy_archetypes::*;
 pub use enemy_boss::*;
 pub use fire_timer::*;
 pub use game_phase::*;
+pub use game_rng::*;
 pub use game_score::*;
 pub use game_state::*;
+pub use input_bindings::*;
+pub use leaderboard::*;
+pub use menu_focus::*;
+pub use player_input::*;
 pub use player_lives::*;
+pub use playfield::*;
 pub use powerups::*;
+pub use replay::*;
+pub use run_clock::*;
+pub use run_report::*;
+pub use run_snapshot::*;
 pub use save_data::*;
 pub use settings::*;
+pub use spatial_grid::*;
+pub use spawn_tables::*;
 pub use spawn_timer::*;
+pub use stats::*;
+pub use storage::*;
+pub use stress_test::*;
+pub use tuning::*;
+pub use waves::*;
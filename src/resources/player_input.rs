@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// Ship controls for the current tick. Filled from the bound actions during
+/// normal play and from the replay stream during playback, so gameplay
+/// systems never read `ButtonInput<KeyCode>` or gamepads directly.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlayerInput {
+    pub rotate_left: bool,
+    pub rotate_right: bool,
+    pub thrust: bool,
+    pub reverse: bool,
+    pub fire: bool,
+    /// Analog turn rate from a stick, full speed left at 127 and right at
+    /// -127, on top of the rotate buttons
+    pub turn: i8,
+    /// Heading a stick points at, in 256ths of a turn counter-clockwise from
+    /// straight up; the ship turns towards it at full speed
+    pub aim: Option<u8>,
+}
+
+impl PlayerInput {
+    const ROTATE_LEFT: u8 = 1 << 0;
+    const ROTATE_RIGHT: u8 = 1 << 1;
+    const THRUST: u8 = 1 << 2;
+    const REVERSE: u8 = 1 << 3;
+    const FIRE: u8 = 1 << 4;
+    /// Set in the bits when a `turn` byte follows them in a replay
+    pub const TURN: u8 = 1 << 5;
+    /// Set in the bits when an `aim` byte follows them in a replay
+    pub const AIM: u8 = 1 << 6;
+
+    /// Packs the buttons into one byte for replay files, flagging the analog
+    /// values that are stored after it
+    pub fn to_bits(self) -> u8 {
+        let mut bits = 0;
+        if self.rotate_left {
+            bits |= Self::ROTATE_LEFT;
+        }
+        if self.rotate_right {
+            bits |= Self::ROTATE_RIGHT;
+        }
+        if self.thrust {
+            bits |= Self::THRUST;
+        }
+        if self.reverse {
+            bits |= Self::REVERSE;
+        }
+        if self.fire {
+            bits |= Self::FIRE;
+        }
+        if self.turn != 0 {
+            bits |= Self::TURN;
+        }
+        if self.aim.is_some() {
+            bits |= Self::AIM;
+        }
+        bits
+    }
+
+    /// The buttons in `bits`; the analog values are read separately
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            rotate_left: bits & Self::ROTATE_LEFT != 0,
+            rotate_right: bits & Self::ROTATE_RIGHT != 0,
+            thrust: bits & Self::THRUST != 0,
+            reverse: bits & Self::REVERSE != 0,
+            fire: bits & Self::FIRE != 0,
+            turn: 0,
+            aim: None,
+        }
+    }
+
+    /// `turn` from a rate between -1 (full right) and 1 (full left)
+    pub fn turn_from(rate: f32) -> i8 {
+        (rate.clamp(-1.0, 1.0) * 127.0).round() as i8
+    }
+
+    /// `aim` from a heading in radians, counter-clockwise from straight up
+    pub fn aim_from(heading: f32) -> u8 {
+        (heading.rem_euclid(TAU) / TAU * 256.0).round() as u32 as u8
+    }
+
+    /// The `aim` heading in radians
+    pub fn aim_heading(aim: u8) -> f32 {
+        aim as f32 / 256.0 * TAU
+    }
+}
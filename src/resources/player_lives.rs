@@ -22,7 +22,6 @@ impl PlayerLives {
         self.lives == 0 // Returns true if game over
     }
 
-    #[allow(dead_code)]
     pub fn is_game_over(&self) -> bool {
         self.lives == 0
     }
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+/// Logical size of the play area in world units, centred on the origin.
+/// Gameplay systems read this instead of the window so they also run headless.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct PlayfieldBounds {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PlayfieldBounds {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn half_width(&self) -> f32 {
+        self.width / 2.0
+    }
+
+    pub fn half_height(&self) -> f32 {
+        self.height / 2.0
+    }
+}
+
+impl Default for PlayfieldBounds {
+    fn default() -> Self {
+        Self::new(800.0, 600.0) // Matches the default window resolution
+    }
+}
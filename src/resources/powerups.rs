@@ -1,5 +1,8 @@
 use crate::components::powerup::PowerUpType;
+use crate::resources::{PowerUpSpawnTable, SpawnTables};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Resource)]
 pub struct PowerUpSpawnTimer {
@@ -8,13 +11,25 @@ pub struct PowerUpSpawnTimer {
 
 impl Default for PowerUpSpawnTimer {
     fn default() -> Self {
+        Self::from_table(&SpawnTables::built_in().powerup_spawn)
+    }
+}
+
+impl PowerUpSpawnTimer {
+    pub fn from_table(table: &PowerUpSpawnTable) -> Self {
         Self {
-            timer: Timer::from_seconds(10.0, TimerMode::Repeating), // Spawn power-up every 10 seconds
+            timer: Timer::from_seconds(table.interval, TimerMode::Repeating),
         }
     }
+
+    /// Takes the new interval without restarting the current countdown
+    pub fn apply_table(&mut self, table: &PowerUpSpawnTable) {
+        self.timer
+            .set_duration(Duration::from_secs_f32(table.interval));
+    }
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct PlayerPowerUps {
     pub active_effects: Vec<PowerUpType>,
     pub rapid_fire_count: u32,
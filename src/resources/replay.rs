@@ -0,0 +1,450 @@
+use crate::resources::{DifficultyId, DifficultySettings, PlayerInput, PlayfieldBounds};
+use bevy::prelude::*;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Identifies a replay file
+const REPLAY_MAGIC: &[u8; 4] = b"RARP";
+
+/// Bumped whenever the binary layout changes
+pub const REPLAY_FORMAT_VERSION: u16 = 1;
+
+/// Longest run a replay may hold, over nine hours even at the highest tick
+/// rate, so a corrupt tick count can't ask for more memory than a real run
+const MAX_TICKS: usize = 1 << 25;
+
+/// Game version stamped on new replays
+pub const GAME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Where finished runs are written
+pub const REPLAY_DIR: &str = "replays";
+
+/// A recorded run: everything needed to re-simulate it exactly.
+///
+/// Binary layout (little endian):
+/// magic `RARP`, format version `u16`, game version string, tuning
+/// fingerprint `u64`, seed `u64`, tick length in nanoseconds `u64`,
+/// playfield width and height `f32`,
+/// difficulty id `u8` + name string +
+/// eight `f32` multipliers, wave file string (empty for endless runs),
+/// campaign level id string (empty outside the campaign), final score
+/// `u32`, tick count `u32`, then the input of every tick run-length encoded
+/// as `varint run_length, u8 input_bits`, followed by an `i8` turn and a
+/// `u8` aim when the bits flag them.
+/// Strings are a `u8` length followed by UTF-8 bytes.
+#[derive(Clone, Debug)]
+pub struct Replay {
+    pub game_version: String,
+    /// `tuning_fingerprint` of the tuning the run was recorded with
+    pub tuning: u64,
+    pub seed: u64,
+    /// Fixed gameplay tick the run was simulated with
+    pub timestep: Duration,
+    /// Play area the run was simulated in, whatever the window size
+    pub playfield: PlayfieldBounds,
+    pub difficulty: DifficultySettings,
+    /// Asset path of the wave file the run played, if any
+    pub waves: Option<String>,
+    /// Id of the campaign level the run played, if any
+    pub level: Option<String>,
+    pub final_score: u32,
+    /// Controls held during each gameplay tick
+    pub inputs: Vec<PlayerInput>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    BadMagic,
+    UnsupportedVersion(u16),
+    Truncated,
+    Corrupt(&'static str),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "{}", error),
+            ReplayError::BadMagic => write!(f, "not a replay file"),
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "unsupported replay format version {}", version)
+            }
+            ReplayError::Truncated => write!(f, "replay file is truncated"),
+            ReplayError::Corrupt(reason) => write!(f, "replay file is corrupt: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(error: std::io::Error) -> Self {
+        ReplayError::Io(error)
+    }
+}
+
+impl Replay {
+    /// Whether this build recorded the replay. Gameplay changes between
+    /// versions can make an older run play out differently.
+    pub fn matches_game_version(&self) -> bool {
+        self.game_version == GAME_VERSION
+    }
+
+    /// Whether the run was recorded with the tuning `fingerprint` describes
+    pub fn matches_tuning(&self, fingerprint: u64) -> bool {
+        self.tuning == fingerprint
+    }
+
+    /// Total simulated time covered by the replay
+    pub fn duration(&self) -> Duration {
+        self.timestep * self.inputs.len() as u32
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(REPLAY_MAGIC);
+        bytes.extend_from_slice(&REPLAY_FORMAT_VERSION.to_le_bytes());
+        write_string(&mut bytes, &self.game_version);
+        bytes.extend_from_slice(&self.tuning.to_le_bytes());
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&(self.timestep.as_nanos() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.playfield.width.to_le_bytes());
+        bytes.extend_from_slice(&self.playfield.height.to_le_bytes());
+
+        bytes.push(self.difficulty.id.to_byte());
+        write_string(&mut bytes, &self.difficulty.name);
+        for value in difficulty_values(&self.difficulty) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        write_string(&mut bytes, self.waves.as_deref().unwrap_or_default());
+        write_string(&mut bytes, self.level.as_deref().unwrap_or_default());
+
+        bytes.extend_from_slice(&self.final_score.to_le_bytes());
+        bytes.extend_from_slice(&(self.inputs.len() as u32).to_le_bytes());
+
+        // Consecutive ticks with the same controls collapse into one run
+        let mut index = 0;
+        while index < self.inputs.len() {
+            let input = self.inputs[index];
+            let mut run_length = 1;
+            while index + run_length < self.inputs.len() && self.inputs[index + run_length] == input
+            {
+                run_length += 1;
+            }
+
+            write_varint(&mut bytes, run_length as u64);
+            bytes.push(input.to_bits());
+            if input.turn != 0 {
+                bytes.push(input.turn as u8);
+            }
+            if let Some(aim) = input.aim {
+                bytes.push(aim);
+            }
+            index += run_length;
+        }
+
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut reader = ByteReader { bytes, position: 0 };
+
+        if reader.take(4)? != REPLAY_MAGIC {
+            return Err(ReplayError::BadMagic);
+        }
+        let version = u16::from_le_bytes(reader.array()?);
+        if version != REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let game_version = reader.string()?;
+        let tuning = u64::from_le_bytes(reader.array()?);
+        let seed = u64::from_le_bytes(reader.array()?);
+        let timestep = Duration::from_nanos(u64::from_le_bytes(reader.array()?));
+        if timestep.is_zero() {
+            return Err(ReplayError::Corrupt("zero tick length"));
+        }
+        let playfield = PlayfieldBounds::new(
+            f32::from_le_bytes(reader.array()?),
+            f32::from_le_bytes(reader.array()?),
+        );
+        // Written so that NaN fails too
+        let valid_size = |size: f32| size > 0.0 && size.is_finite();
+        if !(valid_size(playfield.width) && valid_size(playfield.height)) {
+            return Err(ReplayError::Corrupt("broken playfield size"));
+        }
+
+        let id = DifficultyId::from_byte(reader.take(1)?[0])
+            .ok_or(ReplayError::Corrupt("unknown difficulty"))?;
+        let name = reader.string()?;
+        let mut values = [0.0; 8];
+        for value in values.iter_mut() {
+            *value = f32::from_le_bytes(reader.array()?);
+        }
+        let difficulty = DifficultySettings {
+            id,
+            name,
+            asteroid_spawn_rate: values[0],
+            asteroid_speed_multiplier: values[1],
+            player_speed_multiplier: values[2],
+            bullet_speed_multiplier: values[3],
+            enemy_health_multiplier: values[4],
+            enemy_speed_multiplier: values[5],
+            enemy_damage_multiplier: values[6],
+            boss_health_multiplier: values[7],
+        };
+        // Recorded settings are always sanitized; anything else would break
+        // the spawn timers
+        if !difficulty.is_sane() {
+            return Err(ReplayError::Corrupt("difficulty out of range"));
+        }
+
+        let waves = Some(reader.string()?).filter(|path| !path.is_empty());
+        let level = Some(reader.string()?).filter(|id| !id.is_empty());
+
+        let final_score = u32::from_le_bytes(reader.array()?);
+        let tick_count = u32::from_le_bytes(reader.array()?) as usize;
+        if tick_count > MAX_TICKS {
+            return Err(ReplayError::Corrupt("too many ticks"));
+        }
+
+        // Every run takes at least two bytes, so the file bounds the runs
+        // that can follow even when the tick count is wrong
+        let mut inputs = Vec::with_capacity(tick_count.min(reader.remaining() / 2));
+        while inputs.len() < tick_count {
+            let run_length = reader.varint()? as usize;
+            let input = reader.input()?;
+
+            if run_length == 0 || inputs.len() + run_length > tick_count {
+                return Err(ReplayError::Corrupt("input runs do not match tick count"));
+            }
+            inputs.extend(std::iter::repeat_n(input, run_length));
+        }
+
+        if reader.position != bytes.len() {
+            return Err(ReplayError::Corrupt("trailing data after inputs"));
+        }
+
+        Ok(Self {
+            game_version,
+            tuning,
+            seed,
+            timestep,
+            playfield,
+            difficulty,
+            waves,
+            level,
+            final_score,
+            inputs,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.encode())?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        Self::decode(&std::fs::read(path)?)
+    }
+}
+
+fn difficulty_values(difficulty: &DifficultySettings) -> [f32; 8] {
+    [
+        difficulty.asteroid_spawn_rate,
+        difficulty.asteroid_speed_multiplier,
+        difficulty.player_speed_multiplier,
+        difficulty.bullet_speed_multiplier,
+        difficulty.enemy_health_multiplier,
+        difficulty.enemy_speed_multiplier,
+        difficulty.enemy_damage_multiplier,
+        difficulty.boss_health_multiplier,
+    ]
+}
+
+fn write_string(bytes: &mut Vec<u8>, text: &str) {
+    // Names and versions are short; anything longer is cut at a char boundary
+    let mut end = text.len().min(u8::MAX as usize);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    bytes.push(end as u8);
+    bytes.extend_from_slice(&text.as_bytes()[..end]);
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], ReplayError> {
+        let end = self.position + count;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(ReplayError::Truncated)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn string(&mut self) -> Result<String, ReplayError> {
+        let length = self.take(1)?[0] as usize;
+        String::from_utf8(self.take(length)?.to_vec())
+            .map_err(|_| ReplayError::Corrupt("invalid UTF-8 string"))
+    }
+
+    fn varint(&mut self) -> Result<u64, ReplayError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ReplayError::Corrupt("varint too long"))
+    }
+
+    fn input(&mut self) -> Result<PlayerInput, ReplayError> {
+        let bits = self.take(1)?[0];
+        let mut input = PlayerInput::from_bits(bits);
+        if bits & PlayerInput::TURN != 0 {
+            input.turn = self.take(1)?[0] as i8;
+        }
+        if bits & PlayerInput::AIM != 0 {
+            input.aim = Some(self.take(1)?[0]);
+        }
+        Ok(input)
+    }
+}
+
+/// Collects the inputs of the run in progress
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    pub inputs: Vec<PlayerInput>,
+    /// Overrides the default file in `replays/`, e.g. `--record run.rpl`
+    pub output_path: Option<PathBuf>,
+    /// The run was suspended or resumed part-way, so the inputs don't
+    /// replay it from the start and no replay is kept
+    pub interrupted: bool,
+    /// `tuning_fingerprint` when the run started
+    pub tuning: u64,
+}
+
+/// Default file name for a finished run, unique per seed and finish time
+pub fn default_replay_path(seed: u64) -> PathBuf {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    Path::new(REPLAY_DIR).join(format!("run_{}_{}.rpl", timestamp, seed))
+}
+
+/// The most recently finished run, offered by "Watch Replay" on game over
+#[derive(Resource, Default)]
+pub struct LastReplay(pub Option<Replay>);
+
+/// Active while a replay is being played back instead of live input
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    /// Index of the next tick to simulate
+    pub cursor: usize,
+    pub paused: bool,
+    /// Playback speed multiplier (1, 2, 4 or 8)
+    pub speed: u32,
+    /// Tick to fast-forward to, set by seeking
+    pub seek_target: Option<usize>,
+    /// Seeking backwards restarts the run and fast-forwards from the start
+    pub restart_requested: bool,
+    /// Fixed tick length to restore once playback ends
+    pub restore_timestep: Duration,
+}
+
+impl ReplayPlayback {
+    pub const SPEEDS: [u32; 4] = [1, 2, 4, 8];
+
+    pub fn new(replay: Replay, restore_timestep: Duration) -> Self {
+        Self {
+            replay,
+            cursor: 0,
+            paused: false,
+            speed: 1,
+            seek_target: None,
+            restart_requested: false,
+            restore_timestep,
+        }
+    }
+
+    /// Controls for the next tick, moving the cursor past it
+    pub fn next_input(&mut self) -> Option<PlayerInput> {
+        let input = self.replay.inputs.get(self.cursor).copied()?;
+        self.cursor += 1;
+        Some(input)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.replay.inputs.len()
+    }
+
+    /// Simulated time covered so far
+    pub fn elapsed(&self) -> Duration {
+        self.replay.timestep * self.cursor as u32
+    }
+
+    pub fn cycle_speed(&mut self) {
+        let index = Self::SPEEDS
+            .iter()
+            .position(|speed| *speed == self.speed)
+            .unwrap_or(0);
+        self.speed = Self::SPEEDS[(index + 1) % Self::SPEEDS.len()];
+    }
+
+    /// Requests a jump of `offset_secs` (negative seeks backwards)
+    pub fn seek_by(&mut self, offset_secs: f32) {
+        let offset_ticks = (offset_secs / self.replay.timestep.as_secs_f32()).round() as i64;
+        let target = (self.cursor as i64 + offset_ticks).clamp(0, self.replay.inputs.len() as i64);
+        let target = target as usize;
+
+        if target < self.cursor {
+            self.restart_requested = true;
+        }
+        self.seek_target = Some(target);
+    }
+}
+
+/// Result of comparing a played-back run against its recorded score
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ReplayVerification {
+    pub expected_score: u32,
+    pub actual_score: u32,
+}
+
+impl ReplayVerification {
+    pub fn matches(&self) -> bool {
+        self.expected_score == self.actual_score
+    }
+}
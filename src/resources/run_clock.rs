@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Gameplay time elapsed in the current run. Unlike `Time::elapsed`, it starts at
+/// zero every run and ignores time spent in menus, so replays line up exactly.
+#[derive(Resource, Default)]
+pub struct RunClock {
+    pub elapsed: Duration,
+}
+
+impl RunClock {
+    pub fn tick(&mut self, delta: Duration) {
+        self.elapsed += delta;
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
@@ -0,0 +1,39 @@
+use crate::resources::{LeaderboardEntry, Migration, StatTotals, StoredFile};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What happened in the run that just ended, shown on the first page of the
+/// game-over screen. "EXPORT" writes it to `run_report.json` next to the
+/// save file, replacing the last one exported.
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RunReport {
+    /// When the run ended, in seconds since the Unix epoch
+    pub date: u64,
+    /// Title of the run's leaderboard (see `LeaderboardId::title`), e.g. "Endless - Hard"
+    pub board: String,
+    pub difficulty: String,
+    pub seed: u64,
+    pub score: u32,
+    pub max_combo: u32,
+    /// Seconds survived
+    pub duration: f32,
+    /// Share of shots that hit, from 0 to 1
+    pub accuracy: Option<f32>,
+    pub stats: StatTotals,
+    /// Top entry of the run's board before this run, if it had one
+    pub personal_best: Option<LeaderboardEntry>,
+}
+
+impl StoredFile for RunReport {
+    const FILE_NAME: &'static str = "run_report.json";
+    const MIGRATIONS: &'static [Migration] = &[];
+}
+
+impl RunReport {
+    /// Whether the run beat the best score on its board
+    pub fn is_personal_best(&self) -> bool {
+        self.personal_best
+            .as_ref()
+            .is_none_or(|best| self.score > best.score)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::components::{AIState, AsteroidType, BossState, BossType, EnemyType, PowerUpType};
+use crate::resources::{
+    DifficultySettings, GamePhase, GameScore, Migration, PlayerPowerUps, StatTotals, StoredFile,
+    WaveProgress, data_dir,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A timer's length and how far it has run
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct TimerState {
+    pub duration: Duration,
+    pub elapsed: Duration,
+}
+
+impl TimerState {
+    pub fn of(timer: &Timer) -> Self {
+        Self {
+            duration: timer.duration(),
+            elapsed: timer.elapsed(),
+        }
+    }
+
+    pub fn to_timer(self, mode: TimerMode) -> Timer {
+        let mut timer = Timer::new(self.duration, mode);
+        timer.set_elapsed(self.elapsed);
+        timer
+    }
+}
+
+/// Where something is in the playfield and how it moves
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Motion {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub velocity: [f32; 2],
+}
+
+impl Motion {
+    pub fn new(translation: Vec3, rotation: Quat, velocity: Vec2) -> Self {
+        Self {
+            translation: translation.to_array(),
+            rotation: rotation.to_array(),
+            velocity: velocity.to_array(),
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        // Kept bit for bit as simulated; only a damaged file needs normalizing
+        let rotation = Quat::from_array(self.rotation);
+        let rotation = if rotation.is_normalized() {
+            rotation
+        } else {
+            rotation.normalize()
+        };
+        Transform::from_translation(Vec3::from_array(self.translation)).with_rotation(rotation)
+    }
+
+    pub fn velocity(&self) -> Vec2 {
+        Vec2::from_array(self.velocity)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub motion: Motion,
+    /// Time left on the respawn invincibility, if it is still running
+    pub invincibility: Option<TimerState>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PulseSnapshot {
+    pub timer: TimerState,
+    pub base_scale: f32,
+    pub amplitude: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AsteroidSnapshot {
+    pub size: u32,
+    pub asteroid_type: AsteroidType,
+    pub motion: Motion,
+    pub angular_velocity: f32,
+    pub health: u32,
+    pub max_health: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemySnapshot {
+    pub enemy_type: EnemyType,
+    pub speed: f32,
+    pub last_shot_time: f32,
+    pub shot_cooldown: f32,
+    pub motion: Motion,
+    pub health: u32,
+    pub max_health: u32,
+    pub ai_state: AIState,
+    pub ai_target: [f32; 2],
+    pub ai_timer: TimerState,
+    pub pulse: Option<PulseSnapshot>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BossSnapshot {
+    pub boss_type: BossType,
+    pub state: BossState,
+    pub motion: Motion,
+    pub health: u32,
+    pub max_health: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BulletSnapshot {
+    pub motion: Motion,
+    pub lifetime: TimerState,
+    pub color: [f32; 4],
+    /// Pierces done and allowed, for piercing bullets
+    pub piercing: Option<(u32, u32)>,
+    /// Blast radius and damage, for explosive bullets
+    pub explosive: Option<(f32, u32)>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemyBulletSnapshot {
+    pub motion: Motion,
+    pub lifetime: TimerState,
+    pub color: [f32; 4],
+    pub damage: u32,
+    pub is_explosive: bool,
+    pub pulse: Option<PulseSnapshot>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerUpSnapshot {
+    pub power_type: PowerUpType,
+    pub position: [f32; 2],
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerUpEffectSnapshot {
+    pub power_type: PowerUpType,
+    pub timer: TimerState,
+}
+
+/// Campaign objective counters of the attempt in progress
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    pub level: String,
+    pub destroyed: u32,
+    pub hits: u32,
+    pub boss_defeated: bool,
+    pub beacon_health: Option<u32>,
+}
+
+/// The spawn timers and the boss and phase bookkeeping
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SpawnerSnapshot {
+    pub asteroid_timer: TimerState,
+    pub enemy_timer: TimerState,
+    pub enemy_last_spawn_score: u32,
+    pub powerup_timer: TimerState,
+    pub fire_timer: TimerState,
+    pub phase: GamePhase,
+    pub phase_transition: Option<TimerState>,
+    pub asteroid_spawn_multiplier: f32,
+    pub bosses_spawned: Vec<BossType>,
+}
+
+/// A run left with "Save & Quit", kept in `suspended_run.json` until the
+/// player continues it from the main menu.
+///
+/// Everything the simulation reads is here, including the random number
+/// streams, so the resumed run carries on as it would have.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RunSnapshot {
+    pub difficulty: DifficultySettings,
+    /// Wave file of the run, like `ActiveWaves`
+    pub waves: Option<String>,
+    pub campaign: Option<CampaignProgress>,
+    pub wave_progress: Option<WaveProgress>,
+    pub seed: u64,
+    pub gameplay_rng: u64,
+    pub cosmetic_rng: u64,
+    pub run_clock: Duration,
+    pub score: GameScore,
+    /// The run's tally so far; it is already in the lifetime stats
+    pub stats: StatTotals,
+    pub lives: u32,
+    pub max_lives: u32,
+    pub powerups: PlayerPowerUps,
+    pub effects: Vec<PowerUpEffectSnapshot>,
+    pub spawners: SpawnerSnapshot,
+    pub player: PlayerSnapshot,
+    pub asteroids: Vec<AsteroidSnapshot>,
+    pub enemies: Vec<EnemySnapshot>,
+    pub bosses: Vec<BossSnapshot>,
+    pub bullets: Vec<BulletSnapshot>,
+    pub enemy_bullets: Vec<EnemyBulletSnapshot>,
+    pub pickups: Vec<PowerUpSnapshot>,
+}
+
+impl StoredFile for RunSnapshot {
+    const FILE_NAME: &'static str = "suspended_run.json";
+    const MIGRATIONS: &'static [Migration] = &[];
+}
+
+impl RunSnapshot {
+    /// Whether there is a suspended run to continue
+    pub fn exists() -> bool {
+        data_dir().join(Self::FILE_NAME).is_file()
+    }
+}
+
+/// The snapshot the run being started picks up from. Removed once it has
+/// been applied.
+#[derive(Resource)]
+pub struct ResumeRun(pub RunSnapshot);
@@ -1,5 +1,19 @@
+use crate::resources::{
+    LEADERBOARD_SIZE, LeaderboardEntry, LifetimeStats, Migration, StatTotals, StoredFile, total,
+    unix_now,
+};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Best result on one campaign level
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LevelRecord {
+    /// 1 to 3 once the level has been completed
+    pub stars: u8,
+    pub best_score: u32,
+}
 
 #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
 pub struct SaveData {
@@ -8,7 +22,20 @@ pub struct SaveData {
     pub games_played: u32,
     pub asteroids_destroyed: u32,
     pub power_ups_collected: u32,
+    /// Longest kill combo in any run
+    pub max_combo: u32,
     pub last_difficulty: String,
+    /// Campaign results by level id
+    pub campaign: BTreeMap<String, LevelRecord>,
+    /// Top scores by board key (see `LeaderboardId::key`), best first
+    pub leaderboards: BTreeMap<String, Vec<LeaderboardEntry>>,
+    /// Name last entered for a leaderboard, offered again next time
+    pub player_name: String,
+    /// Kills, shots, deaths and power-ups by type, and time per difficulty
+    pub stats: LifetimeStats,
+    /// Unlocked achievements by id, with when they were unlocked (seconds
+    /// since the Unix epoch)
+    pub achievements: BTreeMap<String, u64>,
 }
 
 impl Default for SaveData {
@@ -19,45 +46,111 @@ impl Default for SaveData {
             games_played: 0,
             asteroids_destroyed: 0,
             power_ups_collected: 0,
+            max_combo: 0,
             last_difficulty: "Normal".to_string(),
+            campaign: BTreeMap::new(),
+            leaderboards: BTreeMap::new(),
+            player_name: String::new(),
+            stats: LifetimeStats::default(),
+            achievements: BTreeMap::new(),
         }
     }
 }
 
-impl SaveData {
-    pub fn load() -> Self {
-        if let Ok(data) = std::fs::read_to_string("save_data.json") {
-            if let Ok(save_data) = serde_json::from_str(&data) {
-                return save_data;
-            }
-        }
-        Self::default()
-    }
+impl StoredFile for SaveData {
+    const FILE_NAME: &'static str = "save_data.json";
+    const MIGRATIONS: &'static [Migration] = &[migrate_save_v1];
+}
 
-    pub fn save(&self) {
-        if let Ok(data) = serde_json::to_string_pretty(self) {
-            let _ = std::fs::write("save_data.json", data);
-        }
-    }
+// Version 1, the unversioned file of the first release, had only the high
+// score, lifetime totals and last difficulty
+fn migrate_save_v1(fields: &mut Map<String, Value>) {
+    fields.entry("max_combo").or_insert(Value::from(0));
+    fields
+        .entry("campaign")
+        .or_insert(Value::Object(Map::new()));
+    fields
+        .entry("leaderboards")
+        .or_insert(Value::Object(Map::new()));
+    fields.entry("player_name").or_insert(Value::from(""));
+    let stats = serde_json::to_value(LifetimeStats::default()).unwrap_or_default();
+    fields.entry("stats").or_insert(stats);
+    fields
+        .entry("achievements")
+        .or_insert(Value::Object(Map::new()));
+}
 
+impl SaveData {
     pub fn update_high_score(&mut self, new_score: u32) {
         if new_score > self.high_score {
             self.high_score = new_score;
         }
     }
 
+    pub fn update_max_combo(&mut self, combo: u32) {
+        self.max_combo = self.max_combo.max(combo);
+    }
+
     pub fn increment_games_played(&mut self) {
         self.games_played += 1;
     }
 
-    #[allow(dead_code)]
-    pub fn add_asteroids_destroyed(&mut self, count: u32) {
-        self.asteroids_destroyed += count;
+    /// Adds a run's kills, shots, deaths and power-ups to the lifetime stats
+    pub fn add_stats(&mut self, totals: &StatTotals) {
+        self.asteroids_destroyed += total(&totals.asteroids);
+        self.power_ups_collected += total(&totals.power_ups);
+        self.stats.totals.add(totals);
+    }
+
+    pub fn has_achievement(&self, id: &str) -> bool {
+        self.achievements.contains_key(id)
+    }
+
+    /// Records an achievement as unlocked now; false if it already was
+    pub fn unlock_achievement(&mut self, id: &str) -> bool {
+        if self.has_achievement(id) {
+            return false;
+        }
+        self.achievements.insert(id.to_string(), unix_now());
+        true
+    }
+
+    /// Stars earned on a campaign level, 0 if it was never completed
+    pub fn level_stars(&self, level_id: &str) -> u8 {
+        self.campaign.get(level_id).map_or(0, |record| record.stars)
+    }
+
+    /// Keeps the best stars and score reached on a completed level
+    pub fn record_level(&mut self, level_id: &str, stars: u8, score: u32) {
+        let record = self.campaign.entry(level_id.to_string()).or_default();
+        record.stars = record.stars.max(stars);
+        record.best_score = record.best_score.max(score);
+    }
+
+    pub fn leaderboard(&self, board: &str) -> &[LeaderboardEntry] {
+        self.leaderboards.get(board).map_or(&[], Vec::as_slice)
+    }
+
+    /// Place `score` would take on `board`, from 1, if it makes the table.
+    /// Ties go below the scores already there.
+    pub fn leaderboard_rank(&self, board: &str, score: u32) -> Option<usize> {
+        let rank = self
+            .leaderboard(board)
+            .iter()
+            .filter(|entry| entry.score >= score)
+            .count()
+            + 1;
+        (score > 0 && rank <= LEADERBOARD_SIZE).then_some(rank)
     }
 
-    #[allow(dead_code)]
-    pub fn increment_power_ups_collected(&mut self) {
-        self.power_ups_collected += 1;
+    /// Puts `entry` in its place on `board`, dropping whatever falls off the end
+    pub fn add_leaderboard_entry(&mut self, board: &str, entry: LeaderboardEntry) {
+        let Some(rank) = self.leaderboard_rank(board, entry.score) else {
+            return;
+        };
+        let entries = self.leaderboards.entry(board.to_string()).or_default();
+        entries.insert(rank - 1, entry);
+        entries.truncate(LEADERBOARD_SIZE);
     }
 
     pub fn add_play_time(&mut self, time: f32) {
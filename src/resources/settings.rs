@@ -1,10 +1,23 @@
+use crate::resources::{
+    DifficultyId, DifficultySettings, GamepadControls, InputBindings, Migration, StoredFile,
+    key_name,
+};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::time::Duration;
 
 #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
 pub struct GameSettings {
     pub graphics: GraphicsSettings,
     pub audio: AudioSettings,
+    pub simulation: SimulationSettings,
+    /// Values for the Custom difficulty, edited on the difficulty menu
+    pub custom_difficulty: DifficultySettings,
+    /// Key bindings, edited on the Controls page
+    pub controls: InputBindings,
+    /// Stick mode, deadzones and sensitivity for controllers
+    pub gamepad: GamepadControls,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -21,6 +34,33 @@ pub struct AudioSettings {
     pub music_volume: f32,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationSettings {
+    pub tick_rate: f64, // gameplay ticks per second, independent of frame rate
+}
+
+impl Default for SimulationSettings {
+    fn default() -> Self {
+        Self { tick_rate: 60.0 }
+    }
+}
+
+impl SimulationSettings {
+    pub const MIN_TICK_RATE: f64 = 10.0;
+    pub const MAX_TICK_RATE: f64 = 1000.0;
+
+    /// Duration of one gameplay tick, with the rate clamped to a sane range
+    pub fn timestep(&self) -> Duration {
+        let tick_rate = if self.tick_rate.is_finite() {
+            self.tick_rate
+                .clamp(Self::MIN_TICK_RATE, Self::MAX_TICK_RATE)
+        } else {
+            SimulationSettings::default().tick_rate
+        };
+        Duration::from_secs_f64(1.0 / tick_rate)
+    }
+}
+
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
@@ -34,23 +74,54 @@ impl Default for GameSettings {
                 sfx_volume: 1.0,
                 music_volume: 1.0,
             },
+            simulation: SimulationSettings::default(),
+            custom_difficulty: DifficultySettings::custom(),
+            controls: InputBindings::default(),
+            gamepad: GamepadControls::default(),
         }
     }
 }
 
-impl GameSettings {
-    pub fn load() -> Self {
-        if let Ok(data) = std::fs::read_to_string("settings.json") {
-            if let Ok(settings) = serde_json::from_str(&data) {
-                return settings;
-            }
+impl StoredFile for GameSettings {
+    const FILE_NAME: &'static str = "settings.json";
+    const MIGRATIONS: &'static [Migration] = &[migrate_settings_v1];
+
+    fn after_load(&mut self) {
+        self.custom_difficulty.id = DifficultyId::Custom;
+        self.custom_difficulty.sanitize();
+        self.gamepad.sanitize();
+        for (action, other, key) in self.controls.conflicts() {
+            warn!(
+                "{} is bound to both {:?} and {:?}",
+                key_name(key),
+                action,
+                other
+            );
         }
-        Self::default()
     }
+}
 
-    pub fn save(&self) {
-        if let Ok(data) = serde_json::to_string_pretty(self) {
-            let _ = std::fs::write("settings.json", data);
-        }
+// Version 1, the unversioned file of the first release, had only the
+// graphics and audio sections
+fn migrate_settings_v1(fields: &mut Map<String, Value>) {
+    if !fields.contains_key("simulation")
+        && let Ok(simulation) = serde_json::to_value(SimulationSettings::default())
+    {
+        fields.insert("simulation".to_string(), simulation);
+    }
+    if !fields.contains_key("custom_difficulty")
+        && let Ok(custom) = serde_json::to_value(DifficultySettings::custom())
+    {
+        fields.insert("custom_difficulty".to_string(), custom);
+    }
+    if !fields.contains_key("controls")
+        && let Ok(controls) = serde_json::to_value(InputBindings::default())
+    {
+        fields.insert("controls".to_string(), controls);
+    }
+    if !fields.contains_key("gamepad")
+        && let Ok(gamepad) = serde_json::to_value(GamepadControls::default())
+    {
+        fields.insert("gamepad".to_string(), gamepad);
     }
 }
@@ -0,0 +1,129 @@
+use crate::components::{Collider, CollisionLayers};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+/// Side length of one grid cell, about the diameter of a large asteroid
+pub const SPATIAL_CELL_SIZE: f32 = 64.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    pub position: Vec2,
+    /// Bounding radius of the collider around `position`
+    pub radius: f32,
+    /// Layers of the entity's collider
+    pub layers: CollisionLayers,
+}
+
+/// Broad phase shared by every collision system.
+///
+/// Rebuilt from every `Collider` once per gameplay tick, after movement.
+/// Each entry is stored in the cell containing its centre; queries widen
+/// their search by the largest radius in the cells, so entries are still
+/// found from neighbouring cells. Colliders bigger than a cell (bosses) are
+/// kept in a short separate list that every query checks, so they do not
+/// widen every search. Entries come back in a fixed order (cells row by row,
+/// then insertion order), which keeps collision results deterministic.
+#[derive(Resource)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<SpatialEntry>>,
+    oversized: Vec<SpatialEntry>,
+    max_radius: f32,
+    len: usize,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::new(SPATIAL_CELL_SIZE)
+    }
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            oversized: Vec::new(),
+            max_radius: 0.0,
+            len: 0,
+        }
+    }
+
+    /// Empties the grid, keeping the storage of cells that were in use
+    pub fn clear(&mut self) {
+        self.cells.retain(|_, entries| {
+            let in_use = !entries.is_empty();
+            entries.clear();
+            in_use
+        });
+        self.oversized.clear();
+        self.max_radius = 0.0;
+        self.len = 0;
+    }
+
+    pub fn insert(&mut self, entity: Entity, transform: &Transform, collider: &Collider) {
+        let entry = SpatialEntry {
+            entity,
+            position: transform.translation.truncate(),
+            radius: collider.bounding_radius(transform),
+            layers: collider.layers,
+        };
+
+        if entry.radius > self.cell_size {
+            self.oversized.push(entry);
+        } else {
+            self.cells
+                .entry(self.cell_of(entry.position))
+                .or_default()
+                .push(entry);
+            self.max_radius = self.max_radius.max(entry.radius);
+        }
+        self.len += 1;
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.len
+    }
+
+    /// Entries on one of the `layers` whose bounding circle comes within
+    /// `reach` of `center`. Candidates still need an exact `Collider` test.
+    pub fn query_circle(
+        &self,
+        center: Vec2,
+        reach: f32,
+        layers: CollisionLayers,
+    ) -> impl Iterator<Item = &SpatialEntry> {
+        let extent = Vec2::splat(reach + self.max_radius);
+        let min_cell = self.cell_of(center - extent);
+        let max_cell = self.cell_of(center + extent);
+
+        (min_cell.y..=max_cell.y)
+            .flat_map(move |y| (min_cell.x..=max_cell.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .chain(self.oversized.iter())
+            .filter(move |entry| {
+                entry.layers.intersects(layers)
+                    && entry.position.distance(center) <= reach + entry.radius
+            })
+    }
+
+    /// Possible hits for `collider` among the given `layers` it is masked to hit
+    pub fn query_collider(
+        &self,
+        transform: &Transform,
+        collider: &Collider,
+        layers: CollisionLayers,
+    ) -> impl Iterator<Item = &SpatialEntry> {
+        self.query_circle(
+            transform.translation.truncate(),
+            collider.bounding_radius(transform),
+            collider.mask & layers,
+        )
+    }
+
+    fn cell_of(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+}
@@ -0,0 +1,99 @@
+use crate::components::AsteroidType;
+use crate::resources::read_tuning_file;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Asset path of the spawn tables
+pub const SPAWNING_PATH: &str = "tuning/spawning.json";
+
+// Shipped tables, used when the file is missing or broken
+const BUILT_IN_SPAWNING: &str = include_str!("../../assets/tuning/spawning.json");
+
+/// Asteroid sizes `min..=max`; each size is `rarity_factor` times rarer
+/// than the one below it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AsteroidSizeTable {
+    pub min: u32,
+    pub max: u32,
+    pub rarity_factor: f32,
+    pub base_probability: f32,
+}
+
+/// Chance of a new asteroid being `asteroid_type`. Chances are checked in
+/// order; whatever they leave below 1.0 becomes Normal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AsteroidTypeChance {
+    #[serde(rename = "type")]
+    pub asteroid_type: AsteroidType,
+    pub chance: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemySpawnTable {
+    /// Seconds between enemy spawns
+    pub interval: f32,
+    /// An enemy also spawns every time the score grows by this much
+    pub score_interval: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerUpSpawnTable {
+    /// Seconds between power-up spawn attempts
+    pub interval: f32,
+}
+
+/// How often and what to spawn, as written in `spawning.json`. The spawn
+/// resources (`AsteroidSizeGenerator`, `AsteroidTypeGenerator`,
+/// `EnemySpawnTimer`, `PowerUpSpawnTimer`) are built from these tables.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct SpawnTables {
+    pub asteroid_sizes: AsteroidSizeTable,
+    pub asteroid_types: Vec<AsteroidTypeChance>,
+    pub enemy_spawn: EnemySpawnTable,
+    pub powerup_spawn: PowerUpSpawnTable,
+}
+
+impl Default for SpawnTables {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+impl SpawnTables {
+    pub fn built_in() -> Self {
+        Self::parse(BUILT_IN_SPAWNING).expect("built-in spawning.json is valid")
+    }
+
+    pub fn load() -> Self {
+        match read_tuning_file(SPAWNING_PATH) {
+            Some(data) => Self::parse(&data).unwrap_or_else(|error| {
+                warn!("Ignoring {}: {}", SPAWNING_PATH, error);
+                Self::built_in()
+            }),
+            None => Self::built_in(),
+        }
+    }
+
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let tables: SpawnTables = serde_json::from_str(data).map_err(|error| error.to_string())?;
+
+        let sizes = &tables.asteroid_sizes;
+        if sizes.min == 0 || sizes.min > sizes.max {
+            return Err("asteroid_sizes needs 1 <= min <= max".to_string());
+        }
+        if sizes.rarity_factor <= 0.0 || sizes.base_probability <= 0.0 {
+            return Err("asteroid_sizes factors must be positive".to_string());
+        }
+        if tables.asteroid_types.iter().any(|entry| entry.chance < 0.0) {
+            return Err("asteroid_types chances can't be negative".to_string());
+        }
+        if tables.enemy_spawn.interval <= 0.0
+            || tables.enemy_spawn.score_interval == 0
+            || tables.powerup_spawn.interval <= 0.0
+        {
+            return Err("spawn intervals must be positive".to_string());
+        }
+
+        Ok(tables)
+    }
+}
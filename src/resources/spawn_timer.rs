@@ -4,3 +4,11 @@ use bevy::prelude::*;
 pub struct AsteroidSpawnTimer {
     pub timer: Timer,
 }
+
+impl Default for AsteroidSpawnTimer {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(2.0, TimerMode::Repeating),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::components::{AsteroidType, BossType, EnemyType, PowerUpType};
+use crate::events::Hazard;
+use crate::resources::DifficultyId;
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Counts of what happened, for one run (`RunStats`) or summed over every
+/// run (`LifetimeStats`). Counts by type are keyed by the type's name.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatTotals {
+    pub asteroids: BTreeMap<String, u32>,
+    pub enemies: BTreeMap<String, u32>,
+    pub bosses: BTreeMap<String, u32>,
+    /// Boss phases cleared, including each boss's last one
+    pub boss_phases: u32,
+    /// Boss phases cleared without the ship being hit
+    #[serde(default)]
+    pub flawless_boss_phases: u32,
+    pub shots_fired: u32,
+    /// Shots that damaged something; a piercing shot counts once
+    pub shots_hit: u32,
+    /// Lives lost, by what hit the ship
+    pub deaths: BTreeMap<String, u32>,
+    pub power_ups: BTreeMap<String, u32>,
+}
+
+fn bump(counts: &mut BTreeMap<String, u32>, key: String) {
+    *counts.entry(key).or_default() += 1;
+}
+
+fn merge(counts: &mut BTreeMap<String, u32>, other: &BTreeMap<String, u32>) {
+    for (key, count) in other {
+        *counts.entry(key.clone()).or_default() += count;
+    }
+}
+
+/// Sum of a breakdown by type
+pub fn total(counts: &BTreeMap<String, u32>) -> u32 {
+    counts.values().sum()
+}
+
+impl StatTotals {
+    pub fn record_asteroid(&mut self, asteroid_type: &AsteroidType) {
+        bump(&mut self.asteroids, asteroid_type.key().to_string());
+    }
+
+    pub fn record_enemy(&mut self, enemy_type: &EnemyType) {
+        bump(&mut self.enemies, enemy_type.to_string());
+    }
+
+    pub fn record_boss(&mut self, boss_type: &BossType) {
+        bump(&mut self.bosses, boss_type.to_string());
+        self.boss_phases += 1;
+    }
+
+    pub fn record_boss_phase(&mut self) {
+        self.boss_phases += 1;
+    }
+
+    pub fn record_death(&mut self, cause: Hazard) {
+        bump(&mut self.deaths, cause.key().to_string());
+    }
+
+    pub fn record_power_up(&mut self, power_type: &PowerUpType) {
+        bump(&mut self.power_ups, power_type.key().to_string());
+    }
+
+    /// Share of shots that hit, from 0 to 1; `None` before the first shot
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.shots_fired > 0).then(|| self.shots_hit as f32 / self.shots_fired as f32)
+    }
+
+    pub fn add(&mut self, other: &StatTotals) {
+        merge(&mut self.asteroids, &other.asteroids);
+        merge(&mut self.enemies, &other.enemies);
+        merge(&mut self.bosses, &other.bosses);
+        self.boss_phases += other.boss_phases;
+        self.flawless_boss_phases += other.flawless_boss_phases;
+        self.shots_fired += other.shots_fired;
+        self.shots_hit += other.shots_hit;
+        merge(&mut self.deaths, &other.deaths);
+        merge(&mut self.power_ups, &other.power_ups);
+    }
+}
+
+/// Time spent on one difficulty
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyStats {
+    /// Seconds of gameplay, pauses and menus not included
+    pub play_time: f32,
+    /// Seconds survived in the longest run
+    pub longest_run: f32,
+}
+
+/// Statistics over every run, kept in the save file
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    pub totals: StatTotals,
+    /// By difficulty id; the menu shows the preset's current name
+    pub difficulties: BTreeMap<DifficultyId, DifficultyStats>,
+}
+
+impl LifetimeStats {
+    /// Adds `seconds` of play on `difficulty`, in a run that has lasted
+    /// `run_length` seconds so far
+    pub fn add_play_time(&mut self, difficulty: DifficultyId, seconds: f32, run_length: f32) {
+        let stats = self.difficulties.entry(difficulty).or_default();
+        stats.play_time += seconds;
+        stats.longest_run = stats.longest_run.max(run_length);
+    }
+}
+
+/// What has happened in the run in progress, tallied from the gameplay
+/// events. `unsaved` holds what the lifetime stats haven't taken yet.
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub totals: StatTotals,
+    pub unsaved: StatTotals,
+    /// Seconds of gameplay not yet in the lifetime stats
+    pub unsaved_time: f32,
+    // Bullets that have already counted as a hit
+    hit_bullets: EntityHashSet,
+    // Whether the ship has been hit since the current boss phase began
+    hit_this_phase: bool,
+}
+
+impl RunStats {
+    /// Applies `change` to both the run's totals and the unsaved ones
+    pub fn record(&mut self, change: impl Fn(&mut StatTotals)) {
+        change(&mut self.totals);
+        change(&mut self.unsaved);
+    }
+
+    /// Counts a hit by `bullet`, unless it has already hit something
+    pub fn record_hit(&mut self, bullet: Entity) {
+        if self.hit_bullets.insert(bullet) {
+            self.record(|totals| totals.shots_hit += 1);
+        }
+    }
+
+    pub fn record_player_hit(&mut self) {
+        self.hit_this_phase = true;
+    }
+
+    /// A boss has appeared; its first phase starts clean
+    pub fn start_boss_phase(&mut self) {
+        self.hit_this_phase = false;
+    }
+
+    /// A boss phase has been cleared, and the next one starts clean
+    pub fn finish_boss_phase(&mut self) {
+        if !self.hit_this_phase {
+            self.record(|totals| totals.flawless_boss_phases += 1);
+        }
+        self.hit_this_phase = false;
+    }
+
+    /// Takes what the lifetime stats haven't been given yet
+    pub fn take_unsaved(&mut self) -> (StatTotals, f32) {
+        (
+            std::mem::take(&mut self.unsaved),
+            std::mem::take(&mut self.unsaved_time),
+        )
+    }
+
+    /// Picks a suspended run's totals back up; they are already in the
+    /// lifetime stats
+    pub fn resume(&mut self, totals: StatTotals) {
+        *self = Self {
+            totals,
+            ..default()
+        };
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable that overrides where save and settings files live
+pub const DATA_DIR_ENV: &str = "RUSTY_ASTEROID_DATA_DIR";
+
+/// Folder created inside the platform data directory
+const APP_DIR: &str = "rusty_asteroid";
+
+/// Field every stored file carries its format version in
+const VERSION_KEY: &str = "version";
+
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Keep save and settings files in `dir` (`--data-dir`). Call it before
+/// anything is loaded; only the first call counts.
+pub fn set_data_dir(dir: impl Into<PathBuf>) {
+    let _ = DATA_DIR_OVERRIDE.set(dir.into());
+}
+
+/// Where save and settings files live: the `--data-dir` or
+/// `RUSTY_ASTEROID_DATA_DIR` override, else `rusty_asteroid/` in the platform
+/// data directory (`$XDG_DATA_HOME` or `~/.local/share` on Linux,
+/// `~/Library/Application Support` on macOS, `%APPDATA%` on Windows), else
+/// the working directory.
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = DATA_DIR_OVERRIDE.get() {
+        return dir.clone();
+    }
+    if let Some(dir) = env_path(DATA_DIR_ENV) {
+        return dir;
+    }
+    platform_data_dir().map_or_else(|| PathBuf::from("."), |dir| dir.join(APP_DIR))
+}
+
+fn platform_data_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        env_path("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_path("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env_path("XDG_DATA_HOME").or_else(|| env_path("HOME").map(|home| home.join(".local/share")))
+    }
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Copy of the previous contents, kept by every save
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[derive(Debug)]
+pub enum StorageError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    NotAnObject,
+    BadVersion,
+    /// Written by a newer build of the game
+    NewerVersion(u32),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io(error) => write!(f, "{}", error),
+            StorageError::Json(error) => write!(f, "invalid JSON: {}", error),
+            StorageError::NotAnObject => write!(f, "expected a JSON object"),
+            StorageError::BadVersion => write!(f, "\"version\" is not a positive integer"),
+            StorageError::NewerVersion(version) => {
+                write!(f, "format version {} is newer than this game", version)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<std::io::Error> for StorageError {
+    fn from(error: std::io::Error) -> Self {
+        StorageError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(error: serde_json::Error) -> Self {
+        StorageError::Json(error)
+    }
+}
+
+/// Problems met loading the save and settings files, shown once the game
+/// is up
+#[derive(Resource, Default)]
+pub struct StorageErrors(pub Vec<String>);
+
+/// Upgrades the fields of a file by one format version
+pub type Migration = fn(&mut Map<String, Value>);
+
+/// Something kept in a versioned JSON file in the data directory.
+///
+/// Files are written with a `version` field. Loading runs the migrations
+/// from the file's version up to the current one before deserializing, so
+/// old files keep working; files without a version are version 1.
+pub trait StoredFile: Serialize + DeserializeOwned + Default {
+    /// File name inside the data directory
+    const FILE_NAME: &'static str;
+
+    /// Entry `i` turns a version `i + 1` file into version `i + 2`. Append
+    /// one whenever the format changes.
+    const MIGRATIONS: &'static [Migration];
+
+    /// Version written by this build
+    const VERSION: u32 = Self::MIGRATIONS.len() as u32 + 1;
+
+    /// Fix-ups after loading, such as clamping values edited by hand
+    fn after_load(&mut self) {}
+
+    fn decode(text: &str) -> Result<Self, StorageError> {
+        let mut value: Value = serde_json::from_str(text)?;
+        let fields = value.as_object_mut().ok_or(StorageError::NotAnObject)?;
+        let version = match fields.remove(VERSION_KEY) {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .filter(|version| *version >= 1)
+                .ok_or(StorageError::BadVersion)?,
+        };
+        if version > Self::VERSION {
+            return Err(StorageError::NewerVersion(version));
+        }
+        for migrate in &Self::MIGRATIONS[version as usize - 1..] {
+            migrate(fields);
+        }
+
+        let mut loaded: Self = serde_json::from_value(value)?;
+        loaded.after_load();
+        Ok(loaded)
+    }
+
+    fn encode(&self) -> Result<String, StorageError> {
+        let mut value = serde_json::to_value(self)?;
+        let fields = value.as_object_mut().ok_or(StorageError::NotAnObject)?;
+        fields.insert(VERSION_KEY.to_string(), Value::from(Self::VERSION));
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// `None` when there is no such file
+    fn read_from(path: &Path) -> Result<Option<Self>, StorageError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::decode(&text).map(Some),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Reads the file from `dir`, falling back to its backup and then to the
+    /// defaults. Returns what went wrong on the way, for the player to see.
+    ///
+    /// An unreadable file is renamed to `*.unreadable` so the next save
+    /// neither overwrites it nor rotates it over a good backup.
+    fn load_from(dir: &Path) -> (Self, Vec<String>) {
+        let path = dir.join(Self::FILE_NAME);
+        let mut problems = Vec::new();
+        match Self::read_from(&path) {
+            Ok(Some(loaded)) => return (loaded, problems),
+            Ok(None) => {}
+            Err(error) => {
+                problems.push(format!("Could not load {}: {}", path.display(), error));
+                let set_aside = with_suffix(&path, ".unreadable");
+                if let Err(error) = std::fs::rename(&path, &set_aside) {
+                    // The next save will copy the broken file over the backup
+                    let message = format!(
+                        "Could not set aside {} as {}: {}",
+                        path.display(),
+                        set_aside.display(),
+                        error
+                    );
+                    error!("{}", message);
+                    problems.push(message);
+                }
+            }
+        }
+
+        let backup = backup_path(&path);
+        match Self::read_from(&backup) {
+            Ok(Some(loaded)) => {
+                if !problems.is_empty() {
+                    problems.push(format!("Restored {} from its backup", Self::FILE_NAME));
+                }
+                (loaded, problems)
+            }
+            Ok(None) => (Self::default(), problems),
+            Err(error) => {
+                problems.push(format!("Could not load {}: {}", backup.display(), error));
+                (Self::default(), problems)
+            }
+        }
+    }
+
+    /// Writes the file to `dir` without ever leaving a half-written one: the
+    /// new contents go to `*.tmp` and are renamed over the old file once they
+    /// are on disk, after the old file is copied to `*.bak`.
+    fn save_to(&self, dir: &Path) -> Result<(), StorageError> {
+        let path = dir.join(Self::FILE_NAME);
+        let text = self.encode()?;
+        std::fs::create_dir_all(dir)?;
+
+        let temp = with_suffix(&path, ".tmp");
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(text.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        if path.exists() {
+            std::fs::copy(&path, backup_path(&path))?;
+        }
+        std::fs::rename(&temp, &path)?;
+        Ok(())
+    }
+
+    /// Loads from the data directory. Builds before the data directory kept
+    /// their files in the working directory; such a file is read when the
+    /// data directory has none, and the next save moves it over.
+    fn load() -> (Self, Vec<String>) {
+        let dir = data_dir();
+        let path = dir.join(Self::FILE_NAME);
+        let legacy = Path::new(Self::FILE_NAME);
+        if !path.exists() && !backup_path(&path).exists() && legacy.is_file() {
+            return Self::load_from(Path::new("."));
+        }
+        Self::load_from(&dir)
+    }
+
+    fn save(&self) -> Result<(), StorageError> {
+        self.save_to(&data_dir())
+    }
+}
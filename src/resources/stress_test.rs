@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use std::time::{Duration, Instant};
+
+/// Benchmark scene enabled with `--stress <count>`: keeps `asteroid_count`
+/// asteroids and `bullet_count` bullets alive and reports gameplay tick times.
+#[derive(Resource)]
+pub struct StressTest {
+    pub asteroid_count: usize,
+    pub bullet_count: usize,
+    pub tick_started: Option<Instant>,
+    pub total_tick_time: Duration,
+    pub peak_tick_time: Duration,
+    pub ticks: u32,
+}
+
+impl StressTest {
+    /// Ticks between two timing reports
+    pub const REPORT_INTERVAL: u32 = 120;
+
+    pub fn new(asteroid_count: usize) -> Self {
+        Self {
+            asteroid_count,
+            bullet_count: asteroid_count / 4,
+            tick_started: None,
+            total_tick_time: Duration::ZERO,
+            peak_tick_time: Duration::ZERO,
+            ticks: 0,
+        }
+    }
+
+    pub fn record_tick(&mut self, tick_time: Duration) {
+        self.total_tick_time += tick_time;
+        self.peak_tick_time = self.peak_tick_time.max(tick_time);
+        self.ticks += 1;
+    }
+
+    /// Average and peak tick time once a full interval has been measured
+    pub fn take_report(&mut self) -> Option<(Duration, Duration)> {
+        if self.ticks < Self::REPORT_INTERVAL {
+            return None;
+        }
+
+        let report = (self.total_tick_time / self.ticks, self.peak_tick_time);
+        self.total_tick_time = Duration::ZERO;
+        self.peak_tick_time = Duration::ZERO;
+        self.ticks = 0;
+        Some(report)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::assets::TuningFile;
+use crate::resources::{BossDefinitions, EnemyArchetypes, SpawnTables};
+use bevy::asset::LoadedFolder;
+use bevy::asset::io::file::FileAssetReader;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Bevy's asset folder. The tuning files are read from here at startup and
+/// watched through the asset server afterwards.
+pub const ASSET_DIR: &str = "assets";
+
+/// Where an asset path such as `tuning/enemies.json` is on disk: under
+/// `assets/` next to the executable, or in the crate root under `cargo run`
+pub fn tuning_file_path(asset_path: &str) -> PathBuf {
+    FileAssetReader::get_base_path()
+        .join(ASSET_DIR)
+        .join(asset_path)
+}
+
+/// Reads a tuning file by its asset path. `None` when it doesn't exist, so
+/// the caller can use its built-in copy.
+pub fn read_tuning_file(asset_path: &str) -> Option<String> {
+    std::fs::read_to_string(tuning_file_path(asset_path)).ok()
+}
+
+/// Identifies the enemies, bosses and spawn tables a run is simulated with.
+/// Replays store it, since the same inputs play out differently once any of
+/// them is edited. `bosses` is `None` when `BossPlugin` is left out.
+pub fn tuning_fingerprint(
+    archetypes: &EnemyArchetypes,
+    bosses: Option<&BossDefinitions>,
+    spawn_tables: &SpawnTables,
+) -> u64 {
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    serde_json::to_writer(&mut hasher, &(archetypes, bosses, spawn_tables))
+        .expect("tuning serializes to JSON");
+    hasher.0
+}
+
+// 64-bit FNV-1a, which unlike `DefaultHasher` is the same on every build
+struct Fnv1a(u64);
+
+impl std::io::Write for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Handles that keep the tuning files loaded, so edits to them show up as
+/// `AssetEvent::Modified`
+#[derive(Resource)]
+pub struct TuningFiles {
+    pub difficulties: Handle<TuningFile>,
+    pub enemies: Handle<TuningFile>,
+    pub bosses: Handle<LoadedFolder>,
+    pub spawning: Handle<TuningFile>,
+    pub campaign: Handle<TuningFile>,
+    pub achievements: Handle<TuningFile>,
+}
@@ -0,0 +1,456 @@
+use crate::components::{AsteroidType, BossType, EnemyType, PowerUpType};
+use crate::resources::{BossDefinitions, EnemyArchetypes, PlayfieldBounds, read_tuning_file};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Asset folder holding the wave files
+pub const WAVES_DIR: &str = "tuning/waves";
+
+/// Asset path of the wave file called `name`, e.g. "training"
+pub fn wave_file_path(name: &str) -> String {
+    format!("{}/{}.json", WAVES_DIR, name)
+}
+
+/// Edge of the playfield something enters from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum SpawnEdge {
+    Top,
+    Right,
+    Bottom,
+    Left,
+    /// A random edge
+    #[default]
+    Any,
+}
+
+impl SpawnEdge {
+    /// A random point `offset` beyond this edge
+    pub fn point(self, bounds: &PlayfieldBounds, offset: f32, rng: &mut fastrand::Rng) -> Vec2 {
+        let edge = match self {
+            SpawnEdge::Any => match rng.u32(0..4) {
+                0 => SpawnEdge::Top,
+                1 => SpawnEdge::Right,
+                2 => SpawnEdge::Bottom,
+                _ => SpawnEdge::Left,
+            },
+            edge => edge,
+        };
+
+        match edge {
+            SpawnEdge::Top => Vec2::new(
+                (rng.f32() - 0.5) * bounds.width,
+                bounds.half_height() + offset,
+            ),
+            SpawnEdge::Right => Vec2::new(
+                bounds.half_width() + offset,
+                (rng.f32() - 0.5) * bounds.height,
+            ),
+            SpawnEdge::Bottom => Vec2::new(
+                (rng.f32() - 0.5) * bounds.width,
+                -bounds.half_height() - offset,
+            ),
+            _ => Vec2::new(
+                -bounds.half_width() - offset,
+                (rng.f32() - 0.5) * bounds.height,
+            ),
+        }
+    }
+}
+
+/// Where a batch of asteroids heads once it enters
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum SpawnAim {
+    /// The middle of the playfield
+    #[default]
+    Center,
+    /// Wherever the ship is when the batch spawns
+    Player,
+}
+
+/// `count` asteroids entering from `edge`, `at` seconds into the wave
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AsteroidBatch {
+    pub at: f32,
+    pub count: u32,
+    pub size: u32,
+    #[serde(rename = "type", default)]
+    pub asteroid_type: AsteroidType,
+    #[serde(default)]
+    pub edge: SpawnEdge,
+    /// Speed before the difficulty's asteroid speed multiplier
+    pub speed: f32,
+    /// Random deviation from the aim, in degrees either way
+    #[serde(default = "default_spread")]
+    pub spread: f32,
+    #[serde(default)]
+    pub aim: SpawnAim,
+}
+
+fn default_spread() -> f32 {
+    15.0
+}
+
+/// `count` ships of one enemy archetype entering from `edge`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemySquad {
+    pub at: f32,
+    pub enemy: EnemyType,
+    #[serde(default = "default_count")]
+    pub count: u32,
+    #[serde(default)]
+    pub edge: SpawnEdge,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+/// A power-up dropped somewhere on screen; a random kind when none is given
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerUpDrop {
+    pub at: f32,
+    #[serde(default)]
+    pub powerup: Option<PowerUpType>,
+}
+
+/// A boss that enters `at` seconds into the wave
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BossTrigger {
+    #[serde(default)]
+    pub at: f32,
+    pub boss: BossType,
+}
+
+/// What finishes a wave
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ClearCondition {
+    /// Everything has spawned and no asteroid, enemy or boss is left
+    Destroyed,
+    /// The ship is still flying after this many seconds
+    Survive { seconds: f32 },
+    /// The wave's boss was spawned and beaten
+    BossDefeated,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Wave {
+    /// Shown under "Wave N" on the banner
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub asteroids: Vec<AsteroidBatch>,
+    #[serde(default)]
+    pub enemies: Vec<EnemySquad>,
+    #[serde(default)]
+    pub powerups: Vec<PowerUpDrop>,
+    #[serde(default)]
+    pub boss: Option<BossTrigger>,
+    pub clear: ClearCondition,
+}
+
+/// An authored run: waves played one after another with a banner in
+/// between, replacing the timed spawners while it is active
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WaveFile {
+    pub name: String,
+    /// Seconds the "Wave N" banner shows before each wave
+    #[serde(default = "default_intermission")]
+    pub intermission: f32,
+    pub waves: Vec<Wave>,
+}
+
+fn default_intermission() -> f32 {
+    3.0
+}
+
+impl WaveFile {
+    pub fn load(asset_path: &str) -> Result<Self, String> {
+        let data = read_tuning_file(asset_path).ok_or_else(|| "file not found".to_string())?;
+        Self::parse(&data)
+    }
+
+    pub fn parse(data: &str) -> Result<Self, String> {
+        let mut file: WaveFile = serde_json::from_str(data).map_err(|error| error.to_string())?;
+
+        if file.waves.is_empty() {
+            return Err("a wave file needs at least one wave".to_string());
+        }
+        if file.intermission < 0.0 {
+            return Err("intermission can't be negative".to_string());
+        }
+        for (index, wave) in file.waves.iter_mut().enumerate() {
+            let number = index + 1;
+            if wave.asteroids.iter().any(|batch| batch.size == 0) {
+                return Err(format!("wave {} has an asteroid of size 0", number));
+            }
+            if wave.clear == ClearCondition::BossDefeated && wave.boss.is_none() {
+                return Err(format!("wave {} waits for a boss it never spawns", number));
+            }
+            if let ClearCondition::Survive { seconds } = wave.clear
+                && seconds <= 0.0
+            {
+                return Err(format!("wave {} must last a positive time", number));
+            }
+
+            // The runner walks each list in time order
+            wave.asteroids.sort_by(|a, b| a.at.total_cmp(&b.at));
+            wave.enemies.sort_by(|a, b| a.at.total_cmp(&b.at));
+            wave.powerups.sort_by(|a, b| a.at.total_cmp(&b.at));
+        }
+
+        Ok(file)
+    }
+
+    /// Checks that every enemy and boss the waves name exists
+    pub fn check_ids(
+        &self,
+        archetypes: &EnemyArchetypes,
+        bosses: &BossDefinitions,
+    ) -> Result<(), String> {
+        for (index, wave) in self.waves.iter().enumerate() {
+            if let Some(squad) = wave
+                .enemies
+                .iter()
+                .find(|squad| archetypes.get(&squad.enemy).is_none())
+            {
+                return Err(format!(
+                    "wave {} refers to unknown enemy {}",
+                    index + 1,
+                    squad.enemy
+                ));
+            }
+            if let Some(trigger) = &wave.boss
+                && bosses.get(&trigger.boss).is_none()
+            {
+                return Err(format!(
+                    "wave {} refers to unknown boss {}",
+                    index + 1,
+                    trigger.boss
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Asset path of the wave file new runs play, e.g. from `--waves training`.
+/// Without it runs use the endless timed spawners.
+#[derive(Resource, Clone, Debug)]
+pub struct ActiveWaves {
+    pub path: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WaveStage {
+    /// The banner for the next wave is up
+    Intermission {
+        remaining: f32,
+    },
+    Playing,
+    /// The last wave was cleared; the run ends once the banner is gone
+    Complete {
+        remaining: f32,
+    },
+}
+
+/// Something the current wave spawns this tick
+#[derive(Clone, Debug)]
+pub enum WaveSpawn {
+    Asteroids(AsteroidBatch),
+    Enemies(EnemySquad),
+    PowerUp(PowerUpDrop),
+    Boss(BossTrigger),
+}
+
+/// Where a `WaveRunner` is in its file, kept with a suspended run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WaveProgress {
+    pub wave: usize,
+    pub stage: WaveStage,
+    pub elapsed: f32,
+    pub next_asteroids: usize,
+    pub next_enemies: usize,
+    pub next_powerups: usize,
+    pub boss_spawned: bool,
+}
+
+/// Progress through the active wave file. Present only during runs that
+/// play one; its presence switches the timed spawners off.
+#[derive(Resource, Debug)]
+pub struct WaveRunner {
+    pub waves: Arc<WaveFile>,
+    /// Index of the current (or upcoming) wave
+    pub wave: usize,
+    pub stage: WaveStage,
+    /// Seconds since the current wave started
+    pub elapsed: f32,
+    next_asteroids: usize,
+    next_enemies: usize,
+    next_powerups: usize,
+    boss_spawned: bool,
+}
+
+impl WaveRunner {
+    pub fn new(waves: Arc<WaveFile>) -> Self {
+        let intermission = waves.intermission;
+        Self {
+            waves,
+            wave: 0,
+            stage: WaveStage::Intermission {
+                remaining: intermission,
+            },
+            elapsed: 0.0,
+            next_asteroids: 0,
+            next_enemies: 0,
+            next_powerups: 0,
+            boss_spawned: false,
+        }
+    }
+
+    pub fn current_wave(&self) -> &Wave {
+        &self.waves.waves[self.wave]
+    }
+
+    pub fn progress(&self) -> WaveProgress {
+        WaveProgress {
+            wave: self.wave,
+            stage: self.stage.clone(),
+            elapsed: self.elapsed,
+            next_asteroids: self.next_asteroids,
+            next_enemies: self.next_enemies,
+            next_powerups: self.next_powerups,
+            boss_spawned: self.boss_spawned,
+        }
+    }
+
+    /// Picks up where `progress` left off. Progress past the end of the file
+    /// (it was edited since) is ignored.
+    pub fn resume(&mut self, progress: &WaveProgress) {
+        let Some(wave) = self.waves.waves.get(progress.wave) else {
+            return;
+        };
+        self.next_asteroids = progress.next_asteroids.min(wave.asteroids.len());
+        self.next_enemies = progress.next_enemies.min(wave.enemies.len());
+        self.next_powerups = progress.next_powerups.min(wave.powerups.len());
+        self.boss_spawned = progress.boss_spawned;
+        self.wave = progress.wave;
+        self.stage = progress.stage.clone();
+        self.elapsed = progress.elapsed;
+    }
+
+    /// 1-based number of the current wave, as shown to the player
+    pub fn wave_number(&self) -> usize {
+        self.wave + 1
+    }
+
+    /// Advances the intermission or the wave clock by `delta` seconds
+    pub fn tick(&mut self, delta: f32) {
+        match &mut self.stage {
+            WaveStage::Intermission { remaining } => {
+                *remaining -= delta;
+                if *remaining <= 0.0 {
+                    self.stage = WaveStage::Playing;
+                    self.elapsed = 0.0;
+                }
+            }
+            WaveStage::Playing => self.elapsed += delta,
+            WaveStage::Complete { remaining } => *remaining -= delta,
+        }
+    }
+
+    /// Everything the wave schedules up to now has spawned
+    pub fn all_spawned(&self) -> bool {
+        let wave = self.current_wave();
+        self.next_asteroids == wave.asteroids.len()
+            && self.next_enemies == wave.enemies.len()
+            && self.next_powerups == wave.powerups.len()
+            && (wave.boss.is_none() || self.boss_spawned)
+    }
+
+    /// Whether the current wave's clear condition holds, given how many
+    /// asteroids, enemies and bosses are still around
+    pub fn is_cleared(&self, hostiles_left: usize, bosses_left: usize) -> bool {
+        if self.stage != WaveStage::Playing {
+            return false;
+        }
+        match self.current_wave().clear {
+            ClearCondition::Destroyed => self.all_spawned() && hostiles_left == 0,
+            ClearCondition::Survive { seconds } => self.elapsed >= seconds,
+            ClearCondition::BossDefeated => self.boss_spawned && bosses_left == 0,
+        }
+    }
+
+    /// Moves on to the next wave's intermission, or completes the file
+    pub fn finish_wave(&mut self) {
+        let remaining = self.waves.intermission;
+        if self.wave + 1 < self.waves.waves.len() {
+            self.wave += 1;
+            self.stage = WaveStage::Intermission { remaining };
+        } else {
+            self.stage = WaveStage::Complete { remaining };
+        }
+        self.elapsed = 0.0;
+        self.next_asteroids = 0;
+        self.next_enemies = 0;
+        self.next_powerups = 0;
+        self.boss_spawned = false;
+    }
+
+    /// The last wave is cleared and its banner has been shown
+    pub fn is_finished(&self) -> bool {
+        matches!(self.stage, WaveStage::Complete { remaining } if remaining <= 0.0)
+    }
+
+    /// Takes everything the current wave schedules up to now that hasn't
+    /// spawned yet
+    pub fn take_due(&mut self) -> Vec<WaveSpawn> {
+        let mut due = Vec::new();
+        if self.stage != WaveStage::Playing {
+            return due;
+        }
+
+        let waves = self.waves.clone();
+        let wave = &waves.waves[self.wave];
+        let elapsed = self.elapsed;
+        while let Some(batch) = wave.asteroids.get(self.next_asteroids)
+            && batch.at <= elapsed
+        {
+            due.push(WaveSpawn::Asteroids(batch.clone()));
+            self.next_asteroids += 1;
+        }
+        while let Some(squad) = wave.enemies.get(self.next_enemies)
+            && squad.at <= elapsed
+        {
+            due.push(WaveSpawn::Enemies(squad.clone()));
+            self.next_enemies += 1;
+        }
+        while let Some(drop) = wave.powerups.get(self.next_powerups)
+            && drop.at <= elapsed
+        {
+            due.push(WaveSpawn::PowerUp(drop.clone()));
+            self.next_powerups += 1;
+        }
+        if let Some(trigger) = &wave.boss
+            && !self.boss_spawned
+            && trigger.at <= elapsed
+        {
+            due.push(WaveSpawn::Boss(trigger.clone()));
+            self.boss_spawned = true;
+        }
+        due
+    }
+
+    /// Text of the banner between waves, if one should be up
+    pub fn banner(&self) -> Option<String> {
+        match self.stage {
+            WaveStage::Intermission { .. } => Some(match &self.current_wave().name {
+                Some(name) => format!("Wave {}\n{}", self.wave_number(), name),
+                None => format!("Wave {}", self.wave_number()),
+            }),
+            WaveStage::Playing => None,
+            WaveStage::Complete { .. } => Some("All waves cleared!".to_string()),
+        }
+    }
+}
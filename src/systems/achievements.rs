@@ -0,0 +1,43 @@
+use crate::events::*;
+use crate::resources::*;
+use crate::systems::report_save_error;
+use bevy::prelude::*;
+
+// Unlock the achievements whose conditions now hold, with a toast for each.
+// Runs after `track_game_stats` so lifetime conditions see this frame's tally.
+pub fn check_achievements(
+    mut commands: Commands,
+    achievements: Res<Achievements>,
+    mut save_data: ResMut<SaveData>,
+    run_stats: Res<RunStats>,
+    difficulty: Res<DifficultySettings>,
+    game_score: Res<GameScore>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    let context = AchievementContext {
+        run: &run_stats.totals,
+        lifetime: &save_data.stats,
+        difficulty: difficulty.id,
+        score: game_score.score,
+        max_combo: game_score.max_combo,
+    };
+    let unlocked: Vec<&Achievement> = achievements
+        .iter()
+        .filter(|achievement| {
+            !save_data.has_achievement(&achievement.id) && achievement.is_met(&context)
+        })
+        .collect();
+    if unlocked.is_empty() {
+        return;
+    }
+
+    for achievement in unlocked {
+        save_data.unlock_achievement(&achievement.id);
+        info!("Achievement unlocked: {}", achievement.name);
+        toasts.write(ShowToast::info(format!(
+            "Achievement unlocked: {}",
+            achievement.name
+        )));
+    }
+    report_save_error(&mut commands, SaveData::FILE_NAME, save_data.save());
+}
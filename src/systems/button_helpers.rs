@@ -33,7 +33,7 @@ pub fn spawn_button_with_text(
     // Calculate text position based on button position and text length
     let text_offset_x = text.len() as f32 * 7.0; // Approximate character width
     let text_y = window_height * 0.5 - position.y - 12.0;
-    let text_x = window_width * 0.5 - text_offset_x;
+    let text_x = window_width * 0.5 + position.x - text_offset_x;
 
     // Spawn button text
     commands.spawn((
@@ -0,0 +1,188 @@
+use crate::assets::create_beacon_mesh;
+use crate::components::*;
+use crate::events::*;
+use crate::resources::*;
+use bevy::prelude::*;
+
+// Make `level_id` the level the next run plays, on its own difficulty
+pub fn start_campaign_level(world: &mut World, level_id: &str) {
+    let Some(level) = world.resource::<Campaign>().get(level_id).cloned() else {
+        warn!("Unknown campaign level {}", level_id);
+        return;
+    };
+    let difficulty = match world.get_resource::<DifficultyPresets>() {
+        Some(presets) => presets.get(level.difficulty).clone(),
+        None => DifficultyPresets::built_in().get(level.difficulty).clone(),
+    };
+    world.insert_resource(difficulty);
+    world.insert_resource(CampaignRun::new(level));
+}
+
+// Move the campaign on to the level after the one just played
+pub fn start_next_campaign_level(world: &mut World) {
+    let next = world
+        .get_resource::<CampaignRun>()
+        .and_then(|campaign_run| {
+            world
+                .resource::<Campaign>()
+                .next_after(&campaign_run.level.id)
+                .map(|level| level.id.clone())
+        });
+    if let Some(next) = next {
+        start_campaign_level(world, &next);
+    }
+}
+
+// Begin an attempt at the campaign level, if one was picked: a fresh
+// objective, the beacon to protect and the objective line at the top
+pub fn start_campaign_attempt(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    campaign_run: Option<ResMut<CampaignRun>>,
+    existing: Query<Entity, Or<(With<Beacon>, With<ObjectiveText>)>>,
+) {
+    // A replay seeking backwards restarts the run without leaving Playing
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Some(mut campaign_run) = campaign_run else {
+        return;
+    };
+    campaign_run.reset();
+
+    if let Objective::Protect {
+        position,
+        radius,
+        health,
+        ..
+    } = campaign_run.level.objective
+    {
+        commands.spawn((
+            Mesh2d(meshes.add(create_beacon_mesh(radius))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(1.0, 2.5, 3.0)))),
+            Transform::from_xyz(position[0], position[1], -0.5),
+            Beacon,
+            Beacon::collider(radius),
+            Health::new(health),
+        ));
+    }
+
+    commands.spawn((
+        Text::new(campaign_run.level.name.clone()),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::srgb(2.0, 2.5, 3.0)),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(0.0),
+            right: Val::Px(0.0),
+            ..default()
+        },
+        ObjectiveText,
+        GameUI,
+    ));
+}
+
+pub fn end_campaign_attempt(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<Beacon>, With<ObjectiveText>)>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Asteroids and enemy bullets that reach the beacon are used up on it:
+// an asteroid costs its size in health, a bullet its damage
+pub fn protect_beacon_system(
+    mut commands: Commands,
+    mut beacons: Query<(&Transform, &Collider, &mut Health), With<Beacon>>,
+    asteroids: Query<(&Transform, &Collider, &Asteroid), Without<Beacon>>,
+    enemy_bullets: Query<(&Transform, &Collider, &EnemyBullet), Without<Beacon>>,
+    grid: Res<SpatialGrid>,
+) {
+    for (beacon_transform, beacon_collider, mut health) in beacons.iter_mut() {
+        for candidate in grid.query_collider(
+            beacon_transform,
+            beacon_collider,
+            CollisionLayers::ASTEROID | CollisionLayers::ENEMY_BULLET,
+        ) {
+            let damage = if let Ok((transform, collider, asteroid)) =
+                asteroids.get(candidate.entity)
+                && beacon_collider.intersects(beacon_transform, collider, transform)
+            {
+                asteroid.size
+            } else if let Ok((transform, collider, bullet)) = enemy_bullets.get(candidate.entity)
+                && beacon_collider.intersects(beacon_transform, collider, transform)
+            {
+                bullet.damage
+            } else {
+                continue;
+            };
+
+            health.take_damage(damage);
+            commands.entity(candidate.entity).despawn();
+        }
+    }
+}
+
+// Count what the objective needs from this tick and end the run once the
+// level is won or lost
+pub fn update_campaign_objective(
+    mut commands: Commands,
+    mut campaign_run: ResMut<CampaignRun>,
+    run_clock: Res<RunClock>,
+    lives: Res<PlayerLives>,
+    beacons: Query<&Health, With<Beacon>>,
+    mut asteroid_events: EventReader<AsteroidDestroyed>,
+    mut boss_events: EventReader<BossDefeated>,
+    mut hit_events: EventReader<PlayerHit>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for event in asteroid_events.read() {
+        campaign_run.record_asteroid(&event.asteroid_type);
+    }
+    for event in boss_events.read() {
+        campaign_run.record_boss(&event.boss_type);
+    }
+    campaign_run.hits += hit_events.read().count() as u32;
+
+    // Losing the last life already ended the run
+    if lives.is_game_over() || campaign_run.status != ObjectiveStatus::InProgress {
+        return;
+    }
+
+    let beacon_health = beacons.single().ok().map(|health| health.current);
+    let verdict = match campaign_run.update(run_clock.elapsed_secs(), beacon_health) {
+        ObjectiveStatus::InProgress => return,
+        ObjectiveStatus::Complete => RunVerdict::Cleared,
+        ObjectiveStatus::Failed => RunVerdict::Failed,
+    };
+    info!("{}: {:?}", campaign_run.level.name, verdict);
+    commands.insert_resource(verdict);
+    next_state.set(GameState::GameOver);
+}
+
+pub fn update_objective_text(
+    campaign_run: Option<Res<CampaignRun>>,
+    run_clock: Res<RunClock>,
+    beacons: Query<&Health, With<Beacon>>,
+    mut query: Query<&mut Text, With<ObjectiveText>>,
+) {
+    let Some(campaign_run) = campaign_run else {
+        return;
+    };
+    let beacon_health = beacons.single().ok().map(|health| health.current);
+    for mut text in query.iter_mut() {
+        text.0 = format!(
+            "{}\n{}",
+            campaign_run.level.name,
+            campaign_run.progress_text(run_clock.elapsed_secs(), beacon_health)
+        );
+    }
+}
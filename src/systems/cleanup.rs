@@ -1,4 +1,6 @@
 use crate::components::*;
+use crate::events::*;
+use crate::resources::*;
 use bevy::prelude::*;
 
 #[allow(dead_code)]
@@ -21,15 +23,40 @@ pub fn despawn_bullets(
 pub fn despawn_asteroids(
     mut commands: Commands,
     asteroids: Query<(Entity, &Transform), With<Asteroid>>,
-    windows: Query<&Window>,
+    bounds: Res<PlayfieldBounds>,
 ) {
-    if let Ok(window) = windows.single() {
-        let half_height = window.height() / 2.0;
+    let half_height = bounds.half_height();
 
-        for (entity, transform) in asteroids.iter() {
-            if transform.translation.y < -half_height - 50.0 {
-                commands.entity(entity).despawn();
-            }
+    for (entity, transform) in asteroids.iter() {
+        if transform.translation.y < -half_height - 50.0 {
+            commands.entity(entity).despawn();
         }
     }
 }
+
+// Announce the end of the run before its entities are cleaned up
+pub fn announce_run_ended(
+    players: Query<(Entity, &Transform), With<Player>>,
+    lives: Res<PlayerLives>,
+    score: Res<GameScore>,
+    playback: Option<Res<ReplayPlayback>>,
+    verdict: Option<Res<RunVerdict>>,
+    mut run_events: EventWriter<RunEnded>,
+) {
+    let player = players.single().ok();
+    let outcome = match verdict.as_deref() {
+        Some(RunVerdict::Cleared) => RunOutcome::Cleared,
+        Some(RunVerdict::Failed) => RunOutcome::GameOver,
+        None if lives.is_game_over() => RunOutcome::GameOver,
+        None => RunOutcome::Quit,
+    };
+
+    run_events.write(RunEnded {
+        entity: player.map(|(entity, _)| entity),
+        outcome,
+        final_score: score.score,
+        replay: playback.is_some(),
+        position: player.map(|(_, transform)| transform.translation),
+        score_delta: 0,
+    });
+}
@@ -1,6 +1,7 @@
 use crate::components::*;
+use crate::events::*;
 use crate::resources::*;
-use crate::systems::spawning::spawn_asteroid_fragments;
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy::prelude::*;
 
 pub fn collision_system(
@@ -9,97 +10,63 @@ pub fn collision_system(
         (
             Entity,
             &Transform,
+            &Collider,
             Option<&mut PiercingBullet>,
             Option<&ExplosiveBullet>,
         ),
         (With<Bullet>, Without<Asteroid>),
     >,
-    mut asteroids: Query<
-        (Entity, &Transform, &Asteroid, &mut Health),
-        (With<Asteroid>, Without<Bullet>),
-    >,
-    mut score: ResMut<GameScore>,
+    asteroids: Query<(&Transform, &Health, &Collider), (With<Asteroid>, Without<Bullet>)>,
+    mut damage_events: EventWriter<DamageEvent>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    grid: Res<SpatialGrid>,
 ) {
     let mut bullets_to_remove = Vec::new();
     let mut explosions_to_create = Vec::new();
-    let mut asteroids_to_despawn = Vec::new();
+    // Damage already dealt this tick, so bullets pass through asteroids that are about to die
+    let mut pending_damage = EntityHashMap::<u32>::default();
 
-    // First pass: check collisions and mark entities for removal
-    for (bullet_entity, bullet_transform, mut piercing_bullet, explosive_bullet) in
+    // First pass: check collisions and mark bullets for removal
+    for (bullet_entity, bullet_transform, bullet_collider, mut piercing_bullet, explosive_bullet) in
         bullets.iter_mut()
     {
         let mut should_remove_bullet = false;
 
-        for (asteroid_entity, asteroid_transform, asteroid, mut health) in asteroids.iter_mut() {
-            // Skip if this asteroid is already marked for despawn
-            if asteroids_to_despawn.contains(&asteroid_entity) {
+        for candidate in
+            grid.query_collider(bullet_transform, bullet_collider, CollisionLayers::ASTEROID)
+        {
+            let Ok((asteroid_transform, health, asteroid_collider)) =
+                asteroids.get(candidate.entity)
+            else {
                 continue;
-            }
-
-            let collision_radius = asteroid.size as f32 * 5.0 + 5.0;
-            let distance = bullet_transform
-                .translation
-                .distance(asteroid_transform.translation);
+            };
 
-            if distance < collision_radius {
-                let asteroid_position = asteroid_transform.translation;
+            // Skip if this asteroid is already destroyed
+            let pending = pending_damage.entry(candidate.entity).or_default();
+            if *pending >= health.current {
+                continue;
+            }
 
+            if bullet_collider.intersects(bullet_transform, asteroid_collider, asteroid_transform) {
                 // Handle explosive bullets
                 if let Some(explosive) = explosive_bullet {
                     explosions_to_create.push((
-                        asteroid_position,
+                        bullet_entity,
+                        asteroid_transform.translation,
                         explosive.explosion_radius,
                         explosive.explosion_damage,
                     ));
                 }
 
                 // Damage the asteroid
-                let is_destroyed = health.take_damage(1);
-
-                if is_destroyed {
-                    asteroids_to_despawn.push(asteroid_entity);
-                    score.score += asteroid.points_value();
-
-                    // Special effects for different asteroid types
-                    if asteroid.has_special_destruction() {
-                        match asteroid.asteroid_type {
-                            AsteroidType::Ice => {
-                                // Ice asteroids create a temporary freeze effect
-                                create_ice_shatter_effect(&mut commands, &mut meshes, &mut materials, asteroid_position);
-                            },
-                            AsteroidType::Crystal => {
-                                // Crystal asteroids have enhanced power-up spawn chance and visual effect
-                                create_crystal_explosion_effect(&mut commands, &mut meshes, &mut materials, asteroid_position);
-                                
-                                // Higher chance to spawn power-up for crystal
-                                if fastrand::f32() < 0.9 {
-                                    use crate::systems::powerups::spawn_random_powerup;
-                                    spawn_random_powerup(&mut commands, &mut meshes, &mut materials, Some(asteroid_position));
-                                }
-                            },
-                            _ => {}
-                        }
-                    } else if asteroid.asteroid_type == AsteroidType::Crystal {
-                        // Regular crystal power-up chance
-                        if fastrand::f32() < 0.8 {
-                            use crate::systems::powerups::spawn_random_powerup;
-                            spawn_random_powerup(&mut commands, &mut meshes, &mut materials, Some(asteroid_position));
-                        }
-                    }
-
-                    spawn_asteroid_fragments(
-                        &mut commands,
-                        &mut meshes,
-                        &mut materials,
-                        asteroid_position,
-                        asteroid.size,
-                        &asteroid.asteroid_type,
-                    );
-                } else {
-                    score.score += 5;
-                }
+                *pending += 1;
+                damage_events.write(DamageEvent {
+                    target: candidate.entity,
+                    amount: 1,
+                    source: Some(bullet_entity),
+                    kind: DamageKind::Bullet,
+                });
 
                 // Handle piercing bullets
                 if let Some(ref mut piercing) = piercing_bullet {
@@ -130,21 +97,18 @@ pub fn collision_system(
         }
     }
 
-    // Remove asteroids that should be destroyed
-    for asteroid_entity in asteroids_to_despawn {
-        if let Ok(mut entity_commands) = commands.get_entity(asteroid_entity) {
-            entity_commands.despawn();
-        }
-    }
-
-    // Create explosions (but don't despawn more asteroids here to avoid double-despawn)
-    for (explosion_center, explosion_radius, explosion_damage) in explosions_to_create {
-        create_explosion_visual(
+    // Explosions damage everything in range after the direct hits
+    for (bullet_entity, explosion_center, explosion_radius, explosion_damage) in
+        explosions_to_create
+    {
+        create_explosion(
             &mut commands,
             &mut meshes,
             &mut materials,
-            &mut asteroids,
-            &mut score,
+            &asteroids,
+            &mut damage_events,
+            &grid,
+            bullet_entity,
             explosion_center,
             explosion_radius,
             explosion_damage,
@@ -154,87 +118,45 @@ pub fn collision_system(
 
 // System to handle laser beam collisions
 pub fn laser_collision_system(
-    mut commands: Commands,
-    lasers: Query<(Entity, &Transform, &LaserBeam), With<LaserBeam>>,
-    mut asteroids: Query<
-        (Entity, &Transform, &Asteroid, &mut Health),
-        (With<Asteroid>, Without<LaserBeam>),
-    >,
-    mut score: ResMut<GameScore>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut lasers: Query<(Entity, &Transform, &mut LaserBeam, &Collider)>,
+    asteroids: Query<(&Transform, &Collider), (With<Asteroid>, Without<LaserBeam>)>,
+    mut damage_events: EventWriter<DamageEvent>,
     time: Res<Time>,
+    grid: Res<SpatialGrid>,
 ) {
-    for (_laser_entity, laser_transform, laser_beam) in lasers.iter() {
-        let laser_start = laser_transform.translation.truncate();
-        let laser_direction = laser_transform.up().truncate();
-        let laser_end = laser_start + laser_direction * laser_beam.max_range;
-
-        for (asteroid_entity, asteroid_transform, asteroid, mut health) in asteroids.iter_mut() {
-            let asteroid_pos = asteroid_transform.translation.truncate();
-            let asteroid_radius = asteroid.size as f32 * 5.0 + 5.0;
-
-            // Check if asteroid intersects with laser line
-            if line_circle_intersection(laser_start, laser_end, asteroid_pos, asteroid_radius) {
-                let damage = (laser_beam.damage_per_second * time.delta_secs()) as u32;
-                let is_destroyed = health.take_damage(damage.max(1));
-
-                if is_destroyed {
-                    let asteroid_position = asteroid_transform.translation;
-
-                    commands.entity(asteroid_entity).despawn();
-                    score.score += asteroid.points_value();
-
-                    // Special effects for different asteroid types
-                    if asteroid.has_special_destruction() {
-                        match asteroid.asteroid_type {
-                            AsteroidType::Ice => {
-                                create_ice_shatter_effect(&mut commands, &mut meshes, &mut materials, asteroid_position);
-                            },
-                            AsteroidType::Crystal => {
-                                create_crystal_explosion_effect(&mut commands, &mut meshes, &mut materials, asteroid_position);
-                                
-                                // Higher chance to spawn power-up for crystal
-                                if fastrand::f32() < 0.9 {
-                                    use crate::systems::powerups::spawn_random_powerup;
-                                    spawn_random_powerup(&mut commands, &mut meshes, &mut materials, Some(asteroid_position));
-                                }
-                            },
-                            _ => {}
-                        }
-                    } else if asteroid.asteroid_type == AsteroidType::Crystal {
-                        // Regular crystal power-up chance
-                        if fastrand::f32() < 0.8 {
-                            use crate::systems::powerups::spawn_random_powerup;
-                            spawn_random_powerup(&mut commands, &mut meshes, &mut materials, Some(asteroid_position));
-                        }
-                    }
-
-                    spawn_asteroid_fragments(
-                        &mut commands,
-                        &mut meshes,
-                        &mut materials,
-                        asteroid_position,
-                        asteroid.size,
-                        &asteroid.asteroid_type,
-                    );
-                } else {
-                    score.score += 1;
+    for (laser_entity, laser_transform, mut laser_beam, laser_collider) in lasers.iter_mut() {
+        for candidate in
+            grid.query_collider(laser_transform, laser_collider, CollisionLayers::ASTEROID)
+        {
+            let Ok((asteroid_transform, asteroid_collider)) = asteroids.get(candidate.entity)
+            else {
+                continue;
+            };
+
+            // Check if asteroid intersects with the beam
+            if laser_collider.intersects(laser_transform, asteroid_collider, asteroid_transform) {
+                let damage = laser_beam.burn(candidate.entity, time.delta_secs());
+                if damage > 0 {
+                    damage_events.write(DamageEvent {
+                        target: candidate.entity,
+                        amount: damage,
+                        source: Some(laser_entity),
+                        kind: DamageKind::Laser,
+                    });
                 }
             }
         }
     }
 }
 
-fn create_explosion_visual(
+fn create_explosion(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
-    asteroids: &mut Query<
-        (Entity, &Transform, &Asteroid, &mut Health),
-        (With<Asteroid>, Without<Bullet>),
-    >,
-    score: &mut ResMut<GameScore>,
+    asteroids: &Query<(&Transform, &Health, &Collider), (With<Asteroid>, Without<Bullet>)>,
+    damage_events: &mut EventWriter<DamageEvent>,
+    grid: &SpatialGrid,
+    bullet_entity: Entity,
     explosion_center: Vec3,
     explosion_radius: f32,
     explosion_damage: u32,
@@ -248,58 +170,45 @@ fn create_explosion_visual(
         ExplosionVisual,                // Add marker component for cleanup
     ));
 
-    // Only damage asteroids, don't despawn them (that's handled in the main collision system)
-    for (_asteroid_entity, asteroid_transform, _asteroid, mut health) in asteroids.iter_mut() {
-        let distance = explosion_center.distance(asteroid_transform.translation);
-
-        if distance <= explosion_radius {
-            let _is_destroyed = health.take_damage(explosion_damage);
-            // Note: We don't despawn here to avoid double-despawn issues
-            // The main collision system will handle despawning
-            score.score += 5; // Small bonus for explosion damage
+    let blast = Collider::circle(explosion_radius)
+        .with_layers(CollisionLayers::NONE, CollisionLayers::ASTEROID);
+    let blast_transform = Transform::from_translation(explosion_center);
+    for candidate in grid.query_collider(&blast_transform, &blast, CollisionLayers::ASTEROID) {
+        let Ok((asteroid_transform, _health, asteroid_collider)) = asteroids.get(candidate.entity)
+        else {
+            continue;
+        };
+
+        if blast.intersects(&blast_transform, asteroid_collider, asteroid_transform) {
+            damage_events.write(DamageEvent {
+                target: candidate.entity,
+                amount: explosion_damage,
+                source: Some(bullet_entity),
+                kind: DamageKind::Explosion,
+            });
         }
     }
 }
 
-fn line_circle_intersection(
-    line_start: Vec2,
-    line_end: Vec2,
-    circle_center: Vec2,
-    circle_radius: f32,
-) -> bool {
-    let line_vec = line_end - line_start;
-    let line_length = line_vec.length();
-
-    if line_length == 0.0 {
-        return false;
-    }
-
-    let line_unit = line_vec / line_length;
-    let to_circle = circle_center - line_start;
-    let projection = to_circle.dot(line_unit);
-
-    // Clamp projection to line segment
-    let clamped_projection = projection.clamp(0.0, line_length);
-    let closest_point = line_start + line_unit * clamped_projection;
-
-    let distance_to_circle = (circle_center - closest_point).length();
-    distance_to_circle <= circle_radius
-}
-
 // System to handle player vs enemy bullet collisions
 pub fn player_enemy_bullet_collision_system(
     mut commands: Commands,
     mut player_query: Query<
-        (Entity, &Transform, Option<&mut Invincibility>),
+        (Entity, &Transform, &Collider, Option<&mut Invincibility>),
         (With<Player>, Without<EnemyBullet>),
     >,
-    enemy_bullets: Query<(Entity, &Transform, &EnemyBullet), (With<EnemyBullet>, Without<Player>)>,
+    enemy_bullets: Query<(Entity, &Transform, &Collider), (With<EnemyBullet>, Without<Player>)>,
     mut player_lives: ResMut<PlayerLives>,
     score: Res<GameScore>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut hit_events: EventWriter<PlayerHit>,
+    mut life_events: EventWriter<LifeLost>,
     time: Res<Time>,
+    grid: Res<SpatialGrid>,
 ) {
-    if let Ok((player_entity, player_transform, mut invincibility_opt)) = player_query.single_mut() {
+    if let Ok((player_entity, player_transform, player_collider, mut invincibility_opt)) =
+        player_query.single_mut()
+    {
         // Update invincibility timer if active
         if let Some(ref mut invincibility) = invincibility_opt {
             invincibility.timer.tick(time.delta());
@@ -316,16 +225,37 @@ pub fn player_enemy_bullet_collision_system(
             .map_or(false, |inv| inv.is_active());
 
         if !is_invincible {
-            for (bullet_entity, bullet_transform, _enemy_bullet) in enemy_bullets.iter() {
-                let collision_radius = 20.0; // Player collision radius
-
-                let distance = player_transform
-                    .translation
-                    .distance(bullet_transform.translation);
-
-                if distance < collision_radius {
+            for candidate in grid.query_collider(
+                player_transform,
+                player_collider,
+                CollisionLayers::ENEMY_BULLET,
+            ) {
+                let Ok((bullet_entity, bullet_transform, bullet_collider)) =
+                    enemy_bullets.get(candidate.entity)
+                else {
+                    continue;
+                };
+
+                if player_collider.intersects(player_transform, bullet_collider, bullet_transform) {
                     // Player hit by enemy bullet
+                    let position = player_transform.translation;
+                    hit_events.write(PlayerHit {
+                        entity: player_entity,
+                        source: bullet_entity,
+                        hazard: Hazard::EnemyBullet,
+                        position,
+                        score_delta: 0,
+                    });
+
                     let game_over = player_lives.lose_life();
+                    life_events.write(LifeLost {
+                        entity: player_entity,
+                        hazard: Hazard::EnemyBullet,
+                        lives_remaining: player_lives.lives,
+                        game_over,
+                        position,
+                        score_delta: 0,
+                    });
 
                     if game_over {
                         // Game over
@@ -352,27 +282,28 @@ pub fn player_enemy_bullet_collision_system(
 // System to handle bullet vs enemy collisions
 pub fn bullet_enemy_collision_system(
     mut commands: Commands,
-    bullets: Query<(Entity, &Transform), (With<Bullet>, Without<Enemy>)>,
-    mut enemies: Query<(Entity, &Transform, &mut Health, &Enemy), (With<Enemy>, Without<Bullet>)>,
-    mut score: ResMut<GameScore>,
+    bullets: Query<(Entity, &Transform, &Collider), (With<Bullet>, Without<Enemy>)>,
+    enemies: Query<(&Transform, &Collider), (With<Enemy>, Without<Bullet>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    grid: Res<SpatialGrid>,
 ) {
     let mut bullets_to_remove = Vec::new();
-    let mut enemies_to_remove = Vec::new();
-
-    for (bullet_entity, bullet_transform) in bullets.iter() {
-        for (enemy_entity, enemy_transform, mut health, enemy) in enemies.iter_mut() {
-            let collision_radius = 25.0; // Enemy collision radius
-            let distance = bullet_transform
-                .translation
-                .distance(enemy_transform.translation);
 
-            if distance < collision_radius {
-                let is_destroyed = health.take_damage(1);
+    for (bullet_entity, bullet_transform, bullet_collider) in bullets.iter() {
+        for candidate in
+            grid.query_collider(bullet_transform, bullet_collider, CollisionLayers::ENEMY)
+        {
+            let Ok((enemy_transform, enemy_collider)) = enemies.get(candidate.entity) else {
+                continue;
+            };
 
-                if is_destroyed {
-                    enemies_to_remove.push(enemy_entity);
-                    score.score += enemy.points_value();
-                }
+            if bullet_collider.intersects(bullet_transform, enemy_collider, enemy_transform) {
+                damage_events.write(DamageEvent {
+                    target: candidate.entity,
+                    amount: 1,
+                    source: Some(bullet_entity),
+                    kind: DamageKind::Bullet,
+                });
 
                 bullets_to_remove.push(bullet_entity);
                 break;
@@ -380,61 +311,43 @@ pub fn bullet_enemy_collision_system(
         }
     }
 
-    // Remove entities
+    // Remove bullets
     for bullet_entity in bullets_to_remove {
         if let Ok(mut entity_commands) = commands.get_entity(bullet_entity) {
             entity_commands.despawn();
         }
     }
-
-    for enemy_entity in enemies_to_remove {
-        if let Ok(mut entity_commands) = commands.get_entity(enemy_entity) {
-            entity_commands.despawn();
-        }
-    }
 }
 
 // System to handle bullet vs boss collisions
 pub fn bullet_boss_collision_system(
     mut commands: Commands,
-    bullets: Query<(Entity, &Transform), (With<Bullet>, Without<Boss>)>,
-    mut bosses: Query<(Entity, &Transform, &mut Health, &mut Boss), (With<Boss>, Without<Bullet>)>,
-    mut score: ResMut<GameScore>,
-    mut boss_manager: ResMut<BossSpawnManager>,
+    bullets: Query<(Entity, &Transform, &Collider), (With<Bullet>, Without<Boss>)>,
+    bosses: Query<(&Transform, &Boss, &Collider), (With<Boss>, Without<Bullet>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    grid: Res<SpatialGrid>,
 ) {
     let mut bullets_to_remove = Vec::new();
 
-    for (bullet_entity, bullet_transform) in bullets.iter() {
-        for (boss_entity, boss_transform, mut health, mut boss) in bosses.iter_mut() {
+    for (bullet_entity, bullet_transform, bullet_collider) in bullets.iter() {
+        for candidate in
+            grid.query_collider(bullet_transform, bullet_collider, CollisionLayers::BOSS)
+        {
+            let Ok((boss_transform, boss, boss_collider)) = bosses.get(candidate.entity) else {
+                continue;
+            };
             // Skip during phase transitions
             if boss.is_in_transition() {
                 continue;
             }
 
-            let collision_radius = 60.0 * boss.size_multiplier; // Boss collision radius
-            let distance = bullet_transform
-                .translation
-                .distance(boss_transform.translation);
-
-            if distance < collision_radius {
-                let is_phase_destroyed = health.take_damage(1);
-
-                if is_phase_destroyed {
-                    score.score += boss.points_value();
-                    
-                    // Try to advance to next phase
-                    if boss.advance_phase() {
-                        // Boss advanced to next phase - update health
-                        *health = Health::new(boss.phase_health);
-                        
-                        // Update visual appearance for new phase
-                        // This would be handled by a separate system that updates materials
-                    } else {
-                        // Boss completely defeated
-                        commands.entity(boss_entity).despawn();
-                        boss_manager.clear_active_boss();
-                    }
-                }
+            if bullet_collider.intersects(bullet_transform, boss_collider, boss_transform) {
+                damage_events.write(DamageEvent {
+                    target: candidate.entity,
+                    amount: 1,
+                    source: Some(bullet_entity),
+                    kind: DamageKind::Bullet,
+                });
 
                 bullets_to_remove.push(bullet_entity);
                 break;
@@ -450,106 +363,46 @@ pub fn bullet_boss_collision_system(
     }
 }
 
-// Special effect for ice asteroids shattering
-fn create_ice_shatter_effect(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
-    position: Vec3,
-) {
-    // Create multiple small ice particles
-    for i in 0..8 {
-        let angle = (i as f32 / 8.0) * 2.0 * std::f32::consts::PI;
-        let distance = 15.0 + fastrand::f32() * 25.0;
-        let particle_pos = position + Vec3::new(
-            angle.cos() * distance,
-            angle.sin() * distance,
-            0.0,
-        );
-
-        commands.spawn((
-            Mesh2d(meshes.add(Circle::new(2.0 + fastrand::f32() * 3.0))),
-            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgba(0.3, 0.8, 1.5, 0.8)))), // Ice blue
-            Transform::from_translation(particle_pos),
-            BulletLifecycle::new(1.0, 3.0), // Fade out over 1 second
-            ExplosionVisual,
-            Velocity(Vec2::new(
-                angle.cos() * (50.0 + fastrand::f32() * 50.0),
-                angle.sin() * (50.0 + fastrand::f32() * 50.0),
-            )),
-        ));
-    }
-}
-
-// Special effect for crystal asteroids exploding
-fn create_crystal_explosion_effect(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
-    position: Vec3,
-) {
-    // Create a bright purple explosion
-    commands.spawn((
-        Mesh2d(meshes.add(Circle::new(40.0))),
-        MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgba(1.5, 0.3, 1.5, 0.6)))), // Bright purple
-        Transform::from_translation(position),
-        BulletLifecycle::new(0.8, 6.0), // Bright flash that fades
-        ExplosionVisual,
-    ));
-
-    // Create sparkling particles
-    for i in 0..12 {
-        let angle = (i as f32 / 12.0) * 2.0 * std::f32::consts::PI + fastrand::f32() * 0.5;
-        let distance = 20.0 + fastrand::f32() * 30.0;
-        let particle_pos = position + Vec3::new(
-            angle.cos() * distance,
-            angle.sin() * distance,
-            0.0,
-        );
-
-        commands.spawn((
-            Mesh2d(meshes.add(Circle::new(1.5))),
-            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(2.0, 1.0, 2.0)))), // Bright sparkle
-            Transform::from_translation(particle_pos),
-            BulletLifecycle::new(1.5, 4.0), // Long-lasting sparkles
-            ExplosionVisual,
-            Velocity(Vec2::new(
-                angle.cos() * (30.0 + fastrand::f32() * 40.0),
-                angle.sin() * (30.0 + fastrand::f32() * 40.0),
-            )),
-        ));
-    }
-}
-
 // System to handle player bullet vs enemy bullet collisions
 pub fn bullet_bullet_collision_system(
     mut commands: Commands,
-    player_bullets: Query<(Entity, &Transform), (With<Bullet>, Without<EnemyBullet>)>,
-    enemy_bullets: Query<(Entity, &Transform), (With<EnemyBullet>, Without<Bullet>)>,
+    player_bullets: Query<(Entity, &Transform, &Collider), (With<Bullet>, Without<EnemyBullet>)>,
+    enemy_bullets: Query<(Entity, &Transform, &Collider), (With<EnemyBullet>, Without<Bullet>)>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rng: ResMut<GameRng>,
+    grid: Res<SpatialGrid>,
 ) {
     let mut player_bullets_to_remove = Vec::new();
-    let mut enemy_bullets_to_remove = Vec::new();
+    let mut enemy_bullets_to_remove = EntityHashSet::default();
     let mut sparks_to_create = Vec::new();
 
-    for (player_bullet_entity, player_bullet_transform) in player_bullets.iter() {
-        for (enemy_bullet_entity, enemy_bullet_transform) in enemy_bullets.iter() {
+    for (player_bullet_entity, player_bullet_transform, player_bullet_collider) in
+        player_bullets.iter()
+    {
+        for candidate in grid.query_collider(
+            player_bullet_transform,
+            player_bullet_collider,
+            CollisionLayers::ENEMY_BULLET,
+        ) {
             // Skip if already marked for removal
-            if player_bullets_to_remove.contains(&player_bullet_entity) 
-                || enemy_bullets_to_remove.contains(&enemy_bullet_entity) {
+            if enemy_bullets_to_remove.contains(&candidate.entity) {
                 continue;
             }
+            let Ok((enemy_bullet_entity, enemy_bullet_transform, enemy_bullet_collider)) =
+                enemy_bullets.get(candidate.entity)
+            else {
+                continue;
+            };
 
-            let collision_radius = 8.0; // Small collision radius for bullet vs bullet
-            let distance = player_bullet_transform
-                .translation
-                .distance(enemy_bullet_transform.translation);
-
-            if distance < collision_radius {
+            if player_bullet_collider.intersects(
+                player_bullet_transform,
+                enemy_bullet_collider,
+                enemy_bullet_transform,
+            ) {
                 // Mark both bullets for removal
                 player_bullets_to_remove.push(player_bullet_entity);
-                enemy_bullets_to_remove.push(enemy_bullet_entity);
+                enemy_bullets_to_remove.insert(enemy_bullet_entity);
                 
                 // Create spark effect at collision point
                 let collision_pos = (player_bullet_transform.translation + enemy_bullet_transform.translation) / 2.0;
@@ -572,7 +425,7 @@ pub fn bullet_bullet_collision_system(
 
     // Create spark effects
     for collision_pos in sparks_to_create {
-        create_bullet_collision_sparks(&mut commands, &mut meshes, &mut materials, collision_pos);
+        create_bullet_collision_sparks(&mut commands, &mut meshes, &mut materials, &mut rng.cosmetic, collision_pos);
     }
 }
 
@@ -581,15 +434,16 @@ fn create_bullet_collision_sparks(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    rng: &mut fastrand::Rng,
     position: Vec3,
 ) {
     // Create multiple small sparks
     for i in 0..6 {
-        let angle = (i as f32 / 6.0) * 2.0 * std::f32::consts::PI + fastrand::f32() * 0.3;
-        let speed = 80.0 + fastrand::f32() * 60.0;
+        let angle = (i as f32 / 6.0) * 2.0 * std::f32::consts::PI + rng.f32() * 0.3;
+        let speed = 80.0 + rng.f32() * 60.0;
         let spark_pos = position + Vec3::new(
-            (fastrand::f32() - 0.5) * 4.0,
-            (fastrand::f32() - 0.5) * 4.0,
+            (rng.f32() - 0.5) * 4.0,
+            (rng.f32() - 0.5) * 4.0,
             0.0,
         );
 
@@ -0,0 +1,268 @@
+use crate::assets::*;
+use crate::components::*;
+use crate::events::ShowToast;
+use crate::resources::*;
+use crate::systems::button_helpers::*;
+use crate::systems::save_system::report_save_error;
+use bevy::prelude::*;
+
+const IDLE_HINT: &str = "Click SET, then press the new key";
+
+// Controls page: one row per action with its keys and a SET button, then
+// the gamepad stick mode
+pub fn setup_controls_menu(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    settings: Res<GameSettings>,
+    windows: Query<&Window>,
+) {
+    if let Ok(window) = windows.single() {
+        let window_width = window.width();
+        let window_height = window.height();
+
+        // Semi-transparent background
+        commands.spawn((
+            Mesh2d(meshes.add(Rectangle::new(800.0, 600.0))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgba(0.0, 0.0, 0.0, 0.7)))),
+            Transform::from_translation(Vec3::new(0.0, 0.0, 10.0)),
+            MenuUI,
+        ));
+
+        // Title
+        commands.spawn((
+            Text::new("CONTROLS"),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(3.0, 3.0, 3.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.02),
+                left: Val::Px(window_width * 0.5 - 85.0),
+                ..default()
+            },
+            MenuUI,
+            MenuText3D,
+        ));
+
+        let set_mesh = meshes.add(create_button_outline_mesh(90.0, 30.0));
+        let rows = InputAction::ALL
+            .into_iter()
+            .map(|action| {
+                (
+                    action.label(),
+                    settings.controls.label(action),
+                    "SET",
+                    MenuAction::Rebind(action),
+                    Some(action),
+                )
+            })
+            .chain([(
+                "Gamepad stick",
+                settings.gamepad.stick.label().to_string(),
+                "SWAP",
+                MenuAction::StickModeToggle,
+                None,
+            )]);
+        for (row, (label, value, button_text, button_action, action)) in rows.enumerate() {
+            let y = 225.0 - row as f32 * 38.0;
+            let top = window_height * 0.5 - y - 11.0;
+
+            commands.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.0, 2.0, 2.0)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(top),
+                    left: Val::Px(window_width * 0.5 - 340.0),
+                    ..default()
+                },
+                MenuUI,
+                MenuText3D,
+            ));
+
+            let mut value_text = commands.spawn((
+                Text::new(value),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.0, 2.5, 2.0)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(top),
+                    left: Val::Px(window_width * 0.5 - 90.0),
+                    ..default()
+                },
+                MenuUI,
+                MenuText3D,
+            ));
+            match action {
+                Some(action) => value_text.insert(ControlsBindingText(action)),
+                None => value_text.insert(ControlsStickText),
+            };
+
+            spawn_button_with_text(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                window_width,
+                window_height,
+                set_mesh.clone(),
+                ButtonWithText::new(
+                    button_text,
+                    button_action,
+                    Vec2::new(90.0, 30.0),
+                    Color::srgb(2.0, 2.0, 2.5),
+                    16.0,
+                    Vec3::new(270.0, y, 11.0),
+                ),
+                MenuUI,
+            );
+        }
+
+        // Hint, replaced by a prompt while waiting for a key
+        commands.spawn((
+            Text::new(IDLE_HINT),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(2.5, 2.5, 1.5)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.5 + 195.0),
+                left: Val::Px(window_width * 0.5 - 200.0),
+                ..default()
+            },
+            ControlsStatusText,
+            MenuUI,
+            MenuText3D,
+        ));
+
+        let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
+
+        // Reset button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "DEFAULTS",
+                MenuAction::ResetControls,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.5, 2.0, 1.5),
+                24.0,
+                Vec3::new(-120.0, -255.0, 11.0),
+            ),
+            MenuUI,
+        );
+
+        // Back to the settings page
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh,
+            ButtonWithText::new(
+                "BACK",
+                MenuAction::Settings,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.5, 1.5, 1.5),
+                24.0,
+                Vec3::new(120.0, -255.0, 11.0),
+            ),
+            MenuUI,
+        );
+    }
+}
+
+// Bind the next key pressed to the action waiting for one. The key is taken
+// out of this frame's input so it doesn't also act under its old binding.
+pub fn rebind_key_system(
+    mut commands: Commands,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    rebinding: Res<Rebinding>,
+    mut settings: ResMut<GameSettings>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    let Some(key) = keyboard_input
+        .get_just_pressed()
+        .copied()
+        .find(|key| BINDABLE_KEYS.contains(key))
+    else {
+        return;
+    };
+    keyboard_input.clear_just_pressed(key);
+
+    let Rebinding(action) = *rebinding;
+    match settings.controls.bind(action, key) {
+        Ok(()) => {
+            commands.remove_resource::<Rebinding>();
+            report_save_error(&mut commands, GameSettings::FILE_NAME, settings.save());
+        }
+        // Keep waiting, so another key can be tried
+        Err(other) => {
+            toasts.write(ShowToast::error(format!(
+                "{} is already bound to {}",
+                key_label(key),
+                other.label()
+            )));
+        }
+    }
+}
+
+// Keep the key labels, stick mode and prompt in step with the settings
+pub fn update_controls_text(
+    settings: Res<GameSettings>,
+    rebinding: Option<Res<Rebinding>>,
+    mut binding_texts: Query<
+        (&mut Text, &ControlsBindingText),
+        (Without<ControlsStatusText>, Without<ControlsStickText>),
+    >,
+    mut stick_texts: Query<&mut Text, (With<ControlsStickText>, Without<ControlsStatusText>)>,
+    mut status_texts: Query<&mut Text, With<ControlsStatusText>>,
+) {
+    for (mut text, ControlsBindingText(action)) in binding_texts.iter_mut() {
+        let label = settings.controls.label(*action);
+        if text.0 != label {
+            text.0 = label;
+        }
+    }
+
+    let stick = settings.gamepad.stick.label();
+    for mut text in stick_texts.iter_mut() {
+        if text.0 != stick {
+            text.0 = stick.to_string();
+        }
+    }
+
+    let status = match rebinding {
+        Some(rebinding) => format!(
+            "Press a key for {} (click SET to cancel)",
+            rebinding.0.label()
+        ),
+        None => IDLE_HINT.to_string(),
+    };
+    for mut text in status_texts.iter_mut() {
+        if text.0 != status {
+            text.0 = status.clone();
+        }
+    }
+}
+
+// Leaving the page stops waiting for a key
+pub fn cancel_rebinding(mut commands: Commands) {
+    commands.remove_resource::<Rebinding>();
+}
@@ -0,0 +1,212 @@
+use crate::assets::*;
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::button_helpers::*;
+use crate::systems::save_system::report_save_error;
+use bevy::prelude::*;
+
+const SLIDER_WIDTH: f32 = 240.0;
+const SLIDER_X: f32 = 60.0;
+const FIRST_ROW_Y: f32 = 170.0;
+const ROW_SPACING: f32 = 45.0;
+
+// Custom difficulty editor: one slider per multiplier, then Start and Back
+pub fn setup_custom_difficulty_menu(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    windows: Query<&Window>,
+    settings: Res<GameSettings>,
+) {
+    if let Ok(window) = windows.single() {
+        let window_width = window.width();
+        let window_height = window.height();
+
+        // Title
+        commands.spawn((
+            Text::new("CUSTOM DIFFICULTY"),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(3.0, 3.0, 3.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.08),
+                left: Val::Px(window_width * 0.5 - 150.0),
+                ..default()
+            },
+            DifficultyMenu,
+            MenuText3D,
+        ));
+
+        let track_mesh = meshes.add(Rectangle::new(SLIDER_WIDTH, 4.0));
+        let knob_mesh = meshes.add(Rectangle::new(10.0, 22.0));
+        let track_material = materials.add(ColorMaterial::from(Color::srgb(1.0, 1.0, 1.2)));
+        let knob_material = materials.add(ColorMaterial::from(Color::srgb(2.0, 1.5, 2.5)));
+
+        for (row, field) in DifficultyField::ALL.into_iter().enumerate() {
+            let y = FIRST_ROW_Y - row as f32 * ROW_SPACING;
+            let text_top = window_height * 0.5 - y - 10.0;
+
+            // Label on the left
+            commands.spawn((
+                Text::new(field.label()),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.0, 2.0, 2.0)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(text_top),
+                    left: Val::Px(window_width * 0.5 - 330.0),
+                    ..default()
+                },
+                DifficultyMenu,
+                MenuText3D,
+            ));
+
+            commands.spawn((
+                Mesh2d(track_mesh.clone()),
+                MeshMaterial2d(track_material.clone()),
+                Transform::from_translation(Vec3::new(SLIDER_X, y, 0.0)),
+                DifficultySlider {
+                    field,
+                    width: SLIDER_WIDTH,
+                },
+                DifficultyMenu,
+            ));
+
+            let value = settings.custom_difficulty.value(field);
+            commands.spawn((
+                Mesh2d(knob_mesh.clone()),
+                MeshMaterial2d(knob_material.clone()),
+                Transform::from_translation(Vec3::new(knob_x(field, value), y, 1.0)),
+                DifficultySliderKnob(field),
+                DifficultyMenu,
+            ));
+
+            // Current value on the right
+            commands.spawn((
+                Text::new(format_value(value)),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.0, 1.5, 2.5)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(text_top),
+                    left: Val::Px(window_width * 0.5 + SLIDER_X + SLIDER_WIDTH * 0.5 + 20.0),
+                    ..default()
+                },
+                DifficultySliderText(field),
+                DifficultyMenu,
+                MenuText3D,
+            ));
+        }
+
+        let button_mesh = meshes.add(create_button_outline_mesh(180.0, 50.0));
+        let buttons = [
+            (
+                "START",
+                MenuAction::SetDifficulty(DifficultyId::Custom),
+                Color::srgb(1.5, 2.5, 1.5),
+                -110.0,
+            ),
+            (
+                "BACK",
+                MenuAction::DifficultySelect,
+                Color::srgb(2.0, 2.0, 2.0),
+                110.0,
+            ),
+        ];
+        for (text, action, color, x) in buttons {
+            spawn_button_with_text(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                window_width,
+                window_height,
+                button_mesh.clone(),
+                ButtonWithText::new(
+                    text,
+                    action,
+                    Vec2::new(180.0, 50.0),
+                    color,
+                    24.0,
+                    Vec3::new(x, -230.0, 0.0),
+                ),
+                DifficultyMenu,
+            );
+        }
+    }
+}
+
+// Click or drag along a slider to change its value. The custom difficulty is
+// saved once the mouse is released.
+pub fn custom_difficulty_slider_system(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    sliders: Query<(&Transform, &DifficultySlider)>,
+    mut knobs: Query<(&mut Transform, &DifficultySliderKnob), Without<DifficultySlider>>,
+    mut texts: Query<(&mut Text, &DifficultySliderText)>,
+    mut settings: ResMut<GameSettings>,
+    mut dragging: Local<Option<DifficultyField>>,
+) {
+    if mouse_input.just_released(MouseButton::Left) && dragging.take().is_some() {
+        report_save_error(&mut commands, GameSettings::FILE_NAME, settings.save());
+    }
+
+    let cursor = windows.single().ok().and_then(|window| {
+        let (camera, camera_transform) = camera_query.single().ok()?;
+        let position = window.cursor_position()?;
+        camera.viewport_to_world_2d(camera_transform, position).ok()
+    });
+
+    if let Some(cursor) = cursor {
+        if mouse_input.just_pressed(MouseButton::Left) {
+            // Grab the slider under the cursor; the knob's height is the hit area
+            *dragging = sliders
+                .iter()
+                .find(|(transform, slider)| {
+                    let offset = cursor - transform.translation.truncate();
+                    offset.x.abs() <= slider.width * 0.5 + 8.0 && offset.y.abs() <= 14.0
+                })
+                .map(|(_, slider)| slider.field);
+        }
+
+        let dragged = dragging.and_then(|field| sliders.iter().find(|(_, s)| s.field == field));
+        if let Some((transform, slider)) = dragged {
+            let (min, max, _) = slider.field.range();
+            let fraction =
+                ((cursor.x - transform.translation.x) / slider.width + 0.5).clamp(0.0, 1.0);
+            let mut custom = settings.custom_difficulty.clone();
+            custom.set_value(slider.field, min + fraction * (max - min));
+            if custom != settings.custom_difficulty {
+                settings.custom_difficulty = custom;
+            }
+        }
+    }
+
+    if settings.is_changed() {
+        for (mut transform, knob) in knobs.iter_mut() {
+            transform.translation.x = knob_x(knob.0, settings.custom_difficulty.value(knob.0));
+        }
+        for (mut text, label) in texts.iter_mut() {
+            **text = format_value(settings.custom_difficulty.value(label.0));
+        }
+    }
+}
+
+fn knob_x(field: DifficultyField, value: f32) -> f32 {
+    let (min, max, _) = field.range();
+    SLIDER_X + ((value - min) / (max - min) - 0.5) * SLIDER_WIDTH
+}
+
+fn format_value(value: f32) -> String {
+    format!("{:.2}", value)
+}
@@ -0,0 +1,269 @@
+use crate::components::*;
+use crate::events::*;
+use crate::resources::*;
+use crate::systems::powerups::spawn_random_powerup;
+use crate::systems::spawning::spawn_asteroid_fragments;
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+
+// Run down the combo window; getting hit ends the combo outright
+pub fn update_combo(
+    mut score: ResMut<GameScore>,
+    mut hit_events: EventReader<PlayerHit>,
+    time: Res<Time>,
+) {
+    score.tick_combo(time.delta_secs());
+    if hit_events.read().count() > 0 {
+        score.break_combo();
+    }
+}
+
+// Apply weapon damage in the order it was dealt. Chip points for asteroid
+// hits and boss phase changes are handled here; anything that runs out of
+// health is reported as `Destroyed`.
+pub fn apply_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    mut destroyed_events: EventWriter<Destroyed>,
+    mut phase_events: EventWriter<BossPhaseChanged>,
+    mut targets: Query<(
+        &Transform,
+        &mut Health,
+        Option<&Asteroid>,
+        Option<&mut Boss>,
+    )>,
+    mut score: ResMut<GameScore>,
+) {
+    // Something spawned or restored with no health left still dies on its
+    // first hit, so only skip what this pass has already reported
+    let mut destroyed = EntityHashSet::default();
+    for damage in damage_events.read() {
+        let Ok((transform, mut health, asteroid, boss)) = targets.get_mut(damage.target) else {
+            continue;
+        };
+
+        // Already destroyed earlier this tick
+        if destroyed.contains(&damage.target) {
+            continue;
+        }
+
+        // Bosses can't be hurt while changing phase
+        if boss.as_ref().is_some_and(|boss| boss.is_in_transition()) {
+            continue;
+        }
+
+        if !health.take_damage(damage.amount) {
+            if asteroid.is_some() {
+                score.add_points(damage.kind.chip_score());
+            }
+            continue;
+        }
+
+        if let Some(mut boss) = boss {
+            // Clearing a phase scores like a kill; the last phase is scored in `handle_destroyed`
+            let points = boss.points_value();
+            if boss.advance_phase() {
+                let phase_points = score.add_kill(points);
+                *health = Health::new(boss.phase_health);
+                phase_events.write(BossPhaseChanged {
+                    entity: damage.target,
+                    boss_type: boss.boss_type.clone(),
+                    phase: boss.phase,
+                    position: transform.translation,
+                    score_delta: phase_points,
+                });
+                continue;
+            }
+        }
+
+        destroyed.insert(damage.target);
+        destroyed_events.write(Destroyed {
+            entity: damage.target,
+            position: transform.translation,
+            source: damage.source,
+            kind: damage.kind,
+        });
+    }
+}
+
+// Single place where asteroids, enemies and bosses die: scoring, special
+// effects, power-up drops, fragments and despawning.
+pub fn handle_destroyed(
+    mut commands: Commands,
+    mut destroyed_events: EventReader<Destroyed>,
+    mut asteroid_events: EventWriter<AsteroidDestroyed>,
+    mut enemy_events: EventWriter<EnemyKilled>,
+    mut boss_events: EventWriter<BossDefeated>,
+    asteroids: Query<&Asteroid>,
+    enemies: Query<&Enemy>,
+    bosses: Query<&Boss>,
+    mut score: ResMut<GameScore>,
+    mut boss_manager: ResMut<BossSpawnManager>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rng: ResMut<GameRng>,
+) {
+    for destroyed in destroyed_events.read() {
+        let position = destroyed.position;
+
+        if let Ok(asteroid) = asteroids.get(destroyed.entity) {
+            let points = score.add_kill(asteroid.points_value());
+            asteroid_events.write(AsteroidDestroyed {
+                entity: destroyed.entity,
+                asteroid_type: asteroid.asteroid_type.clone(),
+                size: asteroid.size,
+                position,
+                score_delta: points,
+                cause: destroyed.kind,
+            });
+
+            // Special effects for different asteroid types
+            if asteroid.has_special_destruction() {
+                match asteroid.asteroid_type {
+                    AsteroidType::Ice => {
+                        // Ice asteroids create a temporary freeze effect
+                        create_ice_shatter_effect(
+                            &mut commands,
+                            &mut meshes,
+                            &mut materials,
+                            &mut rng.cosmetic,
+                            position,
+                        );
+                    }
+                    AsteroidType::Crystal => {
+                        // Crystal asteroids have enhanced power-up spawn chance and visual effect
+                        create_crystal_explosion_effect(
+                            &mut commands,
+                            &mut meshes,
+                            &mut materials,
+                            &mut rng.cosmetic,
+                            position,
+                        );
+
+                        // Higher chance to spawn power-up for crystal
+                        if rng.gameplay.f32() < 0.9 {
+                            spawn_random_powerup(
+                                &mut commands,
+                                &mut meshes,
+                                &mut materials,
+                                &mut rng.gameplay,
+                                Some(position),
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            } else if asteroid.asteroid_type == AsteroidType::Crystal {
+                // Regular crystal power-up chance
+                if rng.gameplay.f32() < 0.8 {
+                    spawn_random_powerup(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        &mut rng.gameplay,
+                        Some(position),
+                    );
+                }
+            }
+
+            spawn_asteroid_fragments(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                position,
+                asteroid.size,
+                &asteroid.asteroid_type,
+                &mut rng,
+            );
+        } else if let Ok(enemy) = enemies.get(destroyed.entity) {
+            let points = score.add_kill(enemy.points_value());
+            enemy_events.write(EnemyKilled {
+                entity: destroyed.entity,
+                enemy_type: enemy.enemy_type.clone(),
+                position,
+                score_delta: points,
+                cause: destroyed.kind,
+            });
+        } else if let Ok(boss) = bosses.get(destroyed.entity) {
+            let points = score.add_kill(boss.points_value());
+            boss_manager.clear_active_boss();
+            boss_events.write(BossDefeated {
+                entity: destroyed.entity,
+                boss_type: boss.boss_type.clone(),
+                position,
+                score_delta: points,
+                cause: destroyed.kind,
+            });
+        }
+
+        if let Ok(mut entity_commands) = commands.get_entity(destroyed.entity) {
+            entity_commands.despawn();
+        }
+    }
+}
+
+// Special effect for ice asteroids shattering
+fn create_ice_shatter_effect(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    rng: &mut fastrand::Rng,
+    position: Vec3,
+) {
+    // Create multiple small ice particles
+    for i in 0..8 {
+        let angle = (i as f32 / 8.0) * 2.0 * std::f32::consts::PI;
+        let distance = 15.0 + rng.f32() * 25.0;
+        let particle_pos =
+            position + Vec3::new(angle.cos() * distance, angle.sin() * distance, 0.0);
+
+        commands.spawn((
+            Mesh2d(meshes.add(Circle::new(2.0 + rng.f32() * 3.0))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgba(0.3, 0.8, 1.5, 0.8)))), // Ice blue
+            Transform::from_translation(particle_pos),
+            BulletLifecycle::new(1.0, 3.0), // Fade out over 1 second
+            ExplosionVisual,
+            Velocity(Vec2::new(
+                angle.cos() * (50.0 + rng.f32() * 50.0),
+                angle.sin() * (50.0 + rng.f32() * 50.0),
+            )),
+        ));
+    }
+}
+
+// Special effect for crystal asteroids exploding
+fn create_crystal_explosion_effect(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    rng: &mut fastrand::Rng,
+    position: Vec3,
+) {
+    // Create a bright purple explosion
+    commands.spawn((
+        Mesh2d(meshes.add(Circle::new(40.0))),
+        MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgba(1.5, 0.3, 1.5, 0.6)))), // Bright purple
+        Transform::from_translation(position),
+        BulletLifecycle::new(0.8, 6.0), // Bright flash that fades
+        ExplosionVisual,
+    ));
+
+    // Create sparkling particles
+    for i in 0..12 {
+        let angle = (i as f32 / 12.0) * 2.0 * std::f32::consts::PI + rng.f32() * 0.5;
+        let distance = 20.0 + rng.f32() * 30.0;
+        let particle_pos =
+            position + Vec3::new(angle.cos() * distance, angle.sin() * distance, 0.0);
+
+        commands.spawn((
+            Mesh2d(meshes.add(Circle::new(1.5))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(2.0, 1.0, 2.0)))), // Bright sparkle
+            Transform::from_translation(particle_pos),
+            BulletLifecycle::new(1.5, 4.0), // Long-lasting sparkles
+            ExplosionVisual,
+            Velocity(Vec2::new(
+                angle.cos() * (30.0 + rng.f32() * 40.0),
+                angle.sin() * (30.0 + rng.f32() * 40.0),
+            )),
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::assets::*;
 use crate::components::*;
 use crate::constants::*;
+use crate::events::*;
 use crate::resources::*;
 use bevy::prelude::*;
 
@@ -13,7 +14,9 @@ pub fn spawn_enemy_system(
     time: Res<Time>,
     score: Res<GameScore>,
     difficulty: Res<DifficultySettings>,
-    windows: Query<&Window>,
+    archetypes: Res<EnemyArchetypes>,
+    bounds: Res<PlayfieldBounds>,
+    mut rng: ResMut<GameRng>,
 ) {
     spawn_timer.timer.tick(time.delta());
 
@@ -21,80 +24,45 @@ pub fn spawn_enemy_system(
         || (score.score >= spawn_timer.last_spawn_score + spawn_timer.spawn_score_interval);
 
     if should_spawn {
-        if let Ok(window) = windows.single() {
-            spawn_timer.last_spawn_score = score.score;
-
-            // Choose enemy type based on score
-            let enemy_type = if score.score < 1000 {
-                EnemyType::Hunter
-            } else if score.score < 3000 {
-                if fastrand::f32() < 0.7 {
-                    EnemyType::Hunter
-                } else {
-                    EnemyType::Bomber
-                }
-            } else {
-                match fastrand::u32(0..3) {
-                    0 => EnemyType::Hunter,
-                    1 => EnemyType::Bomber,
-                    _ => EnemyType::Interceptor,
-                }
-            };
+        spawn_timer.last_spawn_score = score.score;
 
-            spawn_enemy(
-                &mut commands,
-                &mut meshes,
-                &mut materials,
-                &enemy_type,
-                &window,
-                &difficulty,
-            );
-        }
+        // Choose enemy type from the spawn tier the score has reached
+        let archetype = archetypes.choose_spawn(score.score, &mut rng.gameplay).clone();
+
+        let spawn_pos = SpawnEdge::Any.point(&bounds, 100.0, &mut rng.gameplay);
+
+        spawn_enemy(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            archetype,
+            &difficulty,
+            spawn_pos,
+        );
     }
 }
 
-fn spawn_enemy(
+// Spawn one enemy ship at `spawn_pos`, normally just outside the playfield
+pub fn spawn_enemy(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
-    enemy_type: &EnemyType,
-    window: &Window,
+    archetype: std::sync::Arc<EnemyArchetype>,
     difficulty: &DifficultySettings,
+    spawn_pos: Vec2,
 ) {
-    let half_width = window.width() / 2.0;
-    let half_height = window.height() / 2.0;
-    let spawn_offset = 100.0;
-
-    // Spawn from random edge
-    let side = fastrand::u32(0..4);
-    let spawn_pos = match side {
-        0 => Vec2::new(
-            (fastrand::f32() - 0.5) * window.width(),
-            half_height + spawn_offset,
-        ), // Top
-        1 => Vec2::new(
-            half_width + spawn_offset,
-            (fastrand::f32() - 0.5) * window.height(),
-        ), // Right
-        2 => Vec2::new(
-            (fastrand::f32() - 0.5) * window.width(),
-            -half_height - spawn_offset,
-        ), // Bottom
-        _ => Vec2::new(
-            -half_width - spawn_offset,
-            (fastrand::f32() - 0.5) * window.height(),
-        ), // Left
-    };
-
-    let enemy = Enemy::new_with_difficulty(enemy_type.clone(), difficulty);
+    let ship_mesh = create_enemy_ship_mesh(&archetype.shape);
+    let enemy = Enemy::new_with_difficulty(archetype, difficulty);
     let enemy_color = enemy.get_color();
     let enemy_health = enemy.max_health_with_difficulty(difficulty);
+    let enemy_collider = enemy.collider();
 
     commands.spawn((
-        Mesh2d(meshes.add(create_enemy_ship_mesh(enemy_type))),
+        Mesh2d(meshes.add(ship_mesh)),
         MeshMaterial2d(materials.add(ColorMaterial::from(enemy_color))),
         Transform::from_translation(spawn_pos.extend(0.0)),
         enemy,
+        enemy_collider,
         Health::new(enemy_health),
         Velocity(Vec2::ZERO), // Will be set by AI system
         AIBehavior::new(),
@@ -111,6 +79,7 @@ pub fn enemy_ai_system(
     >,
     player_query: Query<(&Transform, &Velocity), (With<Player>, Without<Enemy>)>,
     asteroid_query: Query<&Transform, (With<Asteroid>, Without<Player>, Without<Enemy>)>,
+    mut rng: ResMut<GameRng>,
     time: Res<Time>,
 ) {
     if let Ok((player_transform, player_velocity)) = player_query.single() {
@@ -126,7 +95,7 @@ pub fn enemy_ai_system(
 
             // Update AI behavior based on context
             if ai_behavior.behavior_timer.just_finished() {
-                ai_behavior.update_behavior(distance_to_player, &enemy.enemy_type, player_vel);
+                ai_behavior.update_behavior(distance_to_player, &enemy.archetype, player_vel);
                 ai_behavior.target_position = player_pos;
             }
 
@@ -157,7 +126,7 @@ pub fn enemy_ai_system(
                     // Aggressive movement with slight randomness
                     let aggression_bonus = 1.0 + (enemy.get_evasion_factor() * 0.5);
                     let random_offset =
-                        Vec2::new((fastrand::f32() - 0.5) * 0.3, (fastrand::f32() - 0.5) * 0.3);
+                        Vec2::new((rng.gameplay.f32() - 0.5) * 0.3, (rng.gameplay.f32() - 0.5) * 0.3);
                     (to_predicted_player + random_offset) * enemy.speed * aggression_bonus
                 }
                 AIState::Evading => {
@@ -212,11 +181,11 @@ pub fn enemy_shooting_system(
     player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    time: Res<Time>,
+    run_clock: Res<RunClock>,
 ) {
     if let Ok(player_transform) = player_query.single() {
         let player_pos = player_transform.translation.truncate();
-        let current_time = time.elapsed_secs();
+        let current_time = run_clock.elapsed_secs();
 
         for (enemy_transform, mut enemy) in enemy_query.iter_mut() {
             if current_time - enemy.last_shot_time >= enemy.shot_cooldown {
@@ -228,13 +197,8 @@ pub fn enemy_shooting_system(
                 if distance < 600.0 {
                     // Increased from 400.0 to make enemies more aggressive
                     let bullet_velocity = to_player * BULLET_SPEED * 0.8;
-                    let enemy_bullet = EnemyBullet::new(&enemy.enemy_type);
-
-                    let bullet_color = match enemy.enemy_type {
-                        EnemyType::Hunter => Color::srgb(6.0, 2.0, 2.0), // Much brighter red
-                        EnemyType::Bomber => Color::srgb(6.0, 4.0, 1.0), // Much brighter orange
-                        EnemyType::Interceptor => Color::srgb(2.0, 6.0, 2.0), // Much brighter green
-                    };
+                    let enemy_bullet = EnemyBullet::new(&enemy.archetype);
+                    let bullet_color = EnemyBullet::color(&enemy.archetype);
 
                     commands.spawn((
                         Mesh2d(meshes.add(create_enemy_bullet_mesh())),
@@ -245,6 +209,7 @@ pub fn enemy_shooting_system(
                             ),
                         ),
                         enemy_bullet,
+                        EnemyBullet::collider(),
                         Velocity(bullet_velocity),
                         BulletLifecycle::new(3.0, 5.0),
                         PulsingEffect::new(0.3, 0.3), // Quick pulse for visibility
@@ -266,36 +231,60 @@ pub fn boss_spawn_system(
     mut materials: ResMut<Assets<ColorMaterial>>,
     score: Res<GameScore>,
     difficulty: Res<DifficultySettings>,
-    windows: Query<&Window>,
+    bosses: Res<BossDefinitions>,
+    bounds: Res<PlayfieldBounds>,
+    mut rng: ResMut<GameRng>,
 ) {
-    if let Some(boss_type) = boss_manager.should_spawn_boss(score.score) {
-        if let Ok(window) = windows.single() {
-            // Enter boss encounter phase when spawning a boss
-            phase_manager.enter_boss_encounter();
-            
-            let spawn_pos = Vec2::new(0.0, window.height() / 2.0 + 150.0); // Spawn above screen center
-
-            let boss = Boss::new_with_difficulty(boss_type.clone(), &difficulty);
-            let boss_color = boss.get_color();
-            let boss_health = boss.phase_health;
-
-            let entity = commands
-                .spawn((
-                    Mesh2d(meshes.add(create_boss_mesh(&boss_type, boss.size_multiplier))),
-                    MeshMaterial2d(materials.add(ColorMaterial::from(boss_color))),
-                    Transform::from_translation(spawn_pos.extend(0.0)),
-                    boss,
-                    Health::new(boss_health),
-                    Velocity(Vec2::new(0.0, -30.0)), // Slow descent
-                    BossAttackPattern::new(AttackPattern::CircularShot),
-                ))
-                .id();
-
-            boss_manager.mark_boss_spawned(boss_type, entity);
-        }
+    if let Some(definition) = boss_manager.should_spawn_boss(score.score, &bosses) {
+        // Enter boss encounter phase when spawning a boss
+        phase_manager.enter_boss_encounter();
+
+        spawn_boss(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            definition.clone(),
+            &difficulty,
+            &bounds,
+            &mut boss_manager,
+            &mut rng.cosmetic,
+        );
     }
 }
 
+// Spawn a boss above the playfield
+pub fn spawn_boss(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    definition: std::sync::Arc<BossDefinition>,
+    difficulty: &DifficultySettings,
+    bounds: &PlayfieldBounds,
+    boss_manager: &mut BossSpawnManager,
+    rng: &mut fastrand::Rng,
+) {
+    let spawn_pos = Vec2::new(0.0, bounds.half_height() + 150.0); // Spawn above screen center
+
+    let boss = Boss::new_with_difficulty(definition.clone(), difficulty);
+    let boss_color = boss.get_color();
+    let boss_health = boss.phase_health;
+    let boss_collider = boss.collider();
+
+    let entity = commands
+        .spawn((
+            Mesh2d(meshes.add(create_boss_mesh(&definition.shape, boss.size_multiplier, rng))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(boss_color))),
+            Transform::from_translation(spawn_pos.extend(0.0)),
+            boss,
+            boss_collider,
+            Health::new(boss_health),
+            Velocity(Vec2::new(0.0, -30.0)), // Slow descent
+        ))
+        .id();
+
+    boss_manager.mark_boss_spawned(definition.id.clone(), entity);
+}
+
 // System to manage game phases based on boss presence
 pub fn game_phase_manager_system(
     mut phase_manager: ResMut<GamePhaseManager>,
@@ -323,31 +312,36 @@ pub fn reset_game_phase_system(mut phase_manager: ResMut<GamePhaseManager>) {
     *phase_manager = GamePhaseManager::default();
 }
 
+// Reset enemy spawning when starting a new game
+pub fn reset_enemy_spawner(mut enemy_spawn_timer: ResMut<EnemySpawnTimer>) {
+    enemy_spawn_timer.last_spawn_score = 0;
+    enemy_spawn_timer.timer.reset();
+}
+
+// Reset boss spawning when starting a new game
+pub fn reset_boss_spawner(mut boss_spawn_manager: ResMut<BossSpawnManager>) {
+    boss_spawn_manager.bosses_spawned.clear();
+    boss_spawn_manager.active_boss = None;
+}
+
 // System for boss AI and attacks
 pub fn boss_ai_system(
     mut commands: Commands,
-    mut boss_query: Query<(
-        Entity,
-        &Transform,
-        &mut Boss,
-        &mut BossAttackPattern,
-        &mut Velocity,
-    )>,
+    mut boss_query: Query<(Entity, &Transform, &mut Boss, &mut Velocity)>,
     player_query: Query<&Transform, (With<Player>, Without<Boss>)>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rng: ResMut<GameRng>,
+    archetypes: Res<EnemyArchetypes>,
     time: Res<Time>,
-    _boss_manager: ResMut<BossSpawnManager>,
+    run_clock: Res<RunClock>,
 ) {
     if let Ok(player_transform) = player_query.single() {
         let player_pos = player_transform.translation.truncate();
 
-        for (_boss_entity, boss_transform, mut boss, mut attack_pattern, mut velocity) in
-            boss_query.iter_mut()
-        {
+        for (_boss_entity, boss_transform, mut boss, mut velocity) in boss_query.iter_mut() {
             // Update timers
             boss.attack_timer.tick(time.delta());
-            attack_pattern.pattern_timer.tick(time.delta());
 
             if let Some(ref mut transition_timer) = boss.phase_transition_timer {
                 transition_timer.tick(time.delta());
@@ -360,75 +354,60 @@ pub fn boss_ai_system(
             }
 
             let boss_pos = boss_transform.translation.truncate();
-
-            // Enhanced movement pattern based on boss type and phase
-            let movement_speed = boss.get_movement_speed();
             let to_player = player_pos - boss_pos;
             let distance = to_player.length();
 
-            let desired_distance = match boss.boss_type {
-                BossType::GiantAsteroid => 180.0 + (boss.phase as f32 * 20.0), // Gets closer each phase
-                BossType::AlienMothership => 220.0, // Prefers longer range
-            };
-
-            // Dynamic movement based on distance and boss behavior
-            let movement_vector = if distance > desired_distance + 50.0 {
-                // Move toward player
-                to_player.normalize() * movement_speed
-            } else if distance < desired_distance - 50.0 {
-                // Move away from player
-                -to_player.normalize() * movement_speed * 0.8
-            } else {
-                // Orbital movement around optimal distance
-                let perpendicular = Vec2::new(-to_player.y, to_player.x).normalize();
-                let orbit_direction = if (time.elapsed_secs() * 0.3).sin() > 0.0 {
-                    1.0
-                } else {
-                    -1.0
-                };
-                perpendicular * movement_speed * 0.6 * orbit_direction
+            // Movement style and speed come from the current phase
+            let (movement_vector, erratic_factor) = match boss.current_phase().movement {
+                BossMovement::Orbit {
+                    speed,
+                    distance: desired_distance,
+                    erratic,
+                } => {
+                    let movement_vector = if distance > desired_distance + 50.0 {
+                        // Move toward player
+                        to_player.normalize() * speed
+                    } else if distance < desired_distance - 50.0 {
+                        // Move away from player
+                        -to_player.normalize() * speed * 0.8
+                    } else {
+                        // Orbital movement around optimal distance
+                        let perpendicular = Vec2::new(-to_player.y, to_player.x).normalize();
+                        let orbit_direction = if (run_clock.elapsed_secs() * 0.3).sin() > 0.0 {
+                            1.0
+                        } else {
+                            -1.0
+                        };
+                        perpendicular * speed * 0.6 * orbit_direction
+                    };
+                    (movement_vector, erratic)
+                }
+                BossMovement::Chase { speed, erratic } => {
+                    (to_player.normalize_or_zero() * speed, erratic)
+                }
             };
 
-            // Add some erratic movement based on phase
-            let erratic_factor = boss.phase as f32 * 0.2;
+            // Add some erratic movement on top
             let erratic_movement = Vec2::new(
-                (time.elapsed_secs() * 2.0 + boss.phase as f32).sin() * erratic_factor * 30.0,
-                (time.elapsed_secs() * 1.5 + boss.phase as f32).cos() * erratic_factor * 20.0,
+                (run_clock.elapsed_secs() * 2.0 + boss.phase as f32).sin() * erratic_factor * 30.0,
+                (run_clock.elapsed_secs() * 1.5 + boss.phase as f32).cos() * erratic_factor * 20.0,
             );
 
             velocity.0 = movement_vector + erratic_movement;
 
-            // Execute attack patterns based on boss's current phase
             if boss.attack_timer.just_finished() {
-                let current_pattern = boss.get_current_attack_pattern();
-
-                // Update attack pattern if it has changed
-                if attack_pattern.pattern_type != current_pattern {
-                    *attack_pattern = BossAttackPattern::new(current_pattern);
-                }
-
+                let pattern = boss.next_attack_pattern(&mut rng.gameplay);
                 execute_boss_attack(
                     &mut commands,
                     &mut meshes,
                     &mut materials,
-                    &mut attack_pattern,
-                    boss_transform,
+                    &pattern,
+                    boss_pos,
                     player_pos,
-                    &boss.boss_type,
-                    boss.phase,
+                    &mut rng,
+                    &archetypes,
                 );
             }
-
-            // Change attack pattern when current one finishes
-            if attack_pattern.pattern_timer.just_finished() {
-                let new_pattern = match boss.phase {
-                    1 => AttackPattern::CircularShot,
-                    2 => AttackPattern::TargetedBarrage,
-                    3 => AttackPattern::SpawnMinions,
-                    _ => AttackPattern::AsteroidRain,
-                };
-                *attack_pattern = BossAttackPattern::new(new_pattern);
-            }
         }
     }
 }
@@ -437,114 +416,169 @@ fn execute_boss_attack(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
-    attack_pattern: &mut BossAttackPattern,
-    boss_transform: &Transform,
+    pattern: &AttackPattern,
+    boss_pos: Vec2,
     player_pos: Vec2,
-    _boss_type: &BossType,
-    phase: u32,
+    rng: &mut GameRng,
+    archetypes: &EnemyArchetypes,
 ) {
-    let boss_pos = boss_transform.translation.truncate();
-
-    match &attack_pattern.pattern_type {
-        AttackPattern::CircularShot => {
-            // Shoot bullets in all directions - more bullets in higher phases
-            let bullet_count = 6 + (phase * 2);
+    match pattern {
+        &AttackPattern::CircularShot {
+            bullet_count,
+            speed,
+            damage,
+            explosive,
+            color: [r, g, b],
+        } => {
+            // Shoot bullets in all directions
             for i in 0..bullet_count {
                 let angle = (i as f32 / bullet_count as f32) * 2.0 * std::f32::consts::PI;
                 let direction = Vec2::new(angle.cos(), angle.sin());
-                let bullet_velocity = direction * BULLET_SPEED * (0.6 + phase as f32 * 0.1);
+                let bullet_velocity = direction * BULLET_SPEED * speed;
 
                 commands.spawn((
                     Mesh2d(meshes.add(create_enemy_bullet_mesh())),
-                    MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(6.0, 3.0, 1.0)))), // Brighter boss bullet
+                    MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(r, g, b)))),
                     Transform::from_translation(boss_pos.extend(0.0))
                         .with_rotation(Quat::from_rotation_z(angle - std::f32::consts::FRAC_PI_2)),
                     EnemyBullet {
-                        damage: 2 + phase / 2,
-                        is_explosive: phase > 2,
+                        damage,
+                        is_explosive: explosive,
                     },
+                    EnemyBullet::collider(),
                     Velocity(bullet_velocity),
                     BulletLifecycle::new(4.0, 5.0),
                     PulsingEffect::new(0.2, 0.4), // Stronger pulse for boss bullets
                 ));
             }
         }
-        AttackPattern::TargetedBarrage => {
-            // Shoot multiple bullets at player with some spread - more bullets in higher phases
+        &AttackPattern::TargetedBarrage {
+            bullet_count,
+            spread,
+            speed,
+            damage,
+            explosive,
+            color: [r, g, b],
+        } => {
+            // Shoot a fan of bullets at the player
             let to_player = (player_pos - boss_pos).normalize();
-            let bullet_count = 3 + phase;
 
             for i in 0..bullet_count {
-                let spread_range = 0.4 + (phase as f32 * 0.1);
-                let spread_angle = (i as f32 - (bullet_count as f32 - 1.0) / 2.0) * spread_range
-                    / bullet_count as f32;
+                let spread_angle =
+                    (i as f32 - (bullet_count as f32 - 1.0) / 2.0) * spread / bullet_count as f32;
                 let direction = Vec2::new(
                     to_player.x * spread_angle.cos() - to_player.y * spread_angle.sin(),
                     to_player.x * spread_angle.sin() + to_player.y * spread_angle.cos(),
                 );
-                let bullet_velocity = direction * BULLET_SPEED * (0.8 + phase as f32 * 0.1);
+                let bullet_velocity = direction * BULLET_SPEED * speed;
 
                 commands.spawn((
                     Mesh2d(meshes.add(create_enemy_bullet_mesh())),
-                    MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(6.0, 2.0, 3.0)))), // Brighter boss bullet
+                    MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(r, g, b)))),
                     Transform::from_translation(boss_pos.extend(0.0)).with_rotation(
                         Quat::from_rotation_z(
                             direction.y.atan2(direction.x) - std::f32::consts::FRAC_PI_2,
                         ),
                     ),
                     EnemyBullet {
-                        damage: 3 + phase / 2,
-                        is_explosive: phase > 1,
+                        damage,
+                        is_explosive: explosive,
                     },
+                    EnemyBullet::collider(),
                     Velocity(bullet_velocity),
                     BulletLifecycle::new(4.0, 5.0),
                     PulsingEffect::new(0.2, 0.4), // Stronger pulse for boss bullets
                 ));
             }
         }
-        AttackPattern::SpawnMinions => {
-            // Spawn enemy ships near the boss
-            let enemy_type = EnemyType::Hunter;
-            let spawn_offset = Vec2::new(
-                (fastrand::f32() - 0.5) * 100.0,
-                (fastrand::f32() - 0.5) * 100.0,
-            );
+        AttackPattern::SpawnMinions {
+            count,
+            enemy,
+            spread,
+            health,
+        } => {
+            // Spawn enemy ships near the boss. Unknown ids are rejected when
+            // the bosses or enemies load, so this only misses for a boss
+            // spawned before a reload
+            let archetype = match enemy {
+                Some(id) => match archetypes.get(id) {
+                    Some(archetype) => archetype,
+                    None => {
+                        warn!("Boss minion {} no longer exists", id);
+                        return;
+                    }
+                },
+                None => archetypes.boss_minion(),
+            };
+
+            for _ in 0..*count {
+                let spawn_offset = Vec2::new(
+                    (rng.gameplay.f32() - 0.5) * spread,
+                    (rng.gameplay.f32() - 0.5) * spread,
+                );
 
-            let enemy = Enemy::new(enemy_type.clone());
-            let enemy_color = enemy.get_color();
-
-            commands.spawn((
-                Mesh2d(meshes.add(create_enemy_ship_mesh(&enemy_type))),
-                MeshMaterial2d(materials.add(ColorMaterial::from(enemy_color))),
-                Transform::from_translation((boss_pos + spawn_offset).extend(0.0)),
-                enemy,
-                Health::new(1), // Weaker minions
-                Velocity(Vec2::ZERO),
-                AIBehavior::new(),
-                PulsingEffect::new(0.4, 0.2), // More visible pulse for boss-spawned enemies
-                Wraparound,
-            ));
+                let ship_mesh = create_enemy_ship_mesh(&archetype.shape);
+                let enemy = Enemy::new(archetype.clone());
+                let enemy_color = enemy.get_color();
+                let enemy_collider = enemy.collider();
+
+                commands.spawn((
+                    Mesh2d(meshes.add(ship_mesh)),
+                    MeshMaterial2d(materials.add(ColorMaterial::from(enemy_color))),
+                    Transform::from_translation((boss_pos + spawn_offset).extend(0.0)),
+                    enemy,
+                    enemy_collider,
+                    Health::new(*health),
+                    Velocity(Vec2::ZERO),
+                    AIBehavior::new(),
+                    PulsingEffect::new(0.4, 0.2), // More visible pulse for boss-spawned enemies
+                    Wraparound,
+                ));
+            }
         }
-        AttackPattern::AsteroidRain => {
-            // Spawn small asteroids around the boss
-            let spawn_offset = Vec2::new(
-                (fastrand::f32() - 0.5) * 200.0,
-                (fastrand::f32() - 0.5) * 200.0,
-            );
+        &AttackPattern::AsteroidRain {
+            count,
+            spread,
+            speed,
+        } => {
+            // Spawn small asteroids around the boss, thrown at the player
+            for _ in 0..count {
+                let spawn_offset = Vec2::new(
+                    (rng.gameplay.f32() - 0.5) * spread,
+                    (rng.gameplay.f32() - 0.5) * spread,
+                );
+
+                let asteroid = Asteroid::new(2, AsteroidType::Normal);
+                let asteroid_color = asteroid.get_color();
+                let asteroid_collider = asteroid.collider();
+
+                commands.spawn((
+                    Mesh2d(meshes.add(create_asteroid_mesh(2, 10.0, &mut rng.cosmetic))),
+                    MeshMaterial2d(materials.add(ColorMaterial::from(asteroid_color))),
+                    Transform::from_translation((boss_pos + spawn_offset).extend(0.0)),
+                    asteroid,
+                    asteroid_collider,
+                    Health::new(1),
+                    Velocity((player_pos - boss_pos - spawn_offset).normalize() * speed),
+                    RotationVelocity::random_slow(&mut rng.cosmetic),
+                    Wraparound,
+                ));
+            }
+        }
+    }
+}
 
-            let asteroid = Asteroid::new(2, AsteroidType::Normal);
-            let asteroid_color = asteroid.get_color();
-
-            commands.spawn((
-                Mesh2d(meshes.add(create_asteroid_mesh(2, 10.0))),
-                MeshMaterial2d(materials.add(ColorMaterial::from(asteroid_color))),
-                Transform::from_translation((boss_pos + spawn_offset).extend(0.0)),
-                asteroid,
-                Health::new(1),
-                Velocity((player_pos - boss_pos - spawn_offset).normalize() * 80.0),
-                RotationVelocity::random_slow(),
-                Wraparound,
-            ));
+// Recolour a boss when it enters a new phase
+pub fn boss_phase_color_system(
+    mut phase_events: EventReader<BossPhaseChanged>,
+    bosses: Query<(&Boss, &MeshMaterial2d<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for event in phase_events.read() {
+        if let Ok((boss, material)) = bosses.get(event.entity)
+            && let Some(material) = materials.get_mut(&material.0)
+        {
+            material.color = boss.get_color();
         }
     }
 }
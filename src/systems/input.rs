@@ -0,0 +1,47 @@
+use crate::resources::*;
+use bevy::prelude::*;
+
+// Turn this frame's key presses and gamepad state into actions, through the
+// bindings and gamepad tuning in settings
+pub fn update_action_state(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    settings: Res<GameSettings>,
+    mut actions: ResMut<ActionState>,
+) {
+    actions.clear();
+    for action in InputAction::ALL {
+        let keys = settings.controls.keys(action);
+        if keyboard_input.any_pressed(keys.iter().copied()) {
+            actions.press(action);
+        }
+        if keyboard_input.any_just_pressed(keys.iter().copied()) {
+            actions.just_press(action);
+        }
+
+        let held_on_gamepad = gamepads.iter().any(|gamepad| {
+            action
+                .gamepad_buttons()
+                .iter()
+                .any(|button| settings.gamepad.button_held(gamepad, *button))
+        });
+        if held_on_gamepad {
+            actions.hold(action);
+        }
+    }
+
+    // The first gamepad with its stick off centre steers
+    let stick = gamepads
+        .iter()
+        .find_map(|gamepad| settings.gamepad.stick(gamepad.left_stick()));
+    if let Some(stick) = stick {
+        match settings.gamepad.stick {
+            StickMode::Rotate => {
+                actions.turn = (-stick.x * settings.gamepad.sensitivity).clamp(-1.0, 1.0);
+            }
+            StickMode::Aim => {
+                actions.aim = Some((-stick.x).atan2(stick.y));
+            }
+        }
+    }
+}
@@ -1,116 +1,496 @@
 use crate::components::*;
+use crate::events::MenuButtonPressed;
 use crate::resources::*;
+use crate::systems::campaign::{start_campaign_level, start_next_campaign_level};
+use crate::systems::menu_setup_styled::{leaderboard_table, name_entry_label, seed_entry_label};
+use crate::systems::replay::start_replay_playback;
+use crate::systems::save_system::{report_save_error, save_high_score};
+use crate::systems::stats::export_run_report;
+use crate::systems::suspend::{continue_suspended_run, suspend_run};
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::prelude::*;
 
-pub fn mesh_menu_button_system(
-    mut button_query: Query<(
-        &Transform,
-        &mut MenuButtonMesh,
-        &mut MeshMaterial2d<ColorMaterial>,
-    )>,
+// Hover and click menu buttons with the mouse. The cursor only takes the
+// focus when it moves or a button appears under it, so it doesn't fight a
+// gamepad for it.
+pub fn menu_pointer_system(
+    button_query: Query<(Entity, &Transform, &MenuButtonMesh)>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut focus: ResMut<MenuFocus>,
+    mut pressed: EventWriter<MenuButtonPressed>,
+    mut last_pointer: Local<(Option<Vec2>, Option<Entity>)>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    // Get cursor position
+    let cursor_pos = window.cursor_position();
+    let mut hovered = None;
+    if let Some(cursor_pos) = cursor_pos {
+        // Convert screen coordinates to world coordinates
+        let window_size = Vec2::new(window.width(), window.height());
+        let ndc = (cursor_pos / window_size) * 2.0 - Vec2::ONE;
+        let ndc = Vec3::new(ndc.x, -ndc.y, 0.0);
+
+        if let Some(world_pos) = camera.ndc_to_world(camera_transform, ndc) {
+            let world_pos_2d = world_pos.truncate();
+
+            hovered = button_query
+                .iter()
+                .find(|(_, transform, button)| {
+                    let button_pos = transform.translation.truncate();
+                    let half_bounds = button.bounds * 0.5;
+
+                    // Check if cursor is within button bounds
+                    world_pos_2d.x >= button_pos.x - half_bounds.x
+                        && world_pos_2d.x <= button_pos.x + half_bounds.x
+                        && world_pos_2d.y >= button_pos.y - half_bounds.y
+                        && world_pos_2d.y <= button_pos.y + half_bounds.y
+                })
+                .map(|(entity, _, _)| entity);
+        }
+    }
+
+    if *last_pointer != (cursor_pos, hovered) {
+        focus.0 = hovered;
+        *last_pointer = (cursor_pos, hovered);
+    }
+
+    // Handle clicks
+    if mouse_input.just_pressed(MouseButton::Left)
+        && let Some((_, _, button)) = hovered.and_then(|entity| button_query.get(entity).ok())
+    {
+        pressed.write(MenuButtonPressed {
+            action: button.action.clone(),
+        });
+    }
+}
+
+// Move the focus between menu buttons with the D-pad or left stick and press
+// the focused one with South
+pub fn menu_gamepad_system(
+    gamepads: Query<&Gamepad>,
+    settings: Res<GameSettings>,
+    button_query: Query<(Entity, &Transform, &MenuButtonMesh)>,
+    mut focus: ResMut<MenuFocus>,
+    mut pressed: EventWriter<MenuButtonPressed>,
+    mut stick_direction: Local<Option<IVec2>>,
+) {
+    let mut direction = None;
+    let mut activate = false;
+    let mut stick = None;
+    for gamepad in gamepads.iter() {
+        for (button, step) in [
+            (GamepadButton::DPadUp, IVec2::Y),
+            (GamepadButton::DPadDown, IVec2::NEG_Y),
+            (GamepadButton::DPadLeft, IVec2::NEG_X),
+            (GamepadButton::DPadRight, IVec2::X),
+        ] {
+            if gamepad.just_pressed(button) {
+                direction = Some(step);
+            }
+        }
+        activate |= gamepad.just_pressed(GamepadButton::South);
+        stick = stick.or(settings
+            .gamepad
+            .stick(gamepad.left_stick())
+            .filter(|stick| stick.length() > 0.5)
+            .map(menu_direction));
+    }
+
+    // The stick moves one button per push, like the D-pad
+    if stick != *stick_direction {
+        direction = direction.or(stick);
+        *stick_direction = stick;
+    }
+
+    let current = focus
+        .0
+        .and_then(|entity| button_query.get(entity).ok())
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate()));
+
+    if let Some(step) = direction {
+        focus.0 = match current {
+            Some((entity, position)) => {
+                neighbour(&button_query, entity, position, step.as_vec2()).or(Some(entity))
+            }
+            // Nothing focused yet: start at the top
+            None => button_query
+                .iter()
+                .max_by(|(_, a, _), (_, b, _)| {
+                    a.translation
+                        .y
+                        .total_cmp(&b.translation.y)
+                        .then(b.translation.x.total_cmp(&a.translation.x))
+                })
+                .map(|(entity, _, _)| entity),
+        };
+    } else if activate
+        && let Some((entity, _)) = current
+        && let Ok((_, _, button)) = button_query.get(entity)
+    {
+        pressed.write(MenuButtonPressed {
+            action: button.action.clone(),
+        });
+    }
+}
+
+// The main direction a stick points in
+fn menu_direction(stick: Vec2) -> IVec2 {
+    if stick.x.abs() > stick.y.abs() {
+        IVec2::new(stick.x.signum() as i32, 0)
+    } else {
+        IVec2::new(0, stick.y.signum() as i32)
+    }
+}
+
+// Nearest button in `direction`, preferring ones in line with the current one
+fn neighbour(
+    button_query: &Query<(Entity, &Transform, &MenuButtonMesh)>,
+    from: Entity,
+    position: Vec2,
+    direction: Vec2,
+) -> Option<Entity> {
+    button_query
+        .iter()
+        .filter(|(entity, _, _)| *entity != from)
+        .filter_map(|(entity, transform, _)| {
+            let offset = transform.translation.truncate() - position;
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+            (along > 1.0).then_some((entity, along + across * 2.0))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+// Draw the focused button in its hover colour
+pub fn highlight_menu_focus(
+    focus: Res<MenuFocus>,
+    mut button_query: Query<(Entity, &mut MenuButtonMesh, &MeshMaterial2d<ColorMaterial>)>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, mut button, material_handle) in button_query.iter_mut() {
+        let is_focused = focus.0 == Some(entity);
+        if is_focused != button.is_hovered {
+            button.is_hovered = is_focused;
+
+            let color = if is_focused {
+                button.hover_color
+            } else {
+                button.base_color
+            };
+
+            if let Some(material) = materials.get_mut(material_handle.id()) {
+                material.color = color;
+            }
+        }
+    }
+}
+
+// Carry out what the pressed menu buttons do
+pub fn mesh_menu_button_system(
+    mut commands: Commands,
+    mut pressed: EventReader<MenuButtonPressed>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut next_menu_state: ResMut<NextState<MainMenuState>>,
+    pause_state: Option<Res<State<PauseState>>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
     mut difficulty: ResMut<DifficultySettings>,
+    presets: Res<DifficultyPresets>,
     mut exit: EventWriter<AppExit>,
     mut settings: ResMut<GameSettings>,
+    mut seed_entry: ResMut<SeedEntry>,
+    last_replay: Res<LastReplay>,
+) {
+    for event in pressed.read() {
+        match &event.action {
+            MenuAction::StartGame => {
+                next_state.set(GameState::Playing);
+            }
+            MenuAction::Continue => {
+                commands.queue(continue_suspended_run);
+            }
+            MenuAction::DifficultySelect => {
+                // Endless mode: leave the campaign behind
+                commands.remove_resource::<CampaignRun>();
+                next_state.set(GameState::DifficultySelect);
+            }
+            MenuAction::Campaign => {
+                next_menu_state.set(MainMenuState::LevelSelect);
+            }
+            MenuAction::StartLevel(level_id) => {
+                let level_id = level_id.clone();
+                commands.queue(move |world: &mut World| {
+                    start_campaign_level(world, &level_id);
+                });
+                next_state.set(GameState::Playing);
+            }
+            MenuAction::NextLevel => {
+                commands.queue(start_next_campaign_level);
+                next_state.set(GameState::Playing);
+            }
+            MenuAction::LevelSelect => {
+                next_state.set(GameState::MainMenu);
+                next_menu_state.set(MainMenuState::LevelSelect);
+            }
+            MenuAction::TitleScreen => {
+                next_menu_state.set(MainMenuState::Title);
+            }
+            MenuAction::Leaderboards => {
+                next_menu_state.set(MainMenuState::Leaderboard);
+            }
+            MenuAction::NextLeaderboard => {
+                commands.queue(|world: &mut World| {
+                    world.resource_mut::<LeaderboardView>().index += 1;
+                });
+            }
+            MenuAction::Stats => {
+                next_menu_state.set(MainMenuState::Stats);
+            }
+            MenuAction::Achievements => {
+                next_menu_state.set(MainMenuState::Achievements);
+            }
+            MenuAction::Settings => {
+                // Inside a run, Settings is an overlay on top of the paused game
+                if pause_state.is_some() {
+                    next_pause_state.set(PauseState::Settings);
+                } else {
+                    next_state.set(GameState::Settings);
+                }
+            }
+            MenuAction::SetDifficulty(id) => {
+                *difficulty = presets.resolve(*id, &settings);
+                next_state.set(GameState::Playing);
+            }
+            MenuAction::CustomDifficulty => {
+                next_state.set(GameState::CustomDifficulty);
+            }
+            MenuAction::EditSeed => {
+                seed_entry.editing = !seed_entry.editing;
+            }
+            MenuAction::GameOverOptions => {
+                commands.queue(|world: &mut World| {
+                    world
+                        .resource_mut::<NextState<GameOverState>>()
+                        .set(GameOverState::Options);
+                });
+            }
+            MenuAction::ExportReport => {
+                commands.queue(export_run_report);
+            }
+            MenuAction::PlayAgain => {
+                next_state.set(GameState::Playing);
+            }
+            MenuAction::WatchReplay => {
+                if let Some(replay) = &last_replay.0 {
+                    let replay = replay.clone();
+                    commands.queue(move |world: &mut World| {
+                        start_replay_playback(world, replay);
+                    });
+                    next_state.set(GameState::Playing);
+                }
+            }
+            MenuAction::QuitToMenu => {
+                next_state.set(GameState::MainMenu);
+            }
+            MenuAction::Exit => {
+                exit.write(AppExit::Success);
+            }
+            MenuAction::Resume => {
+                next_pause_state.set(PauseState::Running);
+            }
+            MenuAction::MainMenu => {
+                next_state.set(GameState::MainMenu);
+            }
+            MenuAction::SaveAndQuit => {
+                commands.queue(suspend_run);
+            }
+            MenuAction::BloomToggle => {
+                settings.graphics.bloom_enabled = !settings.graphics.bloom_enabled;
+                report_save_error(&mut commands, GameSettings::FILE_NAME, settings.save());
+            }
+            MenuAction::VsyncToggle => {
+                settings.graphics.vsync_enabled = !settings.graphics.vsync_enabled;
+                report_save_error(&mut commands, GameSettings::FILE_NAME, settings.save());
+            }
+            MenuAction::Controls => {
+                if pause_state.is_some() {
+                    next_pause_state.set(PauseState::Controls);
+                } else {
+                    next_state.set(GameState::Controls);
+                }
+            }
+            MenuAction::Rebind(action) => {
+                let action = *action;
+                commands.queue(move |world: &mut World| {
+                    if world.get_resource::<Rebinding>() == Some(&Rebinding(action)) {
+                        world.remove_resource::<Rebinding>();
+                    } else {
+                        world.insert_resource(Rebinding(action));
+                    }
+                });
+            }
+            MenuAction::ResetControls => {
+                commands.remove_resource::<Rebinding>();
+                settings.controls = InputBindings::default();
+                report_save_error(&mut commands, GameSettings::FILE_NAME, settings.save());
+            }
+            MenuAction::StickModeToggle => {
+                settings.gamepad.stick = match settings.gamepad.stick {
+                    StickMode::Rotate => StickMode::Aim,
+                    StickMode::Aim => StickMode::Rotate,
+                };
+                report_save_error(&mut commands, GameSettings::FILE_NAME, settings.save());
+            }
+            MenuAction::Back => {
+                if pause_state.is_some() {
+                    next_pause_state.set(PauseState::Paused);
+                } else {
+                    next_state.set(GameState::MainMenu);
+                }
+            }
+        }
+    }
+}
+
+// Typing a seed on the difficulty menu: digits append, Backspace deletes, Enter confirms
+pub fn seed_entry_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut seed_entry: ResMut<SeedEntry>,
+    mut rng: ResMut<GameRng>,
+    mut text_query: Query<&mut Text, With<SeedEntryText>>,
 ) {
-    if let Ok(window) = windows.single() {
-        if let Ok((camera, camera_transform)) = camera_query.single() {
-            // Get cursor position
-            if let Some(cursor_pos) = window.cursor_position() {
-                // Convert screen coordinates to world coordinates
-                let window_size = Vec2::new(window.width(), window.height());
-                let ndc = (cursor_pos / window_size) * 2.0 - Vec2::ONE;
-                let ndc = Vec3::new(ndc.x, -ndc.y, 0.0);
-
-                if let Some(world_pos) = camera.ndc_to_world(camera_transform, ndc) {
-                    let world_pos_2d = world_pos.truncate();
-
-                    for (transform, mut button, material_handle) in button_query.iter_mut() {
-                        let button_pos = transform.translation.truncate();
-                        let half_bounds = button.bounds * 0.5;
-
-                        // Check if cursor is within button bounds
-                        let is_hovering = world_pos_2d.x >= button_pos.x - half_bounds.x
-                            && world_pos_2d.x <= button_pos.x + half_bounds.x
-                            && world_pos_2d.y >= button_pos.y - half_bounds.y
-                            && world_pos_2d.y <= button_pos.y + half_bounds.y;
-
-                        // Update hover state and material color
-                        if is_hovering != button.is_hovered {
-                            button.is_hovered = is_hovering;
-
-                            let color = if is_hovering {
-                                button.hover_color
-                            } else {
-                                button.base_color
-                            };
-
-                            if let Some(material) = materials.get_mut(material_handle.id()) {
-                                material.color = color;
-                            }
-                        }
-
-                        // Handle clicks
-                        if is_hovering && mouse_input.just_pressed(MouseButton::Left) {
-                            match &button.action {
-                                MenuAction::StartGame => {
-                                    next_state.set(GameState::Playing);
-                                }
-                                MenuAction::DifficultySelect => {
-                                    next_state.set(GameState::DifficultySelect);
-                                }
-                                MenuAction::Settings => {
-                                    next_state.set(GameState::Settings);
-                                }
-                                MenuAction::SetDifficulty(difficulty_name) => {
-                                    *difficulty = match difficulty_name.as_str() {
-                                        "Easy" => DifficultySettings::easy(),
-                                        "Normal" => DifficultySettings::normal(),
-                                        "Hard" => DifficultySettings::hard(),
-                                        "Insane" => DifficultySettings::insane(),
-                                        _ => DifficultySettings::normal(),
-                                    };
-                                    next_state.set(GameState::Playing);
-                                }
-                                MenuAction::PlayAgain => {
-                                    next_state.set(GameState::Playing);
-                                }
-                                MenuAction::QuitToMenu => {
-                                    next_state.set(GameState::MainMenu);
-                                }
-                                MenuAction::Exit => {
-                                    exit.write(AppExit::Success);
-                                }
-                                MenuAction::Resume => {
-                                    next_state.set(GameState::Playing);
-                                }
-                                MenuAction::MainMenu => {
-                                    next_state.set(GameState::MainMenu);
-                                }
-                                MenuAction::BloomToggle => {
-                                    settings.graphics.bloom_enabled =
-                                        !settings.graphics.bloom_enabled;
-                                    settings.save();
-                                }
-                                MenuAction::VsyncToggle => {
-                                    settings.graphics.vsync_enabled =
-                                        !settings.graphics.vsync_enabled;
-                                    settings.save();
-                                }
-                                MenuAction::Back => {
-                                    next_state.set(GameState::MainMenu);
-                                }
-                            }
-                        }
+    if seed_entry.editing {
+        for key in keyboard_input.get_just_pressed() {
+            if let Some(digit) = key_to_digit(key) {
+                if seed_entry.text.len() < 19 {
+                    seed_entry.text.push(digit); // 19 digits always fit in a u64
+                }
+            } else if *key == KeyCode::Backspace {
+                seed_entry.text.pop();
+            } else if matches!(key, KeyCode::Enter | KeyCode::NumpadEnter) {
+                seed_entry.editing = false;
+            }
+        }
+    }
+
+    if seed_entry.is_changed() {
+        rng.requested_seed = seed_entry.text.parse().ok();
+        for mut text in text_query.iter_mut() {
+            **text = seed_entry_label(&seed_entry);
+        }
+    }
+}
+
+// Typing a name for a new high score on the game-over screen; Enter saves it
+pub fn name_entry_system(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    pending_high_score: Option<ResMut<PendingHighScore>>,
+    mut save_data: ResMut<SaveData>,
+    mut text_query: Query<&mut Text, With<NameEntryText>>,
+) {
+    let Some(mut pending_high_score) = pending_high_score else {
+        keyboard_events.clear();
+        return;
+    };
+
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(typed) => {
+                for character in typed.chars() {
+                    if (character.is_alphanumeric() || matches!(character, '-' | '_' | '.'))
+                        && pending_high_score.entry.name.chars().count() < MAX_NAME_LENGTH
+                    {
+                        pending_high_score.entry.name.push(character);
                     }
                 }
             }
+            Key::Space if pending_high_score.entry.name.chars().count() < MAX_NAME_LENGTH => {
+                pending_high_score.entry.name.push(' ');
+            }
+            Key::Backspace => {
+                pending_high_score.entry.name.pop();
+            }
+            Key::Enter => {
+                let result = save_high_score(&mut save_data, &pending_high_score);
+                report_save_error(&mut commands, SaveData::FILE_NAME, result);
+                commands.remove_resource::<PendingHighScore>();
+                for mut text in text_query.iter_mut() {
+                    **text = format!(
+                        "Saved: #{} on {}",
+                        pending_high_score.rank, pending_high_score.title
+                    );
+                }
+                return;
+            }
+            _ => {}
         }
     }
+
+    if pending_high_score.is_changed() {
+        for mut text in text_query.iter_mut() {
+            **text = name_entry_label(&pending_high_score);
+        }
+    }
+}
+
+// Show the selected board, cycling through the saved ones
+pub fn update_leaderboard_text(
+    view: Res<LeaderboardView>,
+    save_data: Res<SaveData>,
+    presets: Res<DifficultyPresets>,
+    campaign: Res<Campaign>,
+    mut text_query: Query<&mut Text, With<LeaderboardText>>,
+) {
+    if !view.is_changed() {
+        return;
+    }
+    let boards: Vec<&String> = save_data.leaderboards.keys().collect();
+    let table = if boards.is_empty() {
+        "No scores yet. Finish a run to get on the board.".to_string()
+    } else {
+        let board = boards[view.index % boards.len()];
+        format!(
+            "({} / {})  {}",
+            view.index % boards.len() + 1,
+            boards.len(),
+            leaderboard_table(
+                &leaderboard_title(board, &presets, &campaign),
+                save_data.leaderboard(board)
+            )
+        )
+    };
+    for mut text in text_query.iter_mut() {
+        **text = table.clone();
+    }
+}
+
+fn key_to_digit(key: &KeyCode) -> Option<char> {
+    let digit = match key {
+        KeyCode::Digit0 | KeyCode::Numpad0 => '0',
+        KeyCode::Digit1 | KeyCode::Numpad1 => '1',
+        KeyCode::Digit2 | KeyCode::Numpad2 => '2',
+        KeyCode::Digit3 | KeyCode::Numpad3 => '3',
+        KeyCode::Digit4 | KeyCode::Numpad4 => '4',
+        KeyCode::Digit5 | KeyCode::Numpad5 => '5',
+        KeyCode::Digit6 | KeyCode::Numpad6 => '6',
+        KeyCode::Digit7 | KeyCode::Numpad7 => '7',
+        KeyCode::Digit8 | KeyCode::Numpad8 => '8',
+        KeyCode::Digit9 | KeyCode::Numpad9 => '9',
+        _ => return None,
+    };
+    Some(digit)
 }
 
 pub fn cleanup_styled_menu(
@@ -121,6 +501,10 @@ pub fn cleanup_styled_menu(
             With<MainMenu>,
             With<DifficultyMenu>,
             With<GameOverMenu>,
+            With<LevelSelectMenu>,
+            With<LeaderboardMenu>,
+            With<StatsMenu>,
+            With<AchievementsMenu>,
             With<MenuText3D>,
             With<MenuButtonMesh>,
         )>,
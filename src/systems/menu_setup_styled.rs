@@ -3,6 +3,7 @@ use crate::components::*;
 use crate::resources::*;
 use crate::systems::button_helpers::*;
 use bevy::prelude::*;
+use std::collections::BTreeMap;
 
 pub fn setup_main_menu_styled(
     mut commands: Commands,
@@ -34,6 +35,27 @@ pub fn setup_main_menu_styled(
         // Create button meshes and materials
         let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
 
+        // Continue button, while there is a suspended run
+        if RunSnapshot::exists() {
+            spawn_button_with_text(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                window_width,
+                window_height,
+                button_mesh.clone(),
+                ButtonWithText::new(
+                    "CONTINUE",
+                    MenuAction::Continue,
+                    Vec2::new(200.0, 50.0),
+                    Color::srgb(2.5, 2.5, 1.5),
+                    24.0,
+                    Vec3::new(0.0, 80.0, 0.0),
+                ),
+                MainMenu,
+            );
+        }
+
         // Start Game button
         spawn_button_with_text(
             &mut commands,
@@ -48,11 +70,87 @@ pub fn setup_main_menu_styled(
                 Vec2::new(200.0, 50.0),
                 Color::srgb(2.0, 2.5, 2.0),
                 24.0,
+                Vec3::new(0.0, 30.0, 0.0),
+            ),
+            MainMenu,
+        );
+
+        // Campaign button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "CAMPAIGN",
+                MenuAction::Campaign,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.5, 2.0, 1.5),
+                24.0,
                 Vec3::new(0.0, -20.0, 0.0),
             ),
             MainMenu,
         );
 
+        // Leaderboards button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "LEADERBOARDS",
+                MenuAction::Leaderboards,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(1.5, 2.5, 2.5),
+                24.0,
+                Vec3::new(0.0, -70.0, 0.0),
+            ),
+            MainMenu,
+        );
+
+        // Stats button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "STATS",
+                MenuAction::Stats,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.5, 2.5, 2.0),
+                24.0,
+                Vec3::new(0.0, -120.0, 0.0),
+            ),
+            MainMenu,
+        );
+
+        // Achievements button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "ACHIEVEMENTS",
+                MenuAction::Achievements,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.5, 2.0, 2.5),
+                24.0,
+                Vec3::new(0.0, -170.0, 0.0),
+            ),
+            MainMenu,
+        );
+
         // Settings button
         spawn_button_with_text(
             &mut commands,
@@ -67,7 +165,7 @@ pub fn setup_main_menu_styled(
                 Vec2::new(200.0, 50.0),
                 Color::srgb(2.0, 2.0, 2.5),
                 24.0,
-                Vec3::new(0.0, -70.0, 0.0),
+                Vec3::new(0.0, -220.0, 0.0),
             ),
             MainMenu,
         );
@@ -86,19 +184,409 @@ pub fn setup_main_menu_styled(
                 Vec2::new(200.0, 50.0),
                 Color::srgb(2.5, 1.5, 1.5),
                 24.0,
-                Vec3::new(0.0, -120.0, 0.0),
+                Vec3::new(0.0, -270.0, 0.0),
             ),
             MainMenu,
         );
     }
 }
 
+pub fn setup_level_select_menu(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    windows: Query<&Window>,
+    campaign: Res<Campaign>,
+    save_data: Res<SaveData>,
+) {
+    if let Ok(window) = windows.single() {
+        let window_width = window.width();
+        let window_height = window.height();
+
+        // Title
+        commands.spawn((
+            Text::new("CAMPAIGN"),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(3.0, 3.0, 3.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.08),
+                left: Val::Px(window_width * 0.5 - 85.0),
+                ..default()
+            },
+            LevelSelectMenu,
+            MenuText3D,
+        ));
+
+        let button_mesh = meshes.add(create_button_outline_mesh(320.0, 44.0));
+
+        // One row per level: a button with its stars, the objective underneath.
+        // Locked levels are listed but can't be picked.
+        for (i, level) in campaign.levels().iter().enumerate() {
+            let y_pos = 180.0 - (i as f32 * 70.0);
+            let unlocked = campaign.is_unlocked(i, &save_data);
+
+            let (summary, summary_color) = if unlocked {
+                let stars = save_data.level_stars(&level.id) as usize;
+                spawn_button_with_text(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    window_width,
+                    window_height,
+                    button_mesh.clone(),
+                    ButtonWithText::new(
+                        format!(
+                            "{}. {}  [{}{}]",
+                            i + 1,
+                            level.name,
+                            "*".repeat(stars),
+                            "-".repeat(3 - stars.min(3))
+                        ),
+                        MenuAction::StartLevel(level.id.clone()),
+                        Vec2::new(320.0, 44.0),
+                        if stars > 0 {
+                            Color::srgb(1.5, 2.5, 1.5)
+                        } else {
+                            Color::srgb(2.5, 2.5, 1.5)
+                        },
+                        20.0,
+                        Vec3::new(0.0, y_pos, 0.0),
+                    ),
+                    LevelSelectMenu,
+                );
+                (level.objective_summary(), Color::srgb(2.0, 2.0, 2.0))
+            } else {
+                let label = format!("{}. Locked", i + 1);
+                commands.spawn((
+                    Text::new(label.clone()),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor::from(Color::srgb(0.8, 0.8, 0.8)),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(window_height * 0.5 - y_pos - 12.0),
+                        left: Val::Px(window_width * 0.5 - label.len() as f32 * 7.0),
+                        ..default()
+                    },
+                    LevelSelectMenu,
+                    MenuText3D,
+                ));
+                (
+                    "Complete the previous level to unlock".to_string(),
+                    Color::srgb(0.8, 0.8, 0.8),
+                )
+            };
+
+            commands.spawn((
+                Text::new(summary),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor::from(summary_color),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(window_height * 0.5 - y_pos + 24.0),
+                    left: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    ..default()
+                },
+                LevelSelectMenu,
+                MenuText3D,
+            ));
+        }
+
+        // Back button
+        let back_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            back_mesh,
+            ButtonWithText::new(
+                "BACK",
+                MenuAction::TitleScreen,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.0, 2.0, 2.0),
+                24.0,
+                Vec3::new(0.0, -240.0, 0.0),
+            ),
+            LevelSelectMenu,
+        );
+    }
+}
+
+pub fn setup_leaderboard_menu(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    windows: Query<&Window>,
+    mut view: ResMut<LeaderboardView>,
+) {
+    view.index = 0;
+
+    if let Ok(window) = windows.single() {
+        let window_width = window.width();
+        let window_height = window.height();
+
+        // Title
+        commands.spawn((
+            Text::new("LEADERBOARDS"),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(3.0, 3.0, 3.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.08),
+                left: Val::Px(window_width * 0.5 - 130.0),
+                ..default()
+            },
+            LeaderboardMenu,
+            MenuText3D,
+        ));
+
+        // The board itself, filled in by `update_leaderboard_text`
+        commands.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(2.0, 2.0, 2.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.2),
+                left: Val::Px(window_width * 0.5 - 270.0),
+                ..default()
+            },
+            LeaderboardText,
+            LeaderboardMenu,
+            MenuText3D,
+        ));
+
+        let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
+
+        // Next board button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "NEXT BOARD",
+                MenuAction::NextLeaderboard,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(1.5, 2.5, 2.5),
+                24.0,
+                Vec3::new(0.0, -180.0, 0.0),
+            ),
+            LeaderboardMenu,
+        );
+
+        // Back button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "BACK",
+                MenuAction::TitleScreen,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.0, 2.0, 2.0),
+                24.0,
+                Vec3::new(0.0, -240.0, 0.0),
+            ),
+            LeaderboardMenu,
+        );
+    }
+}
+
+pub fn setup_stats_menu(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    windows: Query<&Window>,
+    save_data: Res<SaveData>,
+    presets: Res<DifficultyPresets>,
+) {
+    if let Ok(window) = windows.single() {
+        let window_width = window.width();
+        let window_height = window.height();
+
+        // Title
+        commands.spawn((
+            Text::new("STATISTICS"),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(3.0, 3.0, 3.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.08),
+                left: Val::Px(window_width * 0.5 - 110.0),
+                ..default()
+            },
+            StatsMenu,
+            MenuText3D,
+        ));
+
+        // Two columns side by side
+        let (left_column, right_column) = stats_tables(&save_data, &presets);
+        for (table, left) in [(left_column, -300.0), (right_column, 30.0)] {
+            commands.spawn((
+                Text::new(table),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.0, 2.0, 2.0)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(window_height * 0.18),
+                    left: Val::Px(window_width * 0.5 + left),
+                    ..default()
+                },
+                StatsMenu,
+                MenuText3D,
+            ));
+        }
+
+        // Back button
+        let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh,
+            ButtonWithText::new(
+                "BACK",
+                MenuAction::TitleScreen,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.0, 2.0, 2.0),
+                24.0,
+                Vec3::new(0.0, -260.0, 0.0),
+            ),
+            StatsMenu,
+        );
+    }
+}
+
+pub fn setup_achievements_menu(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    windows: Query<&Window>,
+    achievements: Res<Achievements>,
+    save_data: Res<SaveData>,
+) {
+    if let Ok(window) = windows.single() {
+        let window_width = window.width();
+        let window_height = window.height();
+
+        // Title, with how many are unlocked
+        let unlocked = achievements
+            .iter()
+            .filter(|achievement| save_data.has_achievement(&achievement.id))
+            .count();
+        commands.spawn((
+            Text::new(format!(
+                "ACHIEVEMENTS  {}/{}",
+                unlocked,
+                achievements.iter().count()
+            )),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(3.0, 3.0, 3.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.08),
+                left: Val::Px(window_width * 0.5 - 200.0),
+                ..default()
+            },
+            AchievementsMenu,
+            MenuText3D,
+        ));
+
+        // Two columns side by side
+        let (left_column, right_column) = achievements_tables(&achievements, &save_data);
+        for (table, left) in [(left_column, -370.0), (right_column, 20.0)] {
+            commands.spawn((
+                Text::new(table),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.0, 2.0, 2.0)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(window_height * 0.18),
+                    left: Val::Px(window_width * 0.5 + left),
+                    ..default()
+                },
+                AchievementsMenu,
+                MenuText3D,
+            ));
+        }
+
+        // Back button
+        let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh,
+            ButtonWithText::new(
+                "BACK",
+                MenuAction::TitleScreen,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.0, 2.0, 2.0),
+                24.0,
+                Vec3::new(0.0, -260.0, 0.0),
+            ),
+            AchievementsMenu,
+        );
+    }
+}
+
 pub fn setup_difficulty_menu_styled(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     windows: Query<&Window>,
+    rng: Res<GameRng>,
+    mut seed_entry: ResMut<SeedEntry>,
+    presets: Res<DifficultyPresets>,
 ) {
+    // Start from the seed already requested (command line or a previous visit)
+    seed_entry.editing = false;
+    seed_entry.text = rng
+        .requested_seed
+        .map(|seed| seed.to_string())
+        .unwrap_or_default();
+
     if let Ok(window) = windows.single() {
         let window_width = window.width();
         let window_height = window.height();
@@ -124,16 +612,25 @@ pub fn setup_difficulty_menu_styled(
         // Create button mesh
         let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
 
-        // Difficulty buttons with colors
-        let difficulties = [
-            ("Easy", Color::srgb(1.5, 2.5, 1.5)),   // Green
-            ("Normal", Color::srgb(1.5, 1.5, 2.5)), // Blue
-            ("Hard", Color::srgb(2.5, 2.0, 1.5)),   // Orange
-            ("Insane", Color::srgb(2.5, 1.5, 1.5)), // Red
-        ];
+        // One button per preset from difficulties.json, then Custom
+        let difficulties = presets
+            .iter()
+            .map(|preset| {
+                let [r, g, b] = preset.menu_color;
+                (
+                    preset.settings.name.clone(),
+                    MenuAction::SetDifficulty(preset.settings.id),
+                    Color::srgb(r, g, b),
+                )
+            })
+            .chain(std::iter::once((
+                "Custom".to_string(),
+                MenuAction::CustomDifficulty,
+                Color::srgb(2.0, 1.5, 2.5), // Purple
+            )));
 
-        for (i, (difficulty, color)) in difficulties.iter().enumerate() {
-            let y_pos = 100.0 - (i as f32 * 70.0);
+        for (i, (name, action, color)) in difficulties.enumerate() {
+            let y_pos = 130.0 - (i as f32 * 60.0);
 
             spawn_button_with_text(
                 &mut commands,
@@ -143,10 +640,10 @@ pub fn setup_difficulty_menu_styled(
                 window_height,
                 button_mesh.clone(),
                 ButtonWithText::new(
-                    *difficulty,
-                    MenuAction::SetDifficulty(difficulty.to_string()),
+                    name,
+                    action,
                     Vec2::new(200.0, 50.0),
-                    *color,
+                    color,
                     24.0,
                     Vec3::new(0.0, y_pos, 0.0),
                 ),
@@ -154,6 +651,44 @@ pub fn setup_difficulty_menu_styled(
             );
         }
 
+        // Seed button - click to type a seed, leave empty for a random one
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "SET SEED",
+                MenuAction::EditSeed,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.0, 2.0, 1.5),
+                24.0,
+                Vec3::new(0.0, -170.0, 0.0),
+            ),
+            DifficultyMenu,
+        );
+
+        // Current seed, shown to the right of the seed button
+        commands.spawn((
+            Text::new(seed_entry_label(&seed_entry)),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(2.0, 2.0, 1.5)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.5 + 170.0 - 12.0),
+                left: Val::Px(window_width * 0.5 + 115.0),
+                ..default()
+            },
+            SeedEntryText,
+            DifficultyMenu,
+            MenuText3D,
+        ));
+
         // Back button
         spawn_button_with_text(
             &mut commands,
@@ -168,13 +703,141 @@ pub fn setup_difficulty_menu_styled(
                 Vec2::new(200.0, 50.0),
                 Color::srgb(2.0, 2.0, 2.0),
                 24.0,
-                Vec3::new(0.0, -200.0, 0.0),
+                Vec3::new(0.0, -240.0, 0.0),
             ),
             DifficultyMenu,
         );
     }
 }
 
+// First page of the game-over screen: what happened in the run, against
+// the best on its leaderboard
+pub fn setup_run_report_menu(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    windows: Query<&Window>,
+    report: Option<Res<RunReport>>,
+    mut next_page: ResMut<NextState<GameOverState>>,
+) {
+    // Without the stats there is nothing to report
+    let Some(report) = report else {
+        next_page.set(GameOverState::Options);
+        return;
+    };
+
+    if let Ok(window) = windows.single() {
+        let window_width = window.width();
+        let window_height = window.height();
+
+        // Title
+        commands.spawn((
+            Text::new("RUN REPORT"),
+            TextFont {
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(3.0, 3.0, 3.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.06),
+                left: Val::Px(window_width * 0.5 - 110.0),
+                ..default()
+            },
+            GameOverMenu,
+            MenuText3D,
+        ));
+
+        // Board played, and whether the score beat its best
+        let (headline, color) = match &report.personal_best {
+            Some(_) if report.is_personal_best() => (
+                format!("{}   NEW PERSONAL BEST!", report.board),
+                Color::srgb(2.5, 2.5, 1.0),
+            ),
+            _ => (report.board.clone(), Color::srgb(2.0, 2.0, 2.0)),
+        };
+        commands.spawn((
+            Text::new(headline),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor::from(color),
+            TextLayout::new_with_justify(JustifyText::Center),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.14),
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                ..default()
+            },
+            GameOverMenu,
+            MenuText3D,
+        ));
+
+        // Two columns side by side
+        let (left_column, right_column) = run_report_tables(&report);
+        for (table, left) in [(left_column, -370.0), (right_column, 40.0)] {
+            commands.spawn((
+                Text::new(table),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.0, 2.0, 2.0)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(window_height * 0.19),
+                    left: Val::Px(window_width * 0.5 + left),
+                    ..default()
+                },
+                GameOverMenu,
+                MenuText3D,
+            ));
+        }
+
+        let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
+
+        // Export button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "EXPORT",
+                MenuAction::ExportReport,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(1.5, 2.5, 2.5),
+                24.0,
+                Vec3::new(-120.0, -240.0, 0.0),
+            ),
+            GameOverMenu,
+        );
+
+        // Continue button, on to play again or leave
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh,
+            ButtonWithText::new(
+                "CONTINUE",
+                MenuAction::GameOverOptions,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(1.5, 2.5, 1.5),
+                24.0,
+                Vec3::new(120.0, -240.0, 0.0),
+            ),
+            GameOverMenu,
+        );
+    }
+}
+
 pub fn setup_game_over_menu_styled(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -182,23 +845,39 @@ pub fn setup_game_over_menu_styled(
     windows: Query<&Window>,
     score: Res<GameScore>,
     difficulty: Res<DifficultySettings>,
+    rng: Res<GameRng>,
+    last_replay: Res<LastReplay>,
+    verification: Option<Res<ReplayVerification>>,
+    campaign: Res<Campaign>,
+    campaign_run: Option<Res<CampaignRun>>,
+    pending_high_score: Option<Res<PendingHighScore>>,
 ) {
     if let Ok(window) = windows.single() {
         let window_width = window.width();
         let window_height = window.height();
 
+        // A campaign level reports whether its objective was met
+        let cleared = campaign_run
+            .as_ref()
+            .is_some_and(|campaign_run| campaign_run.status == ObjectiveStatus::Complete);
+        let (title, title_offset, title_color) = match &campaign_run {
+            Some(_) if cleared => ("LEVEL COMPLETE", 185.0, Color::srgb(1.0, 3.0, 1.0)),
+            Some(_) => ("LEVEL FAILED", 160.0, Color::srgb(3.0, 1.0, 1.0)),
+            None => ("GAME OVER", 120.0, Color::srgb(3.0, 1.0, 1.0)), // Bright red glow
+        };
+
         // Game Over title
         commands.spawn((
-            Text::new("GAME OVER"),
+            Text::new(title),
             TextFont {
                 font_size: 48.0,
                 ..default()
             },
-            TextColor::from(Color::srgb(3.0, 1.0, 1.0)), // Bright red glow
+            TextColor::from(title_color),
             Node {
                 position_type: PositionType::Absolute,
                 top: Val::Px(window_height * 0.15),
-                left: Val::Px(window_width * 0.5 - 120.0),
+                left: Val::Px(window_width * 0.5 - title_offset),
                 ..default()
             },
             GameOverMenu,
@@ -207,7 +886,10 @@ pub fn setup_game_over_menu_styled(
 
         // Final score
         commands.spawn((
-            Text::new(format!("Final Score: {}", score.score)),
+            Text::new(format!(
+                "Final Score: {}   Max Combo: {}",
+                score.score, score.max_combo
+            )),
             TextFont {
                 font_size: 24.0,
                 ..default()
@@ -216,16 +898,26 @@ pub fn setup_game_over_menu_styled(
             Node {
                 position_type: PositionType::Absolute,
                 top: Val::Px(window_height * 0.25),
-                left: Val::Px(window_width * 0.5 - 80.0),
+                left: Val::Px(window_width * 0.5 - 150.0),
                 ..default()
             },
             GameOverMenu,
             MenuText3D,
         ));
 
-        // Difficulty played
+        // Difficulty played, or the level and the stars it earned
+        let summary = match &campaign_run {
+            Some(campaign_run) if cleared => format!(
+                "{}  Stars: {} / 3",
+                campaign_run.level.name,
+                campaign_run.level.stars(score.score, campaign_run.hits)
+            ),
+            Some(campaign_run) => campaign_run.level.name.clone(),
+            None => format!("Difficulty: {}", difficulty.name),
+        };
+        let summary_offset = summary.len() as f32 * 5.0;
         commands.spawn((
-            Text::new(format!("Difficulty: {}", difficulty.name)),
+            Text::new(summary),
             TextFont {
                 font_size: 20.0,
                 ..default()
@@ -234,6 +926,24 @@ pub fn setup_game_over_menu_styled(
             Node {
                 position_type: PositionType::Absolute,
                 top: Val::Px(window_height * 0.30),
+                left: Val::Px(window_width * 0.5 - summary_offset),
+                ..default()
+            },
+            GameOverMenu,
+            MenuText3D,
+        ));
+
+        // Seed of the run, so it can be replayed
+        commands.spawn((
+            Text::new(format!("Seed: {}", rng.seed())),
+            TextFont {
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor::from(Color::srgb(2.0, 2.0, 2.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(window_height * 0.35),
                 left: Val::Px(window_width * 0.5 - 60.0),
                 ..default()
             },
@@ -241,10 +951,77 @@ pub fn setup_game_over_menu_styled(
             MenuText3D,
         ));
 
+        // After watching a replay, report whether it reproduced the recorded score
+        if let Some(verification) = verification {
+            let (message, color) = if verification.matches() {
+                (
+                    "Replay verified: score matches".to_string(),
+                    Color::srgb(1.5, 2.5, 1.5),
+                )
+            } else {
+                (
+                    format!("Replay diverged: recorded {}", verification.expected_score),
+                    Color::srgb(2.5, 1.5, 1.5),
+                )
+            };
+            commands.spawn((
+                Text::new(message),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor::from(color),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(window_height * 0.40),
+                    left: Val::Px(window_width * 0.5 - 130.0),
+                    ..default()
+                },
+                GameOverMenu,
+                MenuText3D,
+            ));
+        }
+
+        // A score that made its leaderboard asks for a name, above the buttons
+        let button_shift = if pending_high_score.is_some() {
+            -60.0
+        } else {
+            0.0
+        };
+        if let Some(pending_high_score) = pending_high_score {
+            commands.spawn((
+                Text::new(name_entry_label(&pending_high_score)),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor::from(Color::srgb(2.5, 2.5, 1.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(window_height * 0.40),
+                    left: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    ..default()
+                },
+                NameEntryText,
+                GameOverMenu,
+                MenuText3D,
+            ));
+        }
+
         // Create button mesh
         let button_mesh = meshes.add(create_button_outline_mesh(200.0, 50.0));
 
-        // Play Again button
+        // Play Again button, or on to the next level once this one is won
+        let next_level = campaign_run
+            .as_ref()
+            .filter(|_| cleared)
+            .and_then(|campaign_run| campaign.next_after(&campaign_run.level.id));
+        let (label, action) = match next_level {
+            Some(_) => ("NEXT LEVEL", MenuAction::NextLevel),
+            None => ("PLAY AGAIN", MenuAction::PlayAgain),
+        };
         spawn_button_with_text(
             &mut commands,
             &mut meshes,
@@ -253,17 +1030,42 @@ pub fn setup_game_over_menu_styled(
             window_height,
             button_mesh.clone(),
             ButtonWithText::new(
-                "PLAY AGAIN",
-                MenuAction::PlayAgain,
+                label,
+                action,
                 Vec2::new(200.0, 50.0),
                 Color::srgb(1.5, 2.5, 1.5),
                 24.0,
-                Vec3::new(0.0, 50.0, 0.0),
+                Vec3::new(0.0, 50.0 + button_shift, 0.0),
             ),
             GameOverMenu,
         );
 
-        // Main Menu button
+        // Watch Replay button, once a run has been recorded
+        if last_replay.0.is_some() {
+            spawn_button_with_text(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                window_width,
+                window_height,
+                button_mesh.clone(),
+                ButtonWithText::new(
+                    "WATCH REPLAY",
+                    MenuAction::WatchReplay,
+                    Vec2::new(200.0, 50.0),
+                    Color::srgb(2.5, 2.5, 1.5),
+                    24.0,
+                    Vec3::new(0.0, -20.0 + button_shift, 0.0),
+                ),
+                GameOverMenu,
+            );
+        }
+
+        // Main Menu button; campaign levels go back to the level list
+        let (label, action) = match campaign_run {
+            Some(_) => ("LEVELS", MenuAction::LevelSelect),
+            None => ("MAIN MENU", MenuAction::QuitToMenu),
+        };
         spawn_button_with_text(
             &mut commands,
             &mut meshes,
@@ -272,12 +1074,12 @@ pub fn setup_game_over_menu_styled(
             window_height,
             button_mesh.clone(),
             ButtonWithText::new(
-                "MAIN MENU",
-                MenuAction::QuitToMenu,
+                label,
+                action,
                 Vec2::new(200.0, 50.0),
                 Color::srgb(1.5, 1.5, 2.5),
                 24.0,
-                Vec3::new(0.0, -20.0, 0.0),
+                Vec3::new(0.0, -90.0 + button_shift, 0.0),
             ),
             GameOverMenu,
         );
@@ -296,9 +1098,194 @@ pub fn setup_game_over_menu_styled(
                 Vec2::new(200.0, 50.0),
                 Color::srgb(2.5, 1.5, 1.5),
                 24.0,
-                Vec3::new(0.0, -90.0, 0.0),
+                Vec3::new(0.0, -160.0 + button_shift, 0.0),
             ),
             GameOverMenu,
         );
     }
 }
+
+// Text for the seed field: the typed seed, "Random" when empty, and a cursor while editing
+pub fn seed_entry_label(seed_entry: &SeedEntry) -> String {
+    let seed = if seed_entry.text.is_empty() && !seed_entry.editing {
+        "Random"
+    } else {
+        seed_entry.text.as_str()
+    };
+    if seed_entry.editing {
+        format!("Seed: {}_", seed)
+    } else {
+        format!("Seed: {}", seed)
+    }
+}
+
+// Prompt for a high score's name, with a cursor
+pub fn name_entry_label(pending_high_score: &PendingHighScore) -> String {
+    format!(
+        "New high score! #{} on {}\nName: {}_   (Enter to save)",
+        pending_high_score.rank, pending_high_score.title, pending_high_score.entry.name
+    )
+}
+
+// One board as a table, best score first
+pub fn leaderboard_table(title: &str, entries: &[LeaderboardEntry]) -> String {
+    let mut table = format!("{}\n\n", title);
+    table.push_str(&format!(
+        "{:>3} {:<12} {:>8} {:>5} {:>6}  {}\n",
+        "#", "NAME", "SCORE", "COMBO", "TIME", "DATE"
+    ));
+    for (i, entry) in entries.iter().enumerate() {
+        table.push_str(&format!(
+            "{:>3} {:<12} {:>8} {:>5} {:>6}  {}\n",
+            i + 1,
+            entry.name,
+            entry.score,
+            entry.max_combo,
+            format_duration(entry.duration),
+            format_date(entry.date)
+        ));
+    }
+    table
+}
+
+// Lifetime stats as two columns: overall, shooting and kills on the left;
+// deaths, power-ups and time per difficulty on the right
+pub fn stats_tables(save_data: &SaveData, presets: &DifficultyPresets) -> (String, String) {
+    let stats = &save_data.stats;
+    let totals = &stats.totals;
+
+    let mut left = String::from("GENERAL\n");
+    left.push_str(&format!("  Games played   {:>8}\n", save_data.games_played));
+    left.push_str(&format!(
+        "  Play time      {:>8}\n",
+        format_play_time(save_data.total_play_time)
+    ));
+    left.push_str(&format!("  High score     {:>8}\n", save_data.high_score));
+    left.push_str(&format!("  Best combo     {:>8}\n", save_data.max_combo));
+    left.push_str("\nSHOOTING\n");
+    left.push_str(&format!("  Shots fired    {:>8}\n", totals.shots_fired));
+    left.push_str(&format!("  Shots hit      {:>8}\n", totals.shots_hit));
+    let accuracy = totals.accuracy().map_or("-".to_string(), |accuracy| {
+        format!("{:.1}%", accuracy * 100.0)
+    });
+    left.push_str(&format!("  Accuracy       {:>8}\n", accuracy));
+    push_breakdown(&mut left, "ASTEROIDS", &totals.asteroids);
+    push_breakdown(&mut left, "ENEMIES", &totals.enemies);
+    push_breakdown(&mut left, "BOSSES", &totals.bosses);
+    left.push_str(&format!("  Phases cleared {:>8}\n", totals.boss_phases));
+
+    let mut right = String::new();
+    push_breakdown(&mut right, "LIVES LOST", &totals.deaths);
+    push_breakdown(&mut right, "POWER-UPS", &totals.power_ups);
+    right.push_str("\nDIFFICULTIES      TIME  LONGEST\n");
+    if stats.difficulties.is_empty() {
+        right.push_str("  None yet\n");
+    }
+    for (difficulty, difficulty_stats) in &stats.difficulties {
+        right.push_str(&format!(
+            "  {:<12} {:>7} {:>8}\n",
+            presets.name(*difficulty),
+            format_play_time(difficulty_stats.play_time),
+            format_duration(difficulty_stats.longest_run)
+        ));
+    }
+    (left, right)
+}
+
+// A heading with its total, then one line per type
+fn push_breakdown(table: &mut String, heading: &str, counts: &BTreeMap<String, u32>) {
+    table.push_str(&format!("\n{:<16} {:>7}\n", heading, total(counts)));
+    for (name, count) in counts {
+        table.push_str(&format!("  {:<14} {:>7}\n", name, count));
+    }
+}
+
+// Hours and minutes once play time runs past an hour
+fn format_play_time(seconds: f32) -> String {
+    let minutes = seconds.max(0.0) as u32 / 60;
+    if minutes < 60 {
+        format_duration(seconds)
+    } else {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+// Every achievement with its description, and either the date it was
+// unlocked or its progress so far, split into two columns
+pub fn achievements_tables(achievements: &Achievements, save_data: &SaveData) -> (String, String) {
+    let entries: Vec<String> = achievements
+        .iter()
+        .map(|achievement| {
+            let status = match save_data.achievements.get(&achievement.id) {
+                Some(&date) => format_date(date),
+                None => achievement
+                    .progress(&save_data.stats)
+                    .map_or("locked".to_string(), |(count, needed)| {
+                        format!("{}/{}", count, needed)
+                    }),
+            };
+            let mark = if save_data.has_achievement(&achievement.id) {
+                "*"
+            } else {
+                " "
+            };
+            format!(
+                "{} {:<22} {:>10}\n    {}\n\n",
+                mark, achievement.name, status, achievement.description
+            )
+        })
+        .collect();
+    let (left, right) = entries.split_at(entries.len().div_ceil(2));
+    (left.concat(), right.concat())
+}
+
+// The run report as two columns: how the run went against the personal
+// best, shooting and lives on the left; what was destroyed and collected
+// on the right
+pub fn run_report_tables(report: &RunReport) -> (String, String) {
+    let stats = &report.stats;
+    let best = report.personal_best.as_ref();
+    let versus = |value: String, best_value: Option<String>| match best_value {
+        Some(best_value) => format!("{:>8}  best {}", value, best_value),
+        None => format!("{:>8}", value),
+    };
+
+    let mut left = String::from("RUN\n");
+    left.push_str(&format!(
+        "  Score          {}\n",
+        versus(
+            report.score.to_string(),
+            best.map(|best| best.score.to_string())
+        )
+    ));
+    left.push_str(&format!(
+        "  Time survived  {}\n",
+        versus(
+            format_duration(report.duration),
+            best.map(|best| format_duration(best.duration))
+        )
+    ));
+    left.push_str(&format!(
+        "  Max combo      {}\n",
+        versus(
+            report.max_combo.to_string(),
+            best.map(|best| best.max_combo.to_string())
+        )
+    ));
+    left.push_str("\nSHOOTING\n");
+    left.push_str(&format!("  Shots fired    {:>8}\n", stats.shots_fired));
+    left.push_str(&format!("  Shots hit      {:>8}\n", stats.shots_hit));
+    let accuracy = report.accuracy.map_or("-".to_string(), |accuracy| {
+        format!("{:.1}%", accuracy * 100.0)
+    });
+    left.push_str(&format!("  Accuracy       {:>8}\n", accuracy));
+    push_breakdown(&mut left, "LIVES LOST", &stats.deaths);
+
+    let mut right = String::new();
+    push_breakdown(&mut right, "ASTEROIDS", &stats.asteroids);
+    push_breakdown(&mut right, "ENEMIES", &stats.enemies);
+    push_breakdown(&mut right, "BOSSES", &stats.bosses);
+    right.push_str(&format!("  Phases cleared {:>7}\n", stats.boss_phases));
+    push_breakdown(&mut right, "POWER-UPS", &stats.power_ups);
+    (left, right)
+}
@@ -1,8 +1,14 @@
+pub mod achievements;
 pub mod bullet_lifecycle;
 pub mod button_helpers;
+pub mod campaign;
 pub mod cleanup;
 pub mod combat;
+pub mod controls;
+pub mod custom_difficulty;
+pub mod damage;
 pub mod enemy_boss;
+pub mod input;
 pub mod menu_interaction_styled;
 pub mod menu_setup_styled;
 pub mod pause;
@@ -11,16 +17,29 @@ pub mod player;
 pub mod player_collision;
 pub mod powerup_ui;
 pub mod powerups;
+pub mod replay;
 pub mod save_system;
 pub mod settings;
 pub mod setup;
 pub mod spawning;
+pub mod stats;
+pub mod stress_test;
+pub mod suspend;
+pub mod toast;
+pub mod tuning;
 pub mod ui;
+pub mod waves;
 
+pub use achievements::*;
 pub use bullet_lifecycle::*;
+pub use campaign::*;
 pub use cleanup::*;
 pub use combat::*;
+pub use controls::*;
+pub use custom_difficulty::*;
+pub use damage::*;
 pub use enemy_boss::*;
+pub use input::*;
 pub use menu_interaction_styled::*;
 pub use menu_setup_styled::*;
 pub use pause::*;
@@ -29,8 +48,15 @@ pub use player::*;
 pub use player_collision::*;
 pub use powerup_ui::*;
 pub use powerups::*;
+pub use replay::*;
 pub use save_system::*;
 pub use settings::*;
 pub use setup::*;
 pub use spawning::*;
+pub use stats::*;
+pub use stress_test::*;
+pub use suspend::*;
+pub use toast::*;
+pub use tuning::*;
 pub use ui::*;
+pub use waves::*;
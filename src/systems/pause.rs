@@ -10,6 +10,8 @@ pub fn setup_pause_menu(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     windows: Query<&Window>,
+    playback: Option<Res<ReplayPlayback>>,
+    stress_test: Option<Res<StressTest>>,
 ) {
     if let Ok(window) = windows.single() {
         let window_width = window.width();
@@ -25,9 +27,9 @@ pub fn setup_pause_menu(
 
         // Pause menu background
         commands.spawn((
-            Mesh2d(meshes.add(Rectangle::new(400.0, 300.0))),
+            Mesh2d(meshes.add(Rectangle::new(400.0, 360.0))),
             MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.0, 0.0, 0.0)))),
-            Transform::from_translation(Vec3::new(0.0, 0.0, 11.0)),
+            Transform::from_translation(Vec3::new(0.0, -30.0, 11.0)),
             PauseUI,
         ));
 
@@ -102,24 +104,56 @@ pub fn setup_pause_menu(
             ),
             PauseUI,
         );
+
+        // Save & Quit button; replays and stress-test scenes aren't runs to come back to
+        if playback.is_none() && stress_test.is_none() {
+            spawn_button_with_text(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                window_width,
+                window_height,
+                button_mesh.clone(),
+                ButtonWithText::new(
+                    "SAVE & QUIT",
+                    MenuAction::SaveAndQuit,
+                    Vec2::new(200.0, 50.0),
+                    Color::srgb(2.5, 2.0, 1.5),
+                    24.0,
+                    Vec3::new(0.0, -130.0, 12.0),
+                ),
+                PauseUI,
+            );
+        }
     }
 }
 
 // Pause input system
 pub fn pause_input_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    current_state: Res<State<GameState>>,
+    actions: Res<ActionState>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    current_pause_state: Res<State<PauseState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        match current_state.get() {
-            GameState::Playing => next_state.set(GameState::Paused),
-            GameState::Paused => next_state.set(GameState::Playing),
-            _ => {}
+    if actions.just_pressed(InputAction::Pause) {
+        match current_pause_state.get() {
+            PauseState::Running => next_pause_state.set(PauseState::Paused),
+            PauseState::Paused => next_pause_state.set(PauseState::Running),
+            PauseState::Settings => next_pause_state.set(PauseState::Paused), // Back to the pause menu
+            PauseState::Controls => next_pause_state.set(PauseState::Settings),
         }
     }
 }
 
+// Freeze virtual time so every timer (spawns, power-ups, invincibility) holds its value
+pub fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+// Resume virtual time when the run continues or is left from the pause menu
+pub fn resume_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
 // Cleanup pause menu
 pub fn cleanup_pause_menu(mut commands: Commands, query: Query<Entity, With<PauseUI>>) {
     for entity in query.iter() {
@@ -1,4 +1,5 @@
 use crate::components::*;
+use crate::resources::*;
 use bevy::prelude::*;
 
 pub fn move_entities(
@@ -17,23 +18,108 @@ pub fn rotate_entities(mut query: Query<(&mut Transform, &RotationVelocity)>, ti
     }
 }
 
-pub fn wrap_around(mut query: Query<&mut Transform, With<Wraparound>>, windows: Query<&Window>) {
+pub fn wrap_around(
+    mut query: Query<&mut Transform, With<Wraparound>>,
+    bounds: Res<PlayfieldBounds>,
+) {
+    let half_width = bounds.half_width();
+    let half_height = bounds.half_height();
+
+    for mut transform in query.iter_mut() {
+        if transform.translation.x > half_width {
+            transform.translation.x = -half_width;
+        } else if transform.translation.x < -half_width {
+            transform.translation.x = half_width;
+        }
+
+        if transform.translation.y > half_height {
+            transform.translation.y = -half_height;
+        } else if transform.translation.y < -half_height {
+            transform.translation.y = half_height;
+        }
+    }
+}
+
+// Rebuild the collision broad phase from this tick's positions
+pub fn rebuild_spatial_grid(
+    mut grid: ResMut<SpatialGrid>,
+    colliders: Query<(Entity, &Transform, &Collider)>,
+) {
+    grid.clear();
+
+    for (entity, transform, collider) in colliders.iter() {
+        grid.insert(entity, transform, collider);
+    }
+}
+
+// Keep the logical playfield in sync with the window when one exists. Runs
+// only between runs, so a resize never changes a run (or its replay) part-way.
+pub fn sync_playfield_bounds(windows: Query<&Window>, mut bounds: ResMut<PlayfieldBounds>) {
     if let Ok(window) = windows.single() {
-        let half_width = window.width() / 2.0;
-        let half_height = window.height() / 2.0;
-
-        for mut transform in query.iter_mut() {
-            if transform.translation.x > half_width {
-                transform.translation.x = -half_width;
-            } else if transform.translation.x < -half_width {
-                transform.translation.x = half_width;
-            }
-
-            if transform.translation.y > half_height {
-                transform.translation.y = -half_height;
-            } else if transform.translation.y < -half_height {
-                transform.translation.y = half_height;
-            }
+        let window_bounds = PlayfieldBounds::new(window.width(), window.height());
+        if *bounds != window_bounds {
+            *bounds = window_bounds;
+        }
+    }
+}
+
+// Put the simulated pose back before the fixed loop, undoing last frame's
+// blend and pulse
+pub fn restore_simulated_transforms(mut query: Query<(&mut Transform, &TransformHistory)>) {
+    for (mut transform, history) in query.iter_mut() {
+        (transform.translation, transform.rotation) = history.current;
+        transform.scale = history.scale;
+    }
+}
+
+// Remember where every moving entity starts the tick
+pub fn record_previous_transforms(mut query: Query<(&Transform, &mut TransformHistory)>) {
+    for (transform, mut history) in query.iter_mut() {
+        history.previous = (transform.translation, transform.rotation);
+    }
+}
+
+// Remember where every moving entity ends the tick; new entities start tracking here
+pub fn record_current_transforms(
+    mut commands: Commands,
+    mut tracked: Query<(&Transform, &mut TransformHistory)>,
+    untracked: Query<
+        (Entity, &Transform),
+        (
+            Or<(With<Velocity>, With<RotationVelocity>)>,
+            Without<TransformHistory>,
+        ),
+    >,
+) {
+    for (transform, mut history) in tracked.iter_mut() {
+        history.current = (transform.translation, transform.rotation);
+        history.scale = transform.scale;
+    }
+    for (entity, transform) in untracked.iter() {
+        commands
+            .entity(entity)
+            .insert(TransformHistory::new(transform));
+    }
+}
+
+// Blend rendered transforms between the last two ticks by the leftover frame time
+pub fn interpolate_transforms(
+    mut query: Query<(&mut Transform, &TransformHistory)>,
+    fixed_time: Res<Time<Fixed>>,
+    bounds: Res<PlayfieldBounds>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    // A jump this large is a screen wrap, not motion, so it is not smoothed
+    let max_step = bounds.half_width().min(bounds.half_height());
+
+    for (mut transform, history) in query.iter_mut() {
+        let (previous_translation, previous_rotation) = history.previous;
+        let (current_translation, current_rotation) = history.current;
+
+        if previous_translation.distance(current_translation) > max_step {
+            continue;
         }
+        transform.translation = previous_translation.lerp(current_translation, alpha);
+        transform.rotation = previous_rotation.slerp(current_rotation, alpha);
     }
 }
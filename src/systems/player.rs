@@ -2,42 +2,60 @@ use crate::components::*;
 use crate::constants::*;
 use crate::resources::*;
 use bevy::prelude::*;
+use std::f32::consts::{PI, TAU};
 
 pub fn player_movement(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input: Res<PlayerInput>,
     mut query: Query<(&mut Transform, &mut Velocity), With<Player>>,
     difficulty: Res<DifficultySettings>,
     time: Res<Time>,
 ) {
     for (mut transform, mut velocity) in query.iter_mut() {
         // Rotation controls
-        if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
+        if input.rotate_left {
             transform.rotate_z(PLAYER_ROTATION_SPEED * time.delta_secs());
         }
-        if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
+        if input.rotate_right {
             transform.rotate_z(-PLAYER_ROTATION_SPEED * time.delta_secs());
         }
+        if input.turn != 0 {
+            transform
+                .rotate_z(PLAYER_ROTATION_SPEED * input.turn as f32 / 127.0 * time.delta_secs());
+        }
+        // Direct aim: turn the short way round, without overshooting
+        if let Some(aim) = input.aim {
+            let forward = transform.up().truncate();
+            let heading = (-forward.x).atan2(forward.y);
+            let difference = (PlayerInput::aim_heading(aim) - heading + PI).rem_euclid(TAU) - PI;
+            let max_step = PLAYER_ROTATION_SPEED * time.delta_secs();
+            transform.rotate_z(difference.clamp(-max_step, max_step));
+        }
 
         // Forward thrust
-        if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
+        if input.thrust {
             // Get forward direction from rotation (up direction in ship's local space)
             let forward = transform.up().truncate();
             velocity.0 +=
                 forward * PLAYER_SPEED * difficulty.player_speed_multiplier * time.delta_secs();
         }
         // Backward thrust
-        if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
+        if input.reverse {
             // Get backward direction from rotation (down direction in ship's local space)
             let backward = -transform.up().truncate();
             velocity.0 +=
                 backward * PLAYER_SPEED * difficulty.player_speed_multiplier * time.delta_secs();
         }
 
-        // Apply friction
-        velocity.0 *= 0.995;
+        // Apply friction, scaled so the ship slows the same at any tick rate
+        velocity.0 *= PLAYER_FRICTION.powf(time.delta_secs() * 60.0);
 
         // Apply velocity
         transform.translation.x += velocity.0.x * time.delta_secs();
         transform.translation.y += velocity.0.y * time.delta_secs();
     }
 }
+
+// Reset the fire rate when starting a new game
+pub fn reset_fire_timer(mut fire_timer: ResMut<FireTimer>) {
+    *fire_timer = FireTimer::default();
+}
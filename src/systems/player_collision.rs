@@ -1,20 +1,25 @@
 use crate::components::*;
+use crate::events::*;
 use crate::resources::*;
 use bevy::prelude::*;
 
 pub fn player_asteroid_collision_system(
     mut commands: Commands,
     mut player_query: Query<
-        (Entity, &Transform, Option<&mut Invincibility>),
+        (Entity, &Transform, &Collider, Option<&mut Invincibility>),
         (With<Player>, Without<Asteroid>),
     >,
-    asteroids: Query<(Entity, &Transform, &Asteroid), (With<Asteroid>, Without<Player>)>,
+    asteroids: Query<(Entity, &Transform, &Collider), (With<Asteroid>, Without<Player>)>,
     mut player_lives: ResMut<PlayerLives>,
     score: Res<GameScore>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut hit_events: EventWriter<PlayerHit>,
+    mut life_events: EventWriter<LifeLost>,
     time: Res<Time>,
+    grid: Res<SpatialGrid>,
 ) {
-    if let Ok((player_entity, player_transform, mut invincibility_opt)) = player_query.single_mut()
+    if let Ok((player_entity, player_transform, player_collider, mut invincibility_opt)) =
+        player_query.single_mut()
     {
         // Update invincibility timer if active
         if let Some(ref mut invincibility) = invincibility_opt {
@@ -32,17 +37,40 @@ pub fn player_asteroid_collision_system(
             .map_or(false, |inv| inv.is_active());
 
         if !is_invincible {
-            for (asteroid_entity, asteroid_transform, asteroid) in asteroids.iter() {
-                // Collision threshold based on asteroid size + player size
-                let collision_radius = asteroid.size as f32 * 5.0 + 15.0; // asteroid radius + player buffer
+            for candidate in
+                grid.query_collider(player_transform, player_collider, CollisionLayers::ASTEROID)
+            {
+                let Ok((asteroid_entity, asteroid_transform, asteroid_collider)) =
+                    asteroids.get(candidate.entity)
+                else {
+                    continue;
+                };
 
-                let distance = player_transform
-                    .translation
-                    .distance(asteroid_transform.translation);
-
-                if distance < collision_radius {
+                // Ship hull against the asteroid's outline
+                if player_collider.intersects(
+                    player_transform,
+                    asteroid_collider,
+                    asteroid_transform,
+                ) {
                     // Player hit by asteroid
+                    let position = player_transform.translation;
+                    hit_events.write(PlayerHit {
+                        entity: player_entity,
+                        source: asteroid_entity,
+                        hazard: Hazard::Asteroid,
+                        position,
+                        score_delta: 0,
+                    });
+
                     let game_over = player_lives.lose_life();
+                    life_events.write(LifeLost {
+                        entity: player_entity,
+                        hazard: Hazard::Asteroid,
+                        lives_remaining: player_lives.lives,
+                        game_over,
+                        position,
+                        score_delta: 0,
+                    });
 
                     if game_over {
                         // Game over - despawn player and transition to game over state
@@ -1,6 +1,7 @@
 use crate::assets::*;
 use crate::components::*;
 use crate::constants::*;
+use crate::events::*;
 use crate::resources::*;
 use bevy::prelude::*;
 use std::f32::consts::PI;
@@ -9,10 +10,12 @@ use std::f32::consts::PI;
 pub fn reset_powerups_system(
     mut commands: Commands,
     mut player_powerups: ResMut<PlayerPowerUps>,
+    mut spawn_timer: ResMut<PowerUpSpawnTimer>,
     powerup_effects: Query<Entity, With<PowerUpEffect>>,
 ) {
     // Clear all active power-ups
     player_powerups.clear_all();
+    spawn_timer.timer.reset();
 
     // Remove all active power-up effect entities
     for entity in powerup_effects.iter() {
@@ -26,14 +29,21 @@ pub fn spawn_powerup_system(
     mut spawn_timer: ResMut<PowerUpSpawnTimer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rng: ResMut<GameRng>,
     time: Res<Time>,
 ) {
     spawn_timer.timer.tick(time.delta());
 
     if spawn_timer.timer.just_finished() {
         // Random chance to spawn a power-up (70% chance)
-        if fastrand::f32() < 0.7 {
-            spawn_random_powerup(&mut commands, &mut meshes, &mut materials, None);
+        if rng.gameplay.f32() < 0.7 {
+            spawn_random_powerup(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                &mut rng.gameplay,
+                None,
+            );
         }
     }
 }
@@ -42,6 +52,7 @@ pub fn spawn_random_powerup(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    rng: &mut fastrand::Rng,
     position: Option<Vec3>,
 ) {
     let power_types = [
@@ -52,24 +63,45 @@ pub fn spawn_random_powerup(
         PowerUpType::LaserBeam,
     ];
 
-    let random_type = power_types[fastrand::usize(..power_types.len())].clone();
+    let random_type = power_types[rng.usize(..power_types.len())].clone();
+    spawn_powerup(commands, meshes, materials, random_type, rng, position);
+}
 
+// Spawn a power-up of a given kind, at `position` or somewhere on screen
+pub fn spawn_powerup(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    power_type: PowerUpType,
+    rng: &mut fastrand::Rng,
+    position: Option<Vec3>,
+) {
     // Use provided position or random position on screen
     let (x, y) = if let Some(pos) = position {
         (pos.x, pos.y)
     } else {
-        ((fastrand::f32() - 0.5) * 760.0, (fastrand::f32() - 0.5) * 560.0)
+        ((rng.f32() - 0.5) * 760.0, (rng.f32() - 0.5) * 560.0)
     };
 
-    let (color, mesh) = get_powerup_visual(&random_type);
+    spawn_powerup_at(commands, meshes, materials, power_type, Vec2::new(x, y));
+}
+
+// Spawn a power-up of a given kind at `position`
+pub fn spawn_powerup_at(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    power_type: PowerUpType,
+    position: Vec2,
+) {
+    let (color, mesh) = get_powerup_visual(&power_type);
 
     commands.spawn((
         Mesh2d(meshes.add(mesh)),
         MeshMaterial2d(materials.add(ColorMaterial::from(color))),
-        Transform::from_translation(Vec3::new(x, y, 0.0)),
-        PowerUp {
-            power_type: random_type,
-        },
+        Transform::from_translation(position.extend(0.0)),
+        PowerUp { power_type },
+        PowerUp::collider(),
         RotationVelocity::new(2.0), // Slow rotation for visual appeal
         Wraparound,
     ));
@@ -87,24 +119,37 @@ fn get_powerup_visual(power_type: &PowerUpType) -> (Color, Mesh) {
 // System to handle power-up collection
 pub fn powerup_collection_system(
     mut commands: Commands,
-    powerups: Query<(Entity, &Transform, &PowerUp), With<PowerUp>>,
-    players: Query<&Transform, (With<Player>, Without<PowerUp>)>,
+    powerups: Query<(Entity, &Transform, &PowerUp, &Collider), With<PowerUp>>,
+    players: Query<(&Transform, &Collider), (With<Player>, Without<PowerUp>)>,
     mut player_powerups: ResMut<PlayerPowerUps>,
+    mut collected_events: EventWriter<PowerUpCollected>,
+    grid: Res<SpatialGrid>,
 ) {
-    for player_transform in players.iter() {
-        for (powerup_entity, powerup_transform, powerup) in powerups.iter() {
-            let distance = player_transform
-                .translation
-                .distance(powerup_transform.translation);
+    for (player_transform, player_collider) in players.iter() {
+        for candidate in
+            grid.query_collider(player_transform, player_collider, CollisionLayers::POWER_UP)
+        {
+            let Ok((powerup_entity, powerup_transform, powerup, powerup_collider)) =
+                powerups.get(candidate.entity)
+            else {
+                continue;
+            };
 
             // Collection radius - increased for bigger power-ups
-            if distance < 30.0 {
+            if player_collider.intersects(player_transform, powerup_collider, powerup_transform) {
                 // Add power-up effect to player
                 player_powerups.add_effect(powerup.power_type.clone());
 
                 // Spawn power-up effect component on player
                 commands.spawn(PowerUpEffect::new(powerup.power_type.clone(), 15.0)); // 15 second duration
 
+                collected_events.write(PowerUpCollected {
+                    entity: powerup_entity,
+                    power_type: powerup.power_type.clone(),
+                    position: powerup_transform.translation,
+                    score_delta: 0,
+                });
+
                 // Remove power-up from world
                 commands.entity(powerup_entity).despawn();
             }
@@ -116,7 +161,9 @@ pub fn powerup_collection_system(
 pub fn powerup_effect_system(
     mut commands: Commands,
     mut effects: Query<(Entity, &mut PowerUpEffect)>,
+    players: Query<&Transform, With<Player>>,
     mut player_powerups: ResMut<PlayerPowerUps>,
+    mut expired_events: EventWriter<PowerUpExpired>,
     time: Res<Time>,
 ) {
     for (entity, mut effect) in effects.iter_mut() {
@@ -126,6 +173,15 @@ pub fn powerup_effect_system(
             // Remove the effect from player
             player_powerups.remove_effect(&effect.power_type);
 
+            expired_events.write(PowerUpExpired {
+                entity,
+                power_type: effect.power_type.clone(),
+                position: players
+                    .single()
+                    .map_or(Vec3::ZERO, |transform| transform.translation),
+                score_delta: 0,
+            });
+
             // Remove the effect entity
             commands.entity(entity).despawn();
         }
@@ -135,7 +191,7 @@ pub fn powerup_effect_system(
 // Enhanced player shooting system with power-ups
 pub fn enhanced_player_shoot(
     mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input: Res<PlayerInput>,
     query: Query<&Transform, With<Player>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
@@ -143,6 +199,7 @@ pub fn enhanced_player_shoot(
     player_powerups: Res<PlayerPowerUps>,
     difficulty: Res<DifficultySettings>,
     time: Res<Time>,
+    mut shot_events: EventWriter<ShotFired>,
 ) {
     // Apply rapid fire effect to timer
     let fire_rate_multiplier = player_powerups.rapid_fire_multiplier;
@@ -150,7 +207,7 @@ pub fn enhanced_player_shoot(
         .timer
         .tick(time.delta().mul_f32(fire_rate_multiplier));
 
-    if keyboard_input.pressed(KeyCode::Space) && fire_timer.timer.just_finished() {
+    if input.fire && fire_timer.timer.just_finished() {
         for player_transform in query.iter() {
             let forward = player_transform.up().truncate();
 
@@ -184,6 +241,7 @@ pub fn enhanced_player_shoot(
                     )
                     .with_rotation(player_transform.rotation * Quat::from_rotation_z(angle_offset)),
                     Bullet,
+                    Bullet::collider(),
                     BulletLifecycle::new(10.0, 8.0),
                     Velocity(bullet_velocity),
                     Wraparound,
@@ -197,6 +255,12 @@ pub fn enhanced_player_shoot(
                 if player_powerups.has_explosive {
                     bullet_entity.insert(ExplosiveBullet::new(50.0, 3)); // 50 pixel radius, 3 damage
                 }
+
+                shot_events.write(ShotFired {
+                    entity: bullet_entity.id(),
+                    position: player_transform.translation + spawn_offset.extend(0.0),
+                    score_delta: 0,
+                });
             }
 
             // Handle laser beam separately - fire continuously while space is held
@@ -228,6 +292,8 @@ fn spawn_laser_beam(
 
     // Create laser mesh (elongated rectangle)
     let laser_mesh = Mesh::from(Rectangle::new(4.0, laser_length));
+    let laser = LaserBeam::new(50.0, laser_length, 4.0); // 50 DPS, 400 range, 4 width
+    let collider = laser.collider();
 
     commands.spawn((
         Mesh2d(meshes.add(laser_mesh)),
@@ -236,7 +302,8 @@ fn spawn_laser_beam(
             player_transform.translation + (forward * laser_length / 2.0).extend(0.0),
         )
         .with_rotation(player_transform.rotation),
-        LaserBeam::new(50.0, laser_length, 4.0), // 50 DPS, 400 range, 4 width
-        BulletLifecycle::new(0.05, 10.0),        // Very short lived (50ms)
+        laser,
+        collider,
+        BulletLifecycle::new(0.05, 10.0), // Very short lived (50ms)
     ));
 }
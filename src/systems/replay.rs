@@ -0,0 +1,329 @@
+use crate::components::*;
+use crate::headless::HeadlessRun;
+use crate::resources::*;
+use crate::systems::{cleanup_all_entities, start_campaign_level};
+use bevy::app::FixedMain;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+
+/// Most ticks simulated in one rendered frame while seeking
+const MAX_SEEK_TICKS_PER_FRAME: usize = 600;
+
+/// Seconds skipped by `[` and `]`
+const SEEK_STEP_SECONDS: f32 = 10.0;
+
+// Switch the next run over to a recorded replay, simulated at its recorded tick rate
+pub fn start_replay_playback(world: &mut World, replay: Replay) {
+    if !replay.matches_game_version() {
+        warn!(
+            "Replay was recorded by version {}, this is {}; it may play out differently",
+            replay.game_version, GAME_VERSION
+        );
+    }
+    if !replay.matches_tuning(loaded_tuning_fingerprint(world)) {
+        warn!("Replay was recorded with different tuning files; it may play out differently");
+    }
+
+    let mut fixed_time = world.resource_mut::<Time<Fixed>>();
+    let restore_timestep = fixed_time.timestep();
+    fixed_time.set_timestep(replay.timestep);
+
+    // Pinned until playback ends, so the window size can't change the run
+    world.insert_resource(replay.playfield);
+    world.insert_resource(replay.difficulty.clone());
+    match &replay.waves {
+        Some(path) => world.insert_resource(ActiveWaves { path: path.clone() }),
+        None => {
+            world.remove_resource::<ActiveWaves>();
+        }
+    }
+    // The level sets its own difficulty, so the recorded one goes back on after
+    world.remove_resource::<CampaignRun>();
+    if let Some(level) = &replay.level {
+        start_campaign_level(world, level);
+        world.insert_resource(replay.difficulty.clone());
+    }
+    world.insert_resource(ReplayPlayback::new(replay, restore_timestep));
+}
+
+/// `tuning_fingerprint` of the enemies, bosses and spawn tables in `world`
+pub fn loaded_tuning_fingerprint(world: &World) -> u64 {
+    tuning_fingerprint(
+        world.resource::<EnemyArchetypes>(),
+        world.get_resource::<BossDefinitions>(),
+        world.resource::<SpawnTables>(),
+    )
+}
+
+// Run condition for gameplay ticks: not paused by the menu or by replay
+// controls (a paused replay still simulates while it seeks), and not after a
+// game over has been decided, so the tick count never depends on frame timing.
+pub fn gameplay_running(
+    pause_state: Option<Res<State<PauseState>>>,
+    next_state: Res<NextState<GameState>>,
+    playback: Option<Res<ReplayPlayback>>,
+) -> bool {
+    let running = pause_state.is_some_and(|state| *state.get() == PauseState::Running);
+    let ending = !matches!(*next_state, NextState::Unchanged);
+    let held = playback.is_some_and(|playback| {
+        playback.is_finished() || (playback.paused && playback.seek_target.is_none())
+    });
+
+    running && !ending && !held
+}
+
+// Map the bound actions onto ship controls during live play
+pub fn read_player_input(actions: Res<ActionState>, mut input: ResMut<PlayerInput>) {
+    *input = PlayerInput {
+        rotate_left: actions.pressed(InputAction::RotateLeft),
+        rotate_right: actions.pressed(InputAction::RotateRight),
+        thrust: actions.pressed(InputAction::Thrust),
+        reverse: actions.pressed(InputAction::Reverse),
+        fire: actions.pressed(InputAction::Fire),
+        turn: PlayerInput::turn_from(actions.turn),
+        aim: actions.aim.map(PlayerInput::aim_from),
+    };
+}
+
+// Pause, speed and seek keys while watching a replay (P, F, [ and ] by default)
+pub fn replay_controls_system(
+    actions: Res<ActionState>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if actions.just_pressed(InputAction::ReplayPause) {
+        playback.paused = !playback.paused;
+    }
+    if actions.just_pressed(InputAction::ReplaySpeed) {
+        playback.cycle_speed();
+        // Faster virtual time means proportionally more fixed ticks per frame
+        time.set_relative_speed(playback.speed as f32);
+    }
+    if actions.just_pressed(InputAction::ReplaySeekForward) {
+        playback.seek_by(SEEK_STEP_SECONDS);
+    }
+    if actions.just_pressed(InputAction::ReplaySeekBack) {
+        playback.seek_by(-SEEK_STEP_SECONDS);
+    }
+}
+
+// Advance the run-relative clock used by enemy and boss timing
+pub fn tick_run_clock(mut run_clock: ResMut<RunClock>, time: Res<Time>) {
+    run_clock.tick(time.delta());
+}
+
+// Append this tick's controls to the recording
+pub fn record_replay_input(mut recorder: ResMut<ReplayRecorder>, input: Res<PlayerInput>) {
+    recorder.inputs.push(*input);
+}
+
+// Feed the recorded controls for this tick during playback
+pub fn apply_replay_input(mut playback: ResMut<ReplayPlayback>, mut input: ResMut<PlayerInput>) {
+    *input = playback.next_input().unwrap_or_default();
+}
+
+// Start a fresh recording, or rewind playback, whenever a run begins
+pub fn begin_replay_run(
+    mut commands: Commands,
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    hud_query: Query<(), With<ReplayHudText>>,
+    archetypes: Res<EnemyArchetypes>,
+    bosses: Option<Res<BossDefinitions>>,
+    spawn_tables: Res<SpawnTables>,
+) {
+    let Some(mut playback) = playback else {
+        recorder.inputs.clear();
+        recorder.interrupted = false;
+        recorder.tuning = tuning_fingerprint(&archetypes, bosses.as_deref(), &spawn_tables);
+        return;
+    };
+
+    playback.cursor = 0;
+
+    // Seeking backwards restarts the run but keeps the existing status line
+    if !hud_query.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(2.0, 2.0, 1.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            ..default()
+        },
+        ReplayHudText,
+        GameUI,
+    ));
+}
+
+// Keep the playback status line current
+pub fn update_replay_hud(
+    playback: Res<ReplayPlayback>,
+    mut query: Query<&mut Text, With<ReplayHudText>>,
+) {
+    for mut text in query.iter_mut() {
+        let status = if playback.paused {
+            "PAUSED".to_string()
+        } else {
+            format!("x{}", playback.speed)
+        };
+        text.0 = format!(
+            "REPLAY {:.1}s / {:.1}s  {}   [P] Pause  [F] Speed  [ [ / ] ] Seek",
+            playback.elapsed().as_secs_f32(),
+            playback.replay.duration().as_secs_f32(),
+            status
+        );
+    }
+}
+
+// A recording that stopped without a game over (e.g. the run was quit) ends here
+pub fn end_finished_replay(
+    playback: Res<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if playback.is_finished() && matches!(*next_state, NextState::Unchanged) {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+// Jump to the seek target before the regular fixed ticks of this frame run.
+// Seeking backwards rebuilds the run from its start and fast-forwards.
+pub fn replay_seek_system(world: &mut World) {
+    if world.resource::<ReplayPlayback>().restart_requested {
+        world.resource_mut::<ReplayPlayback>().restart_requested = false;
+        if let Err(error) = world.run_system_once(cleanup_all_entities) {
+            warn!("Failed to restart replay: {}", error);
+        }
+        world.run_schedule(OnEnter(GameState::Playing));
+    }
+
+    let Some(target) = world.resource::<ReplayPlayback>().seek_target else {
+        return;
+    };
+
+    let mut stalled = false;
+    for _ in 0..MAX_SEEK_TICKS_PER_FRAME {
+        let cursor = world.resource::<ReplayPlayback>().cursor;
+        if cursor >= target {
+            break;
+        }
+        if !world.run_system_once(gameplay_running).unwrap_or(false) {
+            stalled = true;
+            break;
+        }
+
+        run_fixed_tick(world);
+
+        // Nothing was simulated (e.g. the run just ended): stop seeking
+        if world.resource::<ReplayPlayback>().cursor == cursor {
+            stalled = true;
+            break;
+        }
+    }
+
+    let mut playback = world.resource_mut::<ReplayPlayback>();
+    if stalled || playback.cursor >= target {
+        playback.seek_target = None;
+    }
+}
+
+// Run one tick of the fixed schedule outside the regular fixed loop
+fn run_fixed_tick(world: &mut World) {
+    let timestep = world.resource::<Time<Fixed>>().timestep();
+    world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+
+    let fixed_time = world.resource::<Time<Fixed>>().as_generic();
+    *world.resource_mut::<Time>() = fixed_time;
+    world.run_schedule(FixedMain);
+
+    let virtual_time = world.resource::<Time<Virtual>>().as_generic();
+    *world.resource_mut::<Time>() = virtual_time;
+}
+
+// When a run ends, check a played-back score or store the new recording
+pub fn finish_replay_run(
+    mut commands: Commands,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut last_replay: ResMut<LastReplay>,
+    playback: Option<Res<ReplayPlayback>>,
+    hud_query: Query<Entity, With<ReplayHudText>>,
+    headless: Option<Res<HeadlessRun>>,
+    stress_test: Option<Res<StressTest>>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    rng: Res<GameRng>,
+    bounds: Res<PlayfieldBounds>,
+    difficulty: Res<DifficultySettings>,
+    active_waves: Option<Res<ActiveWaves>>,
+    campaign_run: Option<Res<CampaignRun>>,
+    score: Res<GameScore>,
+) {
+    if let Some(playback) = playback {
+        let verification = ReplayVerification {
+            expected_score: playback.replay.final_score,
+            actual_score: score.score,
+        };
+        if verification.matches() {
+            info!("Replay verified: final score {}", score.score);
+        } else {
+            warn!(
+                "Replay diverged: recorded score {}, replayed score {}",
+                verification.expected_score, verification.actual_score
+            );
+        }
+
+        for entity in hud_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        fixed_time.set_timestep(playback.restore_timestep);
+        virtual_time.set_relative_speed(1.0);
+        commands.insert_resource(verification);
+        commands.remove_resource::<ReplayPlayback>();
+        return;
+    }
+
+    commands.remove_resource::<ReplayVerification>();
+
+    if recorder.interrupted {
+        recorder.inputs.clear();
+        last_replay.0 = None;
+        return;
+    }
+
+    let replay = Replay {
+        game_version: GAME_VERSION.to_string(),
+        tuning: recorder.tuning,
+        seed: rng.seed(),
+        timestep: fixed_time.timestep(),
+        playfield: *bounds,
+        difficulty: difficulty.clone(),
+        waves: active_waves.map(|active_waves| active_waves.path.clone()),
+        level: campaign_run.map(|campaign_run| campaign_run.level.id.clone()),
+        final_score: score.score,
+        inputs: std::mem::take(&mut recorder.inputs),
+    };
+
+    // Headless runs only write a file when asked to with `--record`, and
+    // stress-test scenes are not real runs
+    let path = recorder
+        .output_path
+        .clone()
+        .or_else(|| headless.is_none().then(|| default_replay_path(replay.seed)))
+        .filter(|_| stress_test.is_none());
+    if let Some(path) = path {
+        match replay.save(&path) {
+            Ok(()) => info!("Saved replay to {}", path.display()),
+            Err(error) => warn!("Failed to save replay {}: {}", path.display(), error),
+        }
+    }
+
+    last_replay.0 = Some(replay);
+}
@@ -1,62 +1,169 @@
+use crate::events::*;
 use crate::resources::*;
 use bevy::core_pipeline::bloom::Bloom;
 use bevy::prelude::*;
 
 // System to save game progress
 pub fn save_game_progress(
+    mut commands: Commands,
     mut save_data: ResMut<SaveData>,
     game_score: Res<GameScore>,
     time: Res<Time>,
 ) {
-    // Update high score if current score is higher
+    // Update high score and best combo if this run beat them
     save_data.update_high_score(game_score.score);
+    save_data.update_max_combo(game_score.max_combo);
 
     // Add to total play time
     save_data.add_play_time(time.delta_secs());
 
     // Save periodically (every 10 seconds)
     if time.elapsed_secs() % 10.0 < time.delta_secs() {
-        save_data.save();
+        report_save_error(&mut commands, SaveData::FILE_NAME, save_data.save());
     }
 }
 
-// System to track game statistics
-#[allow(dead_code)]
+// Move what the run has tallied since the last frame into the lifetime stats
 pub fn track_game_stats(
-    _save_data: ResMut<SaveData>,
-    _commands: Commands,
-    // Track when asteroids are destroyed
-    _asteroid_query: Query<
-        Entity,
-        (
-            With<crate::components::Asteroid>,
-            With<crate::components::Health>,
-        ),
-    >,
-    // Track when power-ups are collected
-    _powerup_events: EventReader<PowerUpCollectedEvent>,
+    mut save_data: ResMut<SaveData>,
+    mut run_stats: ResMut<RunStats>,
+    difficulty: Res<DifficultySettings>,
+    run_clock: Res<RunClock>,
 ) {
-    // This system would need to be integrated with existing collision systems
-    // to properly track when asteroids are destroyed and power-ups collected
+    let (totals, seconds) = run_stats.take_unsaved();
+    save_data.add_stats(&totals);
+    if seconds > 0.0 {
+        save_data
+            .stats
+            .add_play_time(difficulty.id, seconds, run_clock.elapsed_secs());
+    }
 }
 
-// Event for tracking power-up collection
-#[allow(dead_code)]
-#[derive(Event)]
-pub struct PowerUpCollectedEvent;
-
 // System to initialize save data on game start
 #[allow(dead_code)]
 pub fn initialize_save_system(mut commands: Commands) {
-    let save_data = SaveData::load();
+    let (save_data, errors) = SaveData::load();
     commands.insert_resource(save_data);
+    commands.insert_resource(StorageErrors(errors));
+}
+
+// Show whatever went wrong loading the save and settings files
+pub fn report_storage_errors(
+    mut storage_errors: ResMut<StorageErrors>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for message in storage_errors.0.drain(..) {
+        error!("{}", message);
+        toasts.write(ShowToast::error(message));
+    }
+}
+
+// A failed save is logged and shown; the game carries on with what it has
+// in memory and tries again on the next save
+pub fn report_save_error(
+    commands: &mut Commands,
+    file_name: &str,
+    result: Result<(), StorageError>,
+) {
+    if let Err(error) = result {
+        let message = format!("Could not save {}: {}", file_name, error);
+        error!("{}", message);
+        commands.send_event(ShowToast::error(message));
+    }
 }
 
 // System to save on game over
-pub fn save_on_game_over(mut save_data: ResMut<SaveData>, game_score: Res<GameScore>) {
+pub fn save_on_game_over(
+    mut commands: Commands,
+    mut save_data: ResMut<SaveData>,
+    game_score: Res<GameScore>,
+) {
     save_data.update_high_score(game_score.score);
+    save_data.update_max_combo(game_score.max_combo);
     save_data.increment_games_played();
-    save_data.save();
+    report_save_error(&mut commands, SaveData::FILE_NAME, save_data.save());
+}
+
+// Keep the stars and best score of a completed campaign level
+pub fn record_campaign_result(
+    mut save_data: ResMut<SaveData>,
+    campaign_run: Option<Res<CampaignRun>>,
+    game_score: Res<GameScore>,
+) {
+    if let Some(campaign_run) = campaign_run
+        && campaign_run.status == ObjectiveStatus::Complete
+    {
+        let stars = campaign_run
+            .level
+            .stars(game_score.score, campaign_run.hits);
+        save_data.record_level(&campaign_run.level.id, stars, game_score.score);
+    }
+}
+
+// Offer a place on the run's leaderboard when its score makes the top ten
+pub fn check_leaderboard(
+    mut commands: Commands,
+    save_data: Res<SaveData>,
+    game_score: Res<GameScore>,
+    run_clock: Res<RunClock>,
+    rng: Res<GameRng>,
+    difficulty: Res<DifficultySettings>,
+    presets: Res<DifficultyPresets>,
+    campaign: Res<Campaign>,
+    campaign_run: Option<Res<CampaignRun>>,
+    active_waves: Option<Res<ActiveWaves>>,
+) {
+    let board = LeaderboardId::for_run(
+        campaign_run.as_deref(),
+        active_waves.as_deref(),
+        difficulty.id,
+    );
+    let key = board.key();
+    let Some(rank) = save_data.leaderboard_rank(&key, game_score.score) else {
+        return;
+    };
+
+    commands.insert_resource(PendingHighScore {
+        board: key,
+        title: board.title(&presets, &campaign),
+        entry: LeaderboardEntry {
+            name: save_data.player_name.clone(),
+            score: game_score.score,
+            date: unix_now(),
+            duration: run_clock.elapsed_secs(),
+            seed: rng.seed(),
+            max_combo: game_score.max_combo,
+        },
+        rank,
+    });
+}
+
+// A high score left unnamed is kept with the name as typed
+pub fn save_pending_high_score(
+    mut commands: Commands,
+    mut save_data: ResMut<SaveData>,
+    pending: Option<Res<PendingHighScore>>,
+) {
+    if let Some(pending) = pending {
+        let result = save_high_score(&mut save_data, &pending);
+        report_save_error(&mut commands, SaveData::FILE_NAME, result);
+        commands.remove_resource::<PendingHighScore>();
+    }
+}
+
+// Put a named high score on its board and remember the name for next time
+pub fn save_high_score(
+    save_data: &mut SaveData,
+    pending: &PendingHighScore,
+) -> Result<(), StorageError> {
+    let mut entry = pending.entry.clone();
+    entry.name = entry.name.trim().to_string();
+    if entry.name.is_empty() {
+        entry.name = "PLAYER".to_string();
+    }
+    save_data.player_name = entry.name.clone();
+    save_data.add_leaderboard_entry(&pending.board, entry);
+    save_data.save()
 }
 
 // System to apply graphics settings
@@ -111,6 +111,25 @@ pub fn setup_settings_menu(
             MenuUI,
         );
 
+        // Controls page button
+        spawn_button_with_text(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            window_width,
+            window_height,
+            button_mesh.clone(),
+            ButtonWithText::new(
+                "CONTROLS",
+                MenuAction::Controls,
+                Vec2::new(200.0, 50.0),
+                Color::srgb(2.5, 2.0, 1.5),
+                24.0,
+                Vec3::new(0.0, -140.0, 11.0),
+            ),
+            MenuUI,
+        );
+
         // Back button
         spawn_button_with_text(
             &mut commands,
@@ -125,7 +144,7 @@ pub fn setup_settings_menu(
                 Vec2::new(200.0, 50.0),
                 Color::srgb(2.5, 1.5, 1.5),
                 24.0,
-                Vec3::new(0.0, -150.0, 11.0),
+                Vec3::new(0.0, -210.0, 11.0),
             ),
             MenuUI,
         );
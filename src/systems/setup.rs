@@ -1,5 +1,6 @@
 use crate::assets::*;
 use crate::components::*;
+use crate::events::*;
 use crate::resources::*;
 use bevy::{
     core_pipeline::{bloom::Bloom, tonemapping::Tonemapping},
@@ -24,7 +25,7 @@ pub fn setup_game(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    windows: Query<&Window>,
+    bounds: Res<PlayfieldBounds>,
 ) {
     // Spawn player with triangle mesh
     commands.spawn((
@@ -32,6 +33,7 @@ pub fn setup_game(
         MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(2.0, 2.0, 2.0)))), // Bright white for slight bloom
         Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
         Player,
+        Player::collider(),
         Velocity(Vec2::ZERO),
         Wraparound,
     ));
@@ -62,28 +64,65 @@ pub fn setup_game(
         GameUI,
     ));
 
-    // Get window dimensions for heart positioning
-    if let Ok(window) = windows.single() {
-        let window_width = window.width();
+    // Spawn the combo counter and its decay bar, hidden until the first kill
+    commands.spawn((
+        Text::new(""),
+        TextColor(Color::srgb(2.5, 2.0, 1.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(70.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        ComboText,
+        GameUI,
+    ));
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(98.0),
+                left: Val::Px(10.0),
+                width: Val::Px(120.0),
+                height: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.8)),
+            Visibility::Hidden,
+            ComboBar,
+            GameUI,
+        ))
+        .with_child((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(2.5, 2.0, 1.0)),
+            ComboBarFill,
+        ));
 
-        // Create heart mesh and material
-        let heart_mesh = meshes.add(create_heart_mesh());
-        let heart_material = materials.add(ColorMaterial::from(Color::srgb(2.0, 0.3, 0.3))); // Bright red for hearts
+    // Get playfield dimensions for heart positioning
+    let playfield_width = bounds.width;
 
-        // Spawn 3 hearts in the top right corner
-        for i in 0..3 {
-            let heart_x = window_width - 60.0 - (i as f32 * 40.0); // Position from right edge
-            let heart_y = 280.0; // Near top of screen (adjust based on your window height)
+    // Create heart mesh and material
+    let heart_mesh = meshes.add(create_heart_mesh());
+    let heart_material = materials.add(ColorMaterial::from(Color::srgb(2.0, 0.3, 0.3))); // Bright red for hearts
 
-            commands.spawn((
-                Mesh2d(heart_mesh.clone()),
-                MeshMaterial2d(heart_material.clone()),
-                Transform::from_translation(Vec3::new(heart_x, heart_y, 1.0))
-                    .with_scale(Vec3::splat(1.5)), // Scale up the hearts
-                HeartUI { heart_index: i },
-                GameUI,
-            ));
-        }
+    // Spawn 3 hearts in the top right corner
+    for i in 0..3 {
+        let heart_x = playfield_width - 60.0 - (i as f32 * 40.0); // Position from right edge
+        let heart_y = 280.0; // Near top of screen (adjust based on your window height)
+
+        commands.spawn((
+            Mesh2d(heart_mesh.clone()),
+            MeshMaterial2d(heart_material.clone()),
+            Transform::from_translation(Vec3::new(heart_x, heart_y, 1.0))
+                .with_scale(Vec3::splat(1.5)), // Scale up the hearts
+            HeartUI { heart_index: i },
+            GameUI,
+        ));
     }
 }
 
@@ -95,7 +134,7 @@ pub fn cleanup_all_entities(
             With<Player>,
             With<Asteroid>,
             With<Bullet>,
-            With<ScoreText>,
+            Or<(With<ScoreText>, With<ComboText>, With<ComboBar>)>,
             With<LivesText>,
             With<HeartUI>,
             With<PowerUp>,         // Add power-ups to cleanup
@@ -116,30 +155,47 @@ pub fn cleanup_all_entities(
 }
 
 pub fn reset_game_resources(
+    mut commands: Commands,
     mut score: ResMut<GameScore>,
     mut lives: ResMut<PlayerLives>,
     mut spawn_timer: ResMut<AsteroidSpawnTimer>,
-    mut fire_timer: ResMut<FireTimer>,
-    mut player_powerups: ResMut<PlayerPowerUps>, // Add powerup resource reset
-    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>, // Add enemy spawn timer reset
-    mut boss_spawn_manager: ResMut<BossSpawnManager>, // Add boss spawn manager reset
     difficulty: Res<DifficultySettings>,
+    mut rng: ResMut<GameRng>,
+    mut run_clock: ResMut<RunClock>,
+    playback: Option<Res<ReplayPlayback>>,
 ) {
-    // Reset score and lives
-    score.score = 0;
-    *lives = PlayerLives::default();
-
-    // Reset powerups
-    player_powerups.clear_all();
-
-    // Reset enemy spawn timer
-    enemy_spawn_timer.last_spawn_score = 0;
+    // Seed this run's random streams; a replay reuses its recorded seed
+    match playback {
+        Some(playback) => rng.reseed(playback.replay.seed),
+        None => rng.start_run(),
+    }
+    run_clock.reset();
+    commands.remove_resource::<RunVerdict>();
 
-    // Reset boss spawn manager
-    boss_spawn_manager.bosses_spawned.clear();
-    boss_spawn_manager.active_boss = None;
+    // Reset score, combo and lives
+    score.reset();
+    *lives = PlayerLives::default();
 
     // Reset timers with difficulty settings
     spawn_timer.timer = Timer::from_seconds(difficulty.asteroid_spawn_rate, TimerMode::Repeating);
-    fire_timer.timer = Timer::from_seconds(0.05, TimerMode::Repeating);
+}
+
+// Announce the new run once the ship is spawned and the seed is chosen
+pub fn announce_run_started(
+    players: Query<(Entity, &Transform), With<Player>>,
+    difficulty: Res<DifficultySettings>,
+    rng: Res<GameRng>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut run_events: EventWriter<RunStarted>,
+) {
+    for (entity, transform) in players.iter() {
+        run_events.write(RunStarted {
+            entity,
+            difficulty: difficulty.id,
+            seed: rng.seed(),
+            replay: playback.is_some(),
+            position: transform.translation,
+            score_delta: 0,
+        });
+    }
 }
@@ -0,0 +1,118 @@
+use crate::components::Boss;
+use crate::events::*;
+use crate::resources::*;
+use bevy::prelude::*;
+
+// Start every run's tally from zero, including replays and seeks
+pub fn reset_run_stats(mut run_stats: ResMut<RunStats>) {
+    run_stats.reset();
+}
+
+// Tally the run from this tick's gameplay events. Lasers don't fire shots,
+// so their damage doesn't count towards accuracy.
+pub fn tally_run_stats(
+    mut run_stats: ResMut<RunStats>,
+    time: Res<Time>,
+    mut shots: EventReader<ShotFired>,
+    mut damage: EventReader<DamageEvent>,
+    mut asteroids: EventReader<AsteroidDestroyed>,
+    mut enemies: EventReader<EnemyKilled>,
+    mut boss_phases: EventReader<BossPhaseChanged>,
+    mut bosses: EventReader<BossDefeated>,
+    mut lives_lost: EventReader<LifeLost>,
+    mut power_ups: EventReader<PowerUpCollected>,
+    mut player_hits: EventReader<PlayerHit>,
+    new_bosses: Query<(), Added<Boss>>,
+) {
+    run_stats.unsaved_time += time.delta_secs();
+
+    let fired = shots.read().count() as u32;
+    run_stats.record(|totals| totals.shots_fired += fired);
+    for event in damage.read() {
+        if let Some(source) = event.source
+            && event.kind != DamageKind::Laser
+        {
+            run_stats.record_hit(source);
+        }
+    }
+    for event in asteroids.read() {
+        run_stats.record(|totals| totals.record_asteroid(&event.asteroid_type));
+    }
+    for event in enemies.read() {
+        run_stats.record(|totals| totals.record_enemy(&event.enemy_type));
+    }
+    // Hits in the tick a phase ends count against it
+    if player_hits.read().count() > 0 {
+        run_stats.record_player_hit();
+    }
+    if !new_bosses.is_empty() {
+        run_stats.start_boss_phase();
+    }
+    for _ in boss_phases.read() {
+        run_stats.record(StatTotals::record_boss_phase);
+        run_stats.finish_boss_phase();
+    }
+    for event in bosses.read() {
+        run_stats.record(|totals| totals.record_boss(&event.boss_type));
+        run_stats.finish_boss_phase();
+    }
+    for event in lives_lost.read() {
+        run_stats.record(|totals| totals.record_death(event.hazard));
+    }
+    for event in power_ups.read() {
+        run_stats.record(|totals| totals.record_power_up(&event.power_type));
+    }
+}
+
+// Put the report on the run together for the game-over screen, before its
+// score can be added to its leaderboard
+pub fn build_run_report(
+    mut commands: Commands,
+    run_stats: Res<RunStats>,
+    game_score: Res<GameScore>,
+    run_clock: Res<RunClock>,
+    rng: Res<GameRng>,
+    difficulty: Res<DifficultySettings>,
+    presets: Res<DifficultyPresets>,
+    campaign: Res<Campaign>,
+    save_data: Res<SaveData>,
+    campaign_run: Option<Res<CampaignRun>>,
+    active_waves: Option<Res<ActiveWaves>>,
+) {
+    let board = LeaderboardId::for_run(
+        campaign_run.as_deref(),
+        active_waves.as_deref(),
+        difficulty.id,
+    );
+    commands.insert_resource(RunReport {
+        date: unix_now(),
+        personal_best: save_data.leaderboard(&board.key()).first().cloned(),
+        board: board.title(&presets, &campaign),
+        difficulty: difficulty.name.clone(),
+        seed: rng.seed(),
+        score: game_score.score,
+        max_combo: game_score.max_combo,
+        duration: run_clock.elapsed_secs(),
+        accuracy: run_stats.totals.accuracy(),
+        stats: run_stats.totals.clone(),
+    });
+}
+
+// Write the run report next to the save file
+pub fn export_run_report(world: &mut World) {
+    let Some(report) = world.get_resource::<RunReport>() else {
+        return;
+    };
+    let toast = match report.save() {
+        Ok(()) => ShowToast::info(format!(
+            "Report saved to {}",
+            data_dir().join(RunReport::FILE_NAME).display()
+        )),
+        Err(error) => {
+            let message = format!("Could not save {}: {}", RunReport::FILE_NAME, error);
+            error!("{}", message);
+            ShowToast::error(message)
+        }
+    };
+    world.send_event(toast);
+}
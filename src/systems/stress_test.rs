@@ -0,0 +1,99 @@
+use crate::assets::*;
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::*;
+use bevy::prelude::*;
+use std::time::Instant;
+
+// Top the scene back up to the requested asteroid and bullet counts. The
+// player is kept invincible so the run lasts as long as the benchmark.
+pub fn maintain_stress_population(
+    mut commands: Commands,
+    stress_test: Res<StressTest>,
+    asteroids: Query<(), With<Asteroid>>,
+    bullets: Query<(), With<Bullet>>,
+    players: Query<Entity, (With<Player>, Without<Invincibility>)>,
+    bounds: Res<PlayfieldBounds>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rng: ResMut<GameRng>,
+) {
+    for player_entity in players.iter() {
+        commands
+            .entity(player_entity)
+            .insert(Invincibility::new(1.0e6));
+    }
+
+    for _ in asteroids.iter().count()..stress_test.asteroid_count {
+        let position = random_position(&bounds, &mut rng.gameplay);
+        let angle = rng.gameplay.f32() * std::f32::consts::TAU;
+        let velocity = Vec2::from_angle(angle) * ASTEROID_SPEED * (0.5 + rng.gameplay.f32());
+        let size = rng.gameplay.u32(1..=4);
+
+        let asteroid = Asteroid::new(size, AsteroidType::Normal);
+        let color = asteroid.get_color();
+        let health = asteroid.max_health();
+        let collider = asteroid.collider();
+
+        commands.spawn((
+            Mesh2d(meshes.add(create_asteroid_mesh(
+                size,
+                size as f32 * 5.0,
+                &mut rng.cosmetic,
+            ))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(color))),
+            Transform::from_translation(position.extend(0.0)),
+            asteroid,
+            collider,
+            Health::new(health),
+            Velocity(velocity),
+            RotationVelocity::random_slow(&mut rng.cosmetic),
+            Wraparound,
+        ));
+    }
+
+    for _ in bullets.iter().count()..stress_test.bullet_count {
+        let position = random_position(&bounds, &mut rng.gameplay);
+        let direction = Vec2::from_angle(rng.gameplay.f32() * std::f32::consts::TAU);
+
+        commands.spawn((
+            Mesh2d(meshes.add(create_bullet_arrow_mesh())),
+            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(8.0, 8.0, 2.0)))),
+            Transform::from_translation(position.extend(0.0))
+                .with_rotation(Quat::from_rotation_arc_2d(Vec2::Y, direction)),
+            Bullet,
+            Bullet::collider(),
+            BulletLifecycle::new(2.0, 8.0),
+            Velocity(direction * BULLET_SPEED),
+            Wraparound,
+        ));
+    }
+}
+
+fn random_position(bounds: &PlayfieldBounds, rng: &mut fastrand::Rng) -> Vec2 {
+    Vec2::new(
+        (rng.f32() - 0.5) * bounds.width,
+        (rng.f32() - 0.5) * bounds.height,
+    )
+}
+
+// Start timing a gameplay tick
+pub fn begin_stress_tick(mut stress_test: ResMut<StressTest>) {
+    stress_test.tick_started = Some(Instant::now());
+}
+
+// Finish timing a gameplay tick and print a report every few seconds
+pub fn end_stress_tick(mut stress_test: ResMut<StressTest>, grid: Res<SpatialGrid>) {
+    if let Some(started) = stress_test.tick_started.take() {
+        stress_test.record_tick(started.elapsed());
+    }
+
+    if let Some((average, peak)) = stress_test.take_report() {
+        println!(
+            "Stress test: {} entities in the collision grid, tick avg {:.2} ms, peak {:.2} ms",
+            grid.entry_count(),
+            average.as_secs_f64() * 1000.0,
+            peak.as_secs_f64() * 1000.0
+        );
+    }
+}
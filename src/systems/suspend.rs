@@ -0,0 +1,568 @@
+use crate::assets::*;
+use crate::components::*;
+use crate::events::*;
+use crate::resources::*;
+use crate::systems::{spawn_asteroid_entity, spawn_powerup_at, start_campaign_level};
+use bevy::prelude::*;
+
+// Save the run in progress and leave for the main menu. If the file can't be
+// written the run carries on, so nothing is lost.
+pub fn suspend_run(world: &mut World) {
+    let snapshot = capture_run(world);
+    if let Err(error) = snapshot.save() {
+        let message = format!("Could not save {}: {}", RunSnapshot::FILE_NAME, error);
+        error!("{}", message);
+        world.send_event(ShowToast::error(message));
+        return;
+    }
+
+    // The replay of a suspended run would be missing its ending
+    world.resource_mut::<ReplayRecorder>().interrupted = true;
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::MainMenu);
+}
+
+// Set up the suspended run's mode and start it; the snapshot is applied once
+// the run's entities exist. The file goes, so a run can only be continued once.
+pub fn continue_suspended_run(world: &mut World) {
+    let path = data_dir().join(RunSnapshot::FILE_NAME);
+    let snapshot = match RunSnapshot::read_from(&path) {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => return,
+        Err(error) => {
+            let message = format!("Could not continue the saved run: {}", error);
+            error!("{}", message);
+            world.send_event(ShowToast::error(message));
+            if let Err(error) = remove_snapshot_file(&path) {
+                error!("Could not delete {}: {}", path.display(), error);
+            }
+            return;
+        }
+    };
+    // A snapshot that can't be deleted would be continued again next time
+    if let Err(error) = remove_snapshot_file(&path) {
+        let message = format!("Could not continue the saved run: {}", error);
+        error!("{}", message);
+        world.send_event(ShowToast::error(message));
+        return;
+    }
+    // Only the file itself is ever continued, so a stale backup is harmless
+    let backup = backup_path(&path);
+    if let Err(error) = remove_snapshot_file(&backup) {
+        warn!("Could not delete {}: {}", backup.display(), error);
+    }
+
+    match &snapshot.waves {
+        Some(path) => world.insert_resource(ActiveWaves { path: path.clone() }),
+        None => {
+            world.remove_resource::<ActiveWaves>();
+        }
+    }
+    // The level sets its own difficulty, so the saved one goes back on after
+    world.remove_resource::<CampaignRun>();
+    if let Some(campaign) = &snapshot.campaign {
+        start_campaign_level(world, &campaign.level);
+    }
+    world.insert_resource(snapshot.difficulty.clone());
+    world.insert_resource(ResumeRun(snapshot));
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+}
+
+fn remove_snapshot_file(path: &std::path::Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+// The pose an entity was simulated at, not the one interpolated for drawing
+fn motion(transform: &Transform, history: Option<&TransformHistory>, velocity: Vec2) -> Motion {
+    let (translation, rotation) = history
+        .map_or((transform.translation, transform.rotation), |history| {
+            history.current
+        });
+    Motion::new(translation, rotation, velocity)
+}
+
+fn material_color(world: &World, material: &MeshMaterial2d<ColorMaterial>) -> [f32; 4] {
+    world
+        .resource::<Assets<ColorMaterial>>()
+        .get(material.id())
+        .map_or(Srgba::WHITE, |material| material.color.to_srgba())
+        .to_f32_array()
+}
+
+fn pulse(effect: Option<&PulsingEffect>) -> Option<PulseSnapshot> {
+    effect.map(|effect| PulseSnapshot {
+        timer: TimerState::of(&effect.timer),
+        base_scale: effect.base_scale,
+        amplitude: effect.pulse_amplitude,
+    })
+}
+
+fn capture_run(world: &mut World) -> RunSnapshot {
+    let rng = world.resource::<GameRng>();
+    let lives = world.resource::<PlayerLives>();
+    let enemy_timer = world.resource::<EnemySpawnTimer>();
+    let phase_manager = world.resource::<GamePhaseManager>();
+    let mut snapshot = RunSnapshot {
+        difficulty: world.resource::<DifficultySettings>().clone(),
+        waves: world
+            .get_resource::<ActiveWaves>()
+            .map(|active_waves| active_waves.path.clone()),
+        wave_progress: world.get_resource::<WaveRunner>().map(WaveRunner::progress),
+        seed: rng.seed(),
+        gameplay_rng: rng.gameplay.get_seed(),
+        cosmetic_rng: rng.cosmetic.get_seed(),
+        run_clock: world.resource::<RunClock>().elapsed,
+        score: world.resource::<GameScore>().clone(),
+        stats: world.resource::<RunStats>().totals.clone(),
+        lives: lives.lives,
+        max_lives: lives.max_lives,
+        powerups: world.resource::<PlayerPowerUps>().clone(),
+        spawners: SpawnerSnapshot {
+            asteroid_timer: TimerState::of(&world.resource::<AsteroidSpawnTimer>().timer),
+            enemy_timer: TimerState::of(&enemy_timer.timer),
+            enemy_last_spawn_score: enemy_timer.last_spawn_score,
+            powerup_timer: TimerState::of(&world.resource::<PowerUpSpawnTimer>().timer),
+            fire_timer: TimerState::of(&world.resource::<FireTimer>().timer),
+            phase: phase_manager.current_phase.clone(),
+            phase_transition: phase_manager
+                .phase_transition_timer
+                .as_ref()
+                .map(TimerState::of),
+            asteroid_spawn_multiplier: phase_manager.asteroid_spawn_multiplier,
+            bosses_spawned: world.resource::<BossSpawnManager>().bosses_spawned.clone(),
+        },
+        ..default()
+    };
+
+    let mut beacons = world.query_filtered::<&Health, With<Beacon>>();
+    if let Some(campaign_run) = world.get_resource::<CampaignRun>() {
+        snapshot.campaign = Some(CampaignProgress {
+            level: campaign_run.level.id.clone(),
+            destroyed: campaign_run.destroyed,
+            hits: campaign_run.hits,
+            boss_defeated: campaign_run.boss_defeated,
+            beacon_health: beacons.iter(world).next().map(|health| health.current),
+        });
+    }
+
+    let mut players = world.query_filtered::<(
+        &Transform,
+        Option<&TransformHistory>,
+        &Velocity,
+        Option<&Invincibility>,
+    ), With<Player>>();
+    if let Some((transform, history, velocity, invincibility)) = players.iter(world).next() {
+        snapshot.player = PlayerSnapshot {
+            motion: motion(transform, history, velocity.0),
+            invincibility: invincibility
+                .filter(|invincibility| invincibility.is_active())
+                .map(|invincibility| TimerState::of(&invincibility.timer)),
+        };
+    }
+
+    let mut effects = world.query::<&PowerUpEffect>();
+    snapshot.effects = effects
+        .iter(world)
+        .map(|effect| PowerUpEffectSnapshot {
+            power_type: effect.power_type.clone(),
+            timer: TimerState::of(&effect.timer),
+        })
+        .collect();
+
+    let mut asteroids = world.query::<(
+        &Asteroid,
+        &Transform,
+        Option<&TransformHistory>,
+        &Velocity,
+        Option<&RotationVelocity>,
+        &Health,
+    )>();
+    snapshot.asteroids = asteroids
+        .iter(world)
+        .map(
+            |(asteroid, transform, history, velocity, rotation, health)| AsteroidSnapshot {
+                size: asteroid.size,
+                asteroid_type: asteroid.asteroid_type.clone(),
+                motion: motion(transform, history, velocity.0),
+                angular_velocity: rotation.map_or(0.0, |rotation| rotation.angular_velocity),
+                health: health.current,
+                max_health: health.max,
+            },
+        )
+        .collect();
+
+    let mut enemies = world.query::<(
+        &Enemy,
+        &Transform,
+        Option<&TransformHistory>,
+        &Velocity,
+        &Health,
+        &AIBehavior,
+        Option<&PulsingEffect>,
+    )>();
+    snapshot.enemies = enemies
+        .iter(world)
+        .map(
+            |(enemy, transform, history, velocity, health, ai, pulsing)| EnemySnapshot {
+                enemy_type: enemy.enemy_type.clone(),
+                speed: enemy.speed,
+                last_shot_time: enemy.last_shot_time,
+                shot_cooldown: enemy.shot_cooldown,
+                motion: motion(transform, history, velocity.0),
+                health: health.current,
+                max_health: health.max,
+                ai_state: ai.state.clone(),
+                ai_target: ai.target_position.to_array(),
+                ai_timer: TimerState::of(&ai.behavior_timer),
+                pulse: pulse(pulsing),
+            },
+        )
+        .collect();
+
+    let mut bosses = world.query::<(
+        &Boss,
+        &Transform,
+        Option<&TransformHistory>,
+        &Velocity,
+        &Health,
+    )>();
+    snapshot.bosses = bosses
+        .iter(world)
+        .map(
+            |(boss, transform, history, velocity, health)| BossSnapshot {
+                boss_type: boss.boss_type.clone(),
+                state: boss.state(),
+                motion: motion(transform, history, velocity.0),
+                health: health.current,
+                max_health: health.max,
+            },
+        )
+        .collect();
+
+    // Laser beams are left out: each lasts one shot, and the laser power-up
+    // fires a new one on the next
+    let mut bullets = world.query_filtered::<(
+        &Transform,
+        Option<&TransformHistory>,
+        &Velocity,
+        &BulletLifecycle,
+        &MeshMaterial2d<ColorMaterial>,
+        Option<&PiercingBullet>,
+        Option<&ExplosiveBullet>,
+    ), With<Bullet>>();
+    snapshot.bullets = bullets
+        .iter(world)
+        .map(
+            |(transform, history, velocity, lifecycle, material, piercing, explosive)| {
+                BulletSnapshot {
+                    motion: motion(transform, history, velocity.0),
+                    lifetime: TimerState::of(&lifecycle.lifetime),
+                    color: material_color(world, material),
+                    piercing: piercing
+                        .map(|piercing| (piercing.pierced_count, piercing.max_pierces)),
+                    explosive: explosive
+                        .map(|explosive| (explosive.explosion_radius, explosive.explosion_damage)),
+                }
+            },
+        )
+        .collect();
+
+    let mut enemy_bullets = world.query::<(
+        &EnemyBullet,
+        &Transform,
+        Option<&TransformHistory>,
+        &Velocity,
+        &BulletLifecycle,
+        &MeshMaterial2d<ColorMaterial>,
+        Option<&PulsingEffect>,
+    )>();
+    snapshot.enemy_bullets = enemy_bullets
+        .iter(world)
+        .map(
+            |(bullet, transform, history, velocity, lifecycle, material, pulsing)| {
+                EnemyBulletSnapshot {
+                    motion: motion(transform, history, velocity.0),
+                    lifetime: TimerState::of(&lifecycle.lifetime),
+                    color: material_color(world, material),
+                    damage: bullet.damage,
+                    is_explosive: bullet.is_explosive,
+                    pulse: pulse(pulsing),
+                }
+            },
+        )
+        .collect();
+
+    let mut pickups = world.query::<(&PowerUp, &Transform)>();
+    snapshot.pickups = pickups
+        .iter(world)
+        .map(|(powerup, transform)| PowerUpSnapshot {
+            power_type: powerup.power_type.clone(),
+            position: transform.translation.truncate().to_array(),
+        })
+        .collect();
+
+    snapshot
+}
+
+// Put the saved clock, score, timers and progress back over the fresh run's
+pub fn resume_run_state(
+    resume: Res<ResumeRun>,
+    mut rng: ResMut<GameRng>,
+    mut run_clock: ResMut<RunClock>,
+    mut score: ResMut<GameScore>,
+    mut lives: ResMut<PlayerLives>,
+    mut player_powerups: ResMut<PlayerPowerUps>,
+    mut asteroid_timer: ResMut<AsteroidSpawnTimer>,
+    mut enemy_timer: ResMut<EnemySpawnTimer>,
+    mut powerup_timer: ResMut<PowerUpSpawnTimer>,
+    mut fire_timer: ResMut<FireTimer>,
+    mut phase_manager: ResMut<GamePhaseManager>,
+    mut boss_manager: ResMut<BossSpawnManager>,
+    wave_runner: Option<ResMut<WaveRunner>>,
+    campaign_run: Option<ResMut<CampaignRun>>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    let snapshot = &resume.0;
+    rng.resume(snapshot.seed, snapshot.gameplay_rng, snapshot.cosmetic_rng);
+    run_clock.elapsed = snapshot.run_clock;
+    *score = snapshot.score.clone();
+    run_stats.resume(snapshot.stats.clone());
+    lives.lives = snapshot.lives;
+    lives.max_lives = snapshot.max_lives;
+    *player_powerups = snapshot.powerups.clone();
+
+    let spawners = &snapshot.spawners;
+    asteroid_timer.timer = spawners.asteroid_timer.to_timer(TimerMode::Repeating);
+    enemy_timer.timer = spawners.enemy_timer.to_timer(TimerMode::Repeating);
+    enemy_timer.last_spawn_score = spawners.enemy_last_spawn_score;
+    powerup_timer.timer = spawners.powerup_timer.to_timer(TimerMode::Repeating);
+    fire_timer.timer = spawners.fire_timer.to_timer(TimerMode::Repeating);
+    phase_manager.current_phase = spawners.phase.clone();
+    phase_manager.phase_transition_timer = spawners
+        .phase_transition
+        .map(|timer| timer.to_timer(TimerMode::Once));
+    phase_manager.asteroid_spawn_multiplier = spawners.asteroid_spawn_multiplier;
+    boss_manager.bosses_spawned = spawners.bosses_spawned.clone();
+
+    if let (Some(mut wave_runner), Some(progress)) = (wave_runner, &snapshot.wave_progress) {
+        wave_runner.resume(progress);
+    }
+    if let (Some(mut campaign_run), Some(campaign)) = (campaign_run, &snapshot.campaign) {
+        campaign_run.destroyed = campaign.destroyed;
+        campaign_run.hits = campaign.hits;
+        campaign_run.boss_defeated = campaign.boss_defeated;
+    }
+}
+
+// Move the ship to where it was and spawn everything else that was in flight.
+// Enemies or bosses whose tuning file no longer has them are left out.
+pub fn resume_run_entities(
+    mut commands: Commands,
+    resume: Res<ResumeRun>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    archetypes: Res<EnemyArchetypes>,
+    boss_definitions: Res<BossDefinitions>,
+    mut boss_manager: ResMut<BossSpawnManager>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut rng: ResMut<GameRng>,
+    mut players: Query<(Entity, &mut Transform, &mut Velocity), With<Player>>,
+    mut beacons: Query<&mut Health, With<Beacon>>,
+) {
+    let snapshot = &resume.0;
+    commands.remove_resource::<ResumeRun>();
+    // Inputs from here on are not a replay of the whole run
+    recorder.interrupted = true;
+
+    if let Ok((entity, mut transform, mut velocity)) = players.single_mut() {
+        *transform = snapshot.player.motion.transform();
+        velocity.0 = snapshot.player.motion.velocity();
+        if let Some(invincibility) = snapshot.player.invincibility {
+            commands.entity(entity).insert(Invincibility {
+                timer: invincibility.to_timer(TimerMode::Once),
+            });
+        }
+    }
+    if let Some(beacon_health) = snapshot
+        .campaign
+        .as_ref()
+        .and_then(|campaign| campaign.beacon_health)
+    {
+        for mut health in beacons.iter_mut() {
+            health.current = beacon_health.min(health.max);
+        }
+    }
+
+    for effect in &snapshot.effects {
+        let mut power_up_effect = PowerUpEffect::new(
+            effect.power_type.clone(),
+            effect.timer.duration.as_secs_f32(),
+        );
+        power_up_effect.timer = effect.timer.to_timer(TimerMode::Once);
+        commands.spawn(power_up_effect);
+    }
+
+    for saved in &snapshot.asteroids {
+        spawn_asteroid_entity(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &mut rng.cosmetic,
+            Asteroid::new(saved.size, saved.asteroid_type.clone()),
+            saved.motion.transform(),
+            saved.motion.velocity(),
+        )
+        .insert((
+            Health {
+                current: saved.health,
+                max: saved.max_health,
+            },
+            RotationVelocity::new(saved.angular_velocity),
+        ));
+    }
+
+    for saved in &snapshot.enemies {
+        let Some(archetype) = archetypes.get(&saved.enemy_type) else {
+            warn!("Enemy type {} no longer exists", saved.enemy_type);
+            continue;
+        };
+        let ship_mesh = create_enemy_ship_mesh(&archetype.shape);
+        let enemy = Enemy {
+            enemy_type: saved.enemy_type.clone(),
+            archetype: archetype.clone(),
+            speed: saved.speed,
+            last_shot_time: saved.last_shot_time,
+            shot_cooldown: saved.shot_cooldown,
+        };
+        let enemy_color = enemy.get_color();
+        let enemy_collider = enemy.collider();
+
+        let mut entity = commands.spawn((
+            Mesh2d(meshes.add(ship_mesh)),
+            MeshMaterial2d(materials.add(ColorMaterial::from(enemy_color))),
+            saved.motion.transform(),
+            enemy,
+            enemy_collider,
+            Health {
+                current: saved.health,
+                max: saved.max_health,
+            },
+            Velocity(saved.motion.velocity()),
+            AIBehavior {
+                target_position: Vec2::from_array(saved.ai_target),
+                behavior_timer: saved.ai_timer.to_timer(TimerMode::Repeating),
+                state: saved.ai_state.clone(),
+            },
+            Wraparound,
+        ));
+        if let Some(pulse) = &saved.pulse {
+            entity.insert(pulsing_effect(pulse));
+        }
+    }
+
+    for saved in &snapshot.bosses {
+        let Some(definition) = boss_definitions.get(&saved.boss_type) else {
+            warn!("Boss type {} no longer exists", saved.boss_type);
+            continue;
+        };
+        let boss = Boss::from_state(definition.clone(), &saved.state);
+        let boss_color = boss.get_color();
+        let boss_collider = boss.collider();
+
+        let entity = commands
+            .spawn((
+                Mesh2d(meshes.add(create_boss_mesh(
+                    &definition.shape,
+                    boss.size_multiplier,
+                    &mut rng.cosmetic,
+                ))),
+                MeshMaterial2d(materials.add(ColorMaterial::from(boss_color))),
+                saved.motion.transform(),
+                boss,
+                boss_collider,
+                Health {
+                    current: saved.health,
+                    max: saved.max_health,
+                },
+                Velocity(saved.motion.velocity()),
+            ))
+            .id();
+        boss_manager.active_boss = Some(entity);
+    }
+
+    for saved in &snapshot.bullets {
+        let mut entity =
+            commands.spawn((
+                Mesh2d(meshes.add(create_bullet_arrow_mesh())),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::from(
+                    Srgba::from_f32_array(saved.color),
+                )))),
+                saved.motion.transform(),
+                Bullet,
+                Bullet::collider(),
+                BulletLifecycle {
+                    lifetime: saved.lifetime.to_timer(TimerMode::Once),
+                    initial_intensity: 8.0,
+                },
+                Velocity(saved.motion.velocity()),
+                Wraparound,
+            ));
+        if let Some((pierced_count, max_pierces)) = saved.piercing {
+            entity.insert(PiercingBullet {
+                pierced_count,
+                max_pierces,
+            });
+        }
+        if let Some((radius, damage)) = saved.explosive {
+            entity.insert(ExplosiveBullet::new(radius, damage));
+        }
+    }
+
+    for saved in &snapshot.enemy_bullets {
+        let mut entity =
+            commands.spawn((
+                Mesh2d(meshes.add(create_enemy_bullet_mesh())),
+                MeshMaterial2d(materials.add(ColorMaterial::from(Color::from(
+                    Srgba::from_f32_array(saved.color),
+                )))),
+                saved.motion.transform(),
+                EnemyBullet {
+                    damage: saved.damage,
+                    is_explosive: saved.is_explosive,
+                },
+                EnemyBullet::collider(),
+                Velocity(saved.motion.velocity()),
+                BulletLifecycle {
+                    lifetime: saved.lifetime.to_timer(TimerMode::Once),
+                    initial_intensity: 5.0,
+                },
+            ));
+        if let Some(pulse) = &saved.pulse {
+            entity.insert(pulsing_effect(pulse));
+        }
+    }
+
+    for saved in &snapshot.pickups {
+        spawn_powerup_at(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            saved.power_type.clone(),
+            Vec2::from_array(saved.position),
+        );
+    }
+}
+
+fn pulsing_effect(pulse: &PulseSnapshot) -> PulsingEffect {
+    PulsingEffect {
+        timer: pulse.timer.to_timer(TimerMode::Repeating),
+        base_scale: pulse.base_scale,
+        pulse_amplitude: pulse.amplitude,
+    }
+}
@@ -0,0 +1,59 @@
+use crate::components::*;
+use crate::events::*;
+use bevy::prelude::*;
+
+const TOAST_TOP: f32 = 12.0;
+const TOAST_SPACING: f32 = 26.0;
+
+// Spawn a line of text for every toast; errors stay up longer
+pub fn show_toasts(
+    mut commands: Commands,
+    mut toast_events: EventReader<ShowToast>,
+    toasts: Query<(), With<Toast>>,
+) {
+    let shown = toasts.iter().count();
+    for (index, toast) in toast_events.read().enumerate() {
+        let row = shown + index;
+        let (color, seconds) = match toast.kind {
+            ToastKind::Info => (Color::srgb(1.5, 3.0, 1.5), 3.0),
+            ToastKind::Error => (Color::srgb(3.0, 1.0, 1.0), 8.0),
+        };
+
+        commands.spawn((
+            Text::new(toast.message.clone()),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor::from(color),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(TOAST_TOP + row as f32 * TOAST_SPACING),
+                left: Val::Px(12.0),
+                right: Val::Px(12.0),
+                ..default()
+            },
+            GlobalZIndex(100),
+            Toast {
+                timer: Timer::from_seconds(seconds, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+// Remove expired toasts and close the gaps they leave
+pub fn expire_toasts(
+    mut commands: Commands,
+    mut toasts: Query<(Entity, &mut Toast, &mut Node)>,
+    time: Res<Time<Real>>,
+) {
+    let mut row = 0;
+    for (entity, mut toast, mut node) in toasts.iter_mut() {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        node.top = Val::Px(TOAST_TOP + row as f32 * TOAST_SPACING);
+        row += 1;
+    }
+}
@@ -0,0 +1,341 @@
+use crate::assets::TuningFile;
+use crate::events::*;
+use crate::resources::*;
+use bevy::asset::LoadedFolder;
+use bevy::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FileChange {
+    // First load at startup; the resources were already read from disk
+    Loaded,
+    // Edited while the game runs
+    Modified,
+}
+
+// Keep the tuning files loaded through the asset server so edits are noticed
+pub fn load_tuning_files(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TuningFiles {
+        difficulties: asset_server.load(DIFFICULTIES_PATH),
+        enemies: asset_server.load(ENEMIES_PATH),
+        bosses: asset_server.load_folder(BOSSES_DIR),
+        spawning: asset_server.load(SPAWNING_PATH),
+        campaign: asset_server.load(CAMPAIGN_PATH),
+        achievements: asset_server.load(ACHIEVEMENTS_PATH),
+    });
+}
+
+// Whether a reload may change the simulation now. A replay keeps the values
+// it started with, and a recording changed part-way can't be replayed.
+fn reload_reaches_run(
+    game_state: &State<GameState>,
+    playback: Option<&ReplayPlayback>,
+    recorder: &mut ReplayRecorder,
+) -> bool {
+    if playback.is_some() {
+        return false;
+    }
+    if *game_state.get() == GameState::Playing {
+        recorder.interrupted = true;
+    }
+    true
+}
+
+pub fn reload_difficulty_presets(
+    mut events: EventReader<AssetEvent<TuningFile>>,
+    files: Res<TuningFiles>,
+    tuning_files: Res<Assets<TuningFile>>,
+    presets: Option<ResMut<DifficultyPresets>>,
+    mut difficulty: ResMut<DifficultySettings>,
+    mut asteroid_spawn_timer: ResMut<AsteroidSpawnTimer>,
+    game_state: Res<State<GameState>>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut toasts: EventWriter<ShowToast>,
+    mut loaded: Local<bool>,
+) {
+    let Some((change, text)) =
+        changed_file(&mut events, &files.difficulties, &tuning_files, &mut loaded)
+    else {
+        return;
+    };
+    let parsed = DifficultyPresets::parse(text).map_err(|error| error.to_string());
+    let Some(parsed) = check_reload(DIFFICULTIES_PATH, change, parsed, &mut toasts) else {
+        return;
+    };
+
+    // The difficulty being played follows its preset; Custom isn't in the file
+    let preset = parsed.get(difficulty.id);
+    if difficulty.id != DifficultyId::Custom
+        && *preset != *difficulty
+        && reload_reaches_run(&game_state, playback.as_deref(), &mut recorder)
+    {
+        *difficulty = preset.clone();
+        asteroid_spawn_timer
+            .timer
+            .set_duration(std::time::Duration::from_secs_f32(
+                difficulty.asteroid_spawn_rate,
+            ));
+    }
+    if let Some(mut presets) = presets {
+        *presets = parsed;
+    }
+}
+
+pub fn reload_enemy_archetypes(
+    mut events: EventReader<AssetEvent<TuningFile>>,
+    files: Res<TuningFiles>,
+    tuning_files: Res<Assets<TuningFile>>,
+    mut archetypes: ResMut<EnemyArchetypes>,
+    bosses: Option<Res<BossDefinitions>>,
+    game_state: Res<State<GameState>>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut toasts: EventWriter<ShowToast>,
+    mut loaded: Local<bool>,
+    mut pending: Local<Option<EnemyArchetypes>>,
+) {
+    if let Some((change, text)) =
+        changed_file(&mut events, &files.enemies, &tuning_files, &mut loaded)
+    {
+        // Bosses may not lose an enemy they spawn
+        let parsed = EnemyArchetypes::parse(text).and_then(|parsed| match &bosses {
+            Some(bosses) => bosses.check_ids(&parsed).map(|()| parsed),
+            None => Ok(parsed),
+        });
+        if let Some(parsed) = check_reload(ENEMIES_PATH, change, parsed, &mut toasts) {
+            *pending = Some(parsed);
+        }
+    }
+
+    // Held back while a replay plays; ships already flying keep the
+    // archetype they were spawned with
+    if pending.is_some()
+        && reload_reaches_run(&game_state, playback.as_deref(), &mut recorder)
+        && let Some(parsed) = pending.take()
+    {
+        *archetypes = parsed;
+    }
+}
+
+pub fn reload_boss_definitions(
+    mut folder_events: EventReader<AssetEvent<LoadedFolder>>,
+    mut file_events: EventReader<AssetEvent<TuningFile>>,
+    files: Res<TuningFiles>,
+    folders: Res<Assets<LoadedFolder>>,
+    tuning_files: Res<Assets<TuningFile>>,
+    bosses: Option<ResMut<BossDefinitions>>,
+    archetypes: Res<EnemyArchetypes>,
+    game_state: Res<State<GameState>>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut toasts: EventWriter<ShowToast>,
+    mut pending: Local<Option<BossDefinitions>>,
+) {
+    if let Some((change, texts)) = changed_boss_files(
+        &mut folder_events,
+        &mut file_events,
+        &files.bosses,
+        &folders,
+        &tuning_files,
+    ) {
+        let parsed = BossDefinitions::parse(texts)
+            .and_then(|parsed| parsed.check_ids(&archetypes).map(|()| parsed));
+        if let Some(parsed) = check_reload(BOSSES_DIR, change, parsed, &mut toasts) {
+            *pending = Some(parsed);
+        }
+    }
+
+    // Held back while a replay plays; a boss already on screen keeps its
+    // definition
+    if let Some(mut bosses) = bosses
+        && pending.is_some()
+        && reload_reaches_run(&game_state, playback.as_deref(), &mut recorder)
+        && let Some(parsed) = pending.take()
+    {
+        *bosses = parsed;
+    }
+}
+
+pub fn reload_spawn_tables(
+    mut events: EventReader<AssetEvent<TuningFile>>,
+    files: Res<TuningFiles>,
+    tuning_files: Res<Assets<TuningFile>>,
+    mut tables: ResMut<SpawnTables>,
+    mut size_generator: ResMut<AsteroidSizeGenerator>,
+    mut type_generator: ResMut<AsteroidTypeGenerator>,
+    enemy_spawn_timer: Option<ResMut<EnemySpawnTimer>>,
+    powerup_spawn_timer: Option<ResMut<PowerUpSpawnTimer>>,
+    game_state: Res<State<GameState>>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut toasts: EventWriter<ShowToast>,
+    mut loaded: Local<bool>,
+    mut pending: Local<bool>,
+) {
+    if let Some((change, text)) =
+        changed_file(&mut events, &files.spawning, &tuning_files, &mut loaded)
+    {
+        let parsed = SpawnTables::parse(text);
+        if let Some(parsed) = check_reload(SPAWNING_PATH, change, parsed, &mut toasts) {
+            *tables = parsed;
+            *pending = true;
+        }
+    }
+
+    // The spawners pick the tables up once no replay is playing
+    if !*pending || !reload_reaches_run(&game_state, playback.as_deref(), &mut recorder) {
+        return;
+    }
+    *pending = false;
+    *size_generator = AsteroidSizeGenerator::from_table(&tables.asteroid_sizes);
+    *type_generator = AsteroidTypeGenerator::from_table(&tables.asteroid_types);
+    if let Some(mut timer) = enemy_spawn_timer {
+        timer.apply_table(&tables.enemy_spawn);
+    }
+    if let Some(mut timer) = powerup_spawn_timer {
+        timer.apply_table(&tables.powerup_spawn);
+    }
+}
+
+pub fn reload_campaign(
+    mut events: EventReader<AssetEvent<TuningFile>>,
+    files: Res<TuningFiles>,
+    tuning_files: Res<Assets<TuningFile>>,
+    campaign: Option<ResMut<Campaign>>,
+    bosses: Res<BossDefinitions>,
+    mut toasts: EventWriter<ShowToast>,
+    mut loaded: Local<bool>,
+) {
+    let Some((change, text)) =
+        changed_file(&mut events, &files.campaign, &tuning_files, &mut loaded)
+    else {
+        return;
+    };
+    let parsed =
+        Campaign::parse(text).and_then(|parsed| parsed.check_bosses(&bosses).map(|()| parsed));
+    if let (Some(parsed), Some(mut campaign)) = (
+        check_reload(CAMPAIGN_PATH, change, parsed, &mut toasts),
+        campaign,
+    ) {
+        // A level being played keeps the definition it started with
+        *campaign = parsed;
+    }
+}
+
+pub fn reload_achievements(
+    mut events: EventReader<AssetEvent<TuningFile>>,
+    files: Res<TuningFiles>,
+    tuning_files: Res<Assets<TuningFile>>,
+    achievements: Option<ResMut<Achievements>>,
+    bosses: Res<BossDefinitions>,
+    enemies: Res<EnemyArchetypes>,
+    mut toasts: EventWriter<ShowToast>,
+    mut loaded: Local<bool>,
+) {
+    let Some((change, text)) =
+        changed_file(&mut events, &files.achievements, &tuning_files, &mut loaded)
+    else {
+        return;
+    };
+    let parsed = Achievements::parse(text)
+        .and_then(|parsed| parsed.check_names(&bosses, &enemies).map(|()| parsed));
+    if let (Some(parsed), Some(mut achievements)) = (
+        check_reload(ACHIEVEMENTS_PATH, change, parsed, &mut toasts),
+        achievements,
+    ) {
+        // Unlocks are kept by id, so renamed or removed ones just stop showing
+        *achievements = parsed;
+    }
+}
+
+// How `handle` changed since this system last ran, and its text. Every
+// reload is followed by another "loaded" event, so only the first counts.
+fn changed_file<'a>(
+    events: &mut EventReader<AssetEvent<TuningFile>>,
+    handle: &Handle<TuningFile>,
+    tuning_files: &'a Assets<TuningFile>,
+    loaded: &mut bool,
+) -> Option<(FileChange, &'a str)> {
+    let mut change = None;
+    for event in events.read() {
+        if event.is_modified(handle) {
+            change = Some(FileChange::Modified);
+        } else if event.is_loaded_with_dependencies(handle) && !*loaded {
+            *loaded = true;
+            change.get_or_insert(FileChange::Loaded);
+        }
+    }
+    Some((change?, tuning_files.get(handle)?.0.as_str()))
+}
+
+// Like `changed_file`, for the boss folder: how any file in it changed and
+// the text of every file, in path order
+fn changed_boss_files<'a>(
+    folder_events: &mut EventReader<AssetEvent<LoadedFolder>>,
+    file_events: &mut EventReader<AssetEvent<TuningFile>>,
+    folder: &Handle<LoadedFolder>,
+    folders: &Assets<LoadedFolder>,
+    tuning_files: &'a Assets<TuningFile>,
+) -> Option<(FileChange, Vec<&'a str>)> {
+    let mut change = folder_events
+        .read()
+        .any(|event| event.is_loaded_with_dependencies(folder))
+        .then_some(FileChange::Loaded);
+    let Some(folder) = folders.get(folder) else {
+        file_events.clear();
+        return None;
+    };
+
+    let mut handles: Vec<Handle<TuningFile>> = folder
+        .handles
+        .iter()
+        .filter_map(|handle| handle.clone().try_typed().ok())
+        .collect();
+    handles.sort_by_key(|handle| handle.path().map(ToString::to_string));
+
+    let mut modified = false;
+    for event in file_events.read() {
+        modified |= handles.iter().any(|handle| event.is_modified(handle));
+    }
+    if modified {
+        change = Some(FileChange::Modified);
+    }
+    let change = change?;
+
+    let texts: Option<Vec<&str>> = handles
+        .iter()
+        .map(|handle| tuning_files.get(handle).map(|file| file.0.as_str()))
+        .collect();
+    Some((change, texts.filter(|texts| !texts.is_empty())?))
+}
+
+// Reports the outcome of parsing a changed file. Only a successful reload
+// returns the parsed value; the startup load just gets its errors shown,
+// since the resource already fell back to the built-in copy.
+fn check_reload<T>(
+    path: &str,
+    change: FileChange,
+    parsed: Result<T, String>,
+    toasts: &mut EventWriter<ShowToast>,
+) -> Option<T> {
+    match (parsed, change) {
+        (Ok(parsed), FileChange::Modified) => {
+            info!("Reloaded {}", path);
+            toasts.write(ShowToast::info(format!("Reloaded {}", path)));
+            Some(parsed)
+        }
+        (Ok(_), FileChange::Loaded) => None,
+        (Err(error), change) => {
+            let fallback = match change {
+                FileChange::Loaded => "using the built-in copy",
+                FileChange::Modified => "keeping the previous values",
+            };
+            warn!("{}: {} ({})", path, error, fallback);
+            toasts.write(ShowToast::error(format!(
+                "{}: {} ({})",
+                path, error, fallback
+            )));
+            None
+        }
+    }
+}
@@ -10,6 +10,33 @@ pub fn update_score_display(score: Res<GameScore>, mut query: Query<&mut Text, W
     }
 }
 
+pub fn update_combo_display(
+    score: Res<GameScore>,
+    mut text_query: Query<(&mut Text, &mut Visibility), With<ComboText>>,
+    mut bar_query: Query<&mut Visibility, (With<ComboBar>, Without<ComboText>)>,
+    mut fill_query: Query<&mut Node, With<ComboBarFill>>,
+) {
+    if !score.is_changed() {
+        return;
+    }
+    // Only shown while a combo is running
+    let visibility = if score.combo > 0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for (mut text, mut text_visibility) in text_query.iter_mut() {
+        **text = format!("Combo {}  x{}", score.combo, score.multiplier());
+        *text_visibility = visibility;
+    }
+    for mut bar_visibility in bar_query.iter_mut() {
+        *bar_visibility = visibility;
+    }
+    for mut fill in fill_query.iter_mut() {
+        fill.width = Val::Percent(score.combo_fraction() * 100.0);
+    }
+}
+
 pub fn update_lives_display(lives: Res<PlayerLives>, mut query: Query<&mut Text, With<LivesText>>) {
     if lives.is_changed() {
         for mut text in query.iter_mut() {
@@ -115,11 +142,10 @@ pub fn update_boss_health_bar(
 
         // Update phase text
         if let Ok(mut text) = phase_text_query.single_mut() {
-            let boss_name = match boss.boss_type {
-                crate::components::BossType::GiantAsteroid => "Giant Asteroid",
-                crate::components::BossType::AlienMothership => "Alien Mothership",
-            };
-            **text = format!("{} - Phase {} / {}", boss_name, boss.phase, boss.max_phases);
+            **text = format!(
+                "{} - Phase {} / {}",
+                boss.definition.name, boss.phase, boss.max_phases
+            );
         }
 
         // Only handle the first boss if there are multiple
@@ -0,0 +1,218 @@
+use crate::components::*;
+use crate::events::*;
+use crate::resources::*;
+use crate::systems::{
+    spawn_asteroid, spawn_boss, spawn_enemy, spawn_powerup, spawn_random_powerup,
+};
+use bevy::prelude::*;
+use std::sync::Arc;
+
+// Start the run's wave file, if any, when a run begins: the campaign level's,
+// or else the active one. A file that can't be played leaves the run on the
+// timed spawners.
+pub fn start_wave_runner(
+    mut commands: Commands,
+    campaign_run: Option<Res<CampaignRun>>,
+    active_waves: Option<Res<ActiveWaves>>,
+    archetypes: Res<EnemyArchetypes>,
+    bosses: Res<BossDefinitions>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    commands.remove_resource::<WaveRunner>();
+    let path = match (campaign_run, active_waves) {
+        (Some(campaign_run), _) => campaign_run.level.wave_path(),
+        (None, Some(active_waves)) => active_waves.path.clone(),
+        (None, None) => return,
+    };
+
+    let waves = WaveFile::load(&path)
+        .and_then(|waves| waves.check_ids(&archetypes, &bosses).map(|()| waves));
+    match waves {
+        Ok(waves) => {
+            info!("Playing {} ({} waves)", waves.name, waves.waves.len());
+            commands.insert_resource(WaveRunner::new(Arc::new(waves)));
+        }
+        Err(error) => {
+            warn!("{}: {} (playing endless instead)", path, error);
+            toasts.write(ShowToast::error(format!(
+                "{}: {} (playing endless instead)",
+                path, error
+            )));
+        }
+    }
+}
+
+pub fn end_wave_run(mut commands: Commands, banners: Query<Entity, With<WaveBanner>>) {
+    commands.remove_resource::<WaveRunner>();
+    for entity in banners.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Run the wave clock, move on once a wave's clear condition holds and end
+// the run after the last one
+pub fn advance_waves(
+    mut commands: Commands,
+    mut runner: ResMut<WaveRunner>,
+    time: Res<Time>,
+    hostiles: Query<(), Or<(With<Asteroid>, With<Enemy>, With<Boss>)>>,
+    bosses: Query<(), With<Boss>>,
+    campaign_run: Option<Res<CampaignRun>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    runner.tick(time.delta_secs());
+
+    if runner.is_cleared(hostiles.iter().count(), bosses.iter().count()) {
+        info!("Wave {} cleared", runner.wave_number());
+        runner.finish_wave();
+    }
+    if runner.is_finished() {
+        // A campaign level whose waves run out before its objective is met is lost
+        let verdict = match campaign_run {
+            Some(_) => RunVerdict::Failed,
+            None => RunVerdict::Cleared,
+        };
+        commands.insert_resource(verdict);
+        next_state.set(GameState::GameOver);
+    }
+}
+
+// Spawn whatever the current wave schedules for this moment
+pub fn spawn_wave_contents(
+    mut commands: Commands,
+    mut runner: ResMut<WaveRunner>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rng: ResMut<GameRng>,
+    difficulty: Res<DifficultySettings>,
+    bounds: Res<PlayfieldBounds>,
+    archetypes: Res<EnemyArchetypes>,
+    bosses: Res<BossDefinitions>,
+    mut boss_manager: ResMut<BossSpawnManager>,
+    mut phase_manager: ResMut<GamePhaseManager>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    for spawn in runner.take_due() {
+        match spawn {
+            WaveSpawn::Asteroids(batch) => {
+                let target = match batch.aim {
+                    SpawnAim::Center => Vec2::ZERO,
+                    SpawnAim::Player => player_query
+                        .single()
+                        .map(|transform| transform.translation.truncate())
+                        .unwrap_or(Vec2::ZERO),
+                };
+                let speed = batch.speed * difficulty.asteroid_speed_multiplier;
+
+                for _ in 0..batch.count {
+                    let spawn_pos = batch.edge.point(&bounds, 50.0, &mut rng.gameplay);
+                    let spread = (rng.gameplay.f32() * 2.0 - 1.0) * batch.spread.to_radians();
+                    let direction = Vec2::from_angle(spread)
+                        .rotate((target - spawn_pos).normalize_or(Vec2::NEG_Y));
+
+                    spawn_asteroid(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        &mut rng,
+                        Asteroid::new(batch.size, batch.asteroid_type.clone()),
+                        spawn_pos,
+                        direction * speed,
+                    );
+                }
+            }
+            WaveSpawn::Enemies(squad) => {
+                // Checked when the wave file was loaded
+                let Some(archetype) = archetypes.get(&squad.enemy) else {
+                    continue;
+                };
+                for _ in 0..squad.count {
+                    let spawn_pos = squad.edge.point(&bounds, 100.0, &mut rng.gameplay);
+                    spawn_enemy(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        archetype.clone(),
+                        &difficulty,
+                        spawn_pos,
+                    );
+                }
+            }
+            WaveSpawn::PowerUp(drop) => match drop.powerup {
+                Some(power_type) => spawn_powerup(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    power_type,
+                    &mut rng.gameplay,
+                    None,
+                ),
+                None => spawn_random_powerup(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    &mut rng.gameplay,
+                    None,
+                ),
+            },
+            WaveSpawn::Boss(trigger) => {
+                let Some(definition) = bosses.get(&trigger.boss) else {
+                    continue;
+                };
+                phase_manager.enter_boss_encounter();
+                spawn_boss(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    definition.clone(),
+                    &difficulty,
+                    &bounds,
+                    &mut boss_manager,
+                    &mut rng.cosmetic,
+                );
+            }
+        }
+    }
+}
+
+// Show the "Wave N" banner during intermissions and hide it while a wave plays
+pub fn update_wave_banner(
+    mut commands: Commands,
+    runner: Option<Res<WaveRunner>>,
+    mut banners: Query<(Entity, &mut Text), With<WaveBanner>>,
+) {
+    let Some(banner) = runner.and_then(|runner| runner.banner()) else {
+        for (entity, _) in banners.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    match banners.single_mut() {
+        Ok((_, mut text)) => {
+            if text.0 != banner {
+                text.0 = banner;
+            }
+        }
+        Err(_) => {
+            commands.spawn((
+                Text::new(banner),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(2.5, 2.5, 4.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(35.0),
+                    left: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    ..default()
+                },
+                WaveBanner,
+                GameUI,
+            ));
+        }
+    }
+}
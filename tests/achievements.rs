@@ -0,0 +1,91 @@
+use rusty_asteroid::components::{AsteroidType, BossType, PowerUpType};
+use rusty_asteroid::events::Hazard;
+use rusty_asteroid::resources::*;
+
+fn context<'a>(
+    run: &'a StatTotals,
+    lifetime: &'a LifetimeStats,
+    difficulty: DifficultyId,
+) -> AchievementContext<'a> {
+    AchievementContext {
+        run,
+        lifetime,
+        difficulty,
+        score: 0,
+        max_combo: 0,
+    }
+}
+
+fn achievement<'a>(achievements: &'a Achievements, id: &str) -> &'a Achievement {
+    achievements
+        .iter()
+        .find(|achievement| achievement.id == id)
+        .unwrap()
+}
+
+#[test]
+fn built_in_achievements_name_known_bosses_and_enemies() {
+    Achievements::built_in()
+        .check_names(&BossDefinitions::built_in(), &EnemyArchetypes::built_in())
+        .unwrap();
+}
+
+#[test]
+fn conditions_must_all_hold() {
+    let achievements = Achievements::built_in();
+    let insane_mothership = achievement(&achievements, "insane_mothership");
+
+    let mut run = StatTotals::default();
+    run.record_boss(&BossType("AlienMothership".to_string()));
+    let lifetime = LifetimeStats::default();
+    assert!(insane_mothership.is_met(&context(&run, &lifetime, DifficultyId::Insane)));
+    assert!(!insane_mothership.is_met(&context(&run, &lifetime, DifficultyId::Hard)));
+    assert!(!insane_mothership.is_met(&context(
+        &StatTotals::default(),
+        &lifetime,
+        DifficultyId::Insane
+    )));
+}
+
+#[test]
+fn lifetime_conditions_count_every_run() {
+    let achievements = Achievements::built_in();
+    let crystal_collector = achievement(&achievements, "crystal_collector");
+
+    let mut lifetime = LifetimeStats::default();
+    for _ in 0..49 {
+        lifetime.totals.record_asteroid(&AsteroidType::Crystal);
+    }
+    lifetime.totals.record_asteroid(&AsteroidType::Metal);
+    let run = StatTotals::default();
+    assert!(!crystal_collector.is_met(&context(&run, &lifetime, DifficultyId::Normal)));
+    assert_eq!(crystal_collector.progress(&lifetime), Some((49, 50)));
+
+    lifetime.totals.record_asteroid(&AsteroidType::Crystal);
+    assert!(crystal_collector.is_met(&context(&run, &lifetime, DifficultyId::Normal)));
+}
+
+#[test]
+fn rejects_unmeetable_conditions() {
+    let file = r#"{"achievements": [{"id": "a", "name": "A", "description": "",
+        "conditions": [{"type": "Difficulty", "difficulty": "Custom"}]}]}"#;
+    assert!(Achievements::parse(file).is_err());
+}
+
+#[test]
+fn stats_are_keyed_by_stable_names() {
+    let mut totals = StatTotals::default();
+    totals.record_asteroid(&AsteroidType::Crystal);
+    totals.record_power_up(&PowerUpType::LaserBeam);
+    totals.record_death(Hazard::EnemyBullet);
+
+    // Saved counts must keep matching the serde names used in tuning files
+    let json = serde_json::to_value(&totals).unwrap();
+    assert_eq!(json["asteroids"]["Crystal"], 1);
+    assert_eq!(json["power_ups"]["LaserBeam"], 1);
+    assert_eq!(json["deaths"]["EnemyBullet"], 1);
+    assert_eq!(
+        serde_json::to_string(&PowerUpType::ExplosiveBullets).unwrap(),
+        format!("\"{}\"", PowerUpType::ExplosiveBullets.key())
+    );
+}
@@ -0,0 +1,155 @@
+use rusty_asteroid::components::{AsteroidType, BossType};
+use rusty_asteroid::resources::*;
+use std::sync::Arc;
+
+// A campaign with one level around `objective`, plus any extra level fields
+fn one_level(objective: &str, extra: &str) -> Result<Campaign, String> {
+    Campaign::parse(&format!(
+        r#"{{ "levels": [{{
+            "id": "test",
+            "name": "Test",
+            "waves": "training",
+            "objective": {},
+            "two_stars": {{ "score": 1000, "max_hits": 2 }},
+            "three_stars": {{ "score": 2000, "max_hits": 0 }}{}
+        }}] }}"#,
+        objective, extra
+    ))
+}
+
+fn run_of(objective: &str) -> CampaignRun {
+    let campaign = one_level(objective, "").unwrap();
+    CampaignRun::new(Arc::clone(&campaign.levels()[0]))
+}
+
+#[test]
+fn built_in_campaign_names_known_bosses() {
+    let campaign = Campaign::built_in();
+    campaign.check_bosses(&BossDefinitions::built_in()).unwrap();
+    assert_eq!(campaign.levels()[0].id, "first_contact");
+    assert_eq!(campaign.levels()[0].difficulty, DifficultyId::Easy);
+    assert_eq!(campaign.levels()[1].difficulty, DifficultyId::Normal);
+}
+
+#[test]
+fn rejects_levels_that_cannot_be_played() {
+    assert!(Campaign::parse(r#"{ "levels": [] }"#).is_err());
+
+    let survive = r#"{ "type": "Survive", "seconds": 30.0 }"#;
+    assert!(one_level(survive, "").is_ok());
+    let error = one_level(survive, r#", "difficulty": "Custom""#).unwrap_err();
+    assert!(error.contains("Custom"), "{}", error);
+
+    for objective in [
+        r#"{ "type": "Survive", "seconds": 0.0 }"#,
+        r#"{ "type": "DestroyAsteroids", "count": 0 }"#,
+        r#"{ "type": "Protect", "position": [0.0, 0.0], "radius": 30.0, "health": 0, "seconds": 60.0 }"#,
+        r#"{ "type": "Protect", "position": [0.0, 0.0], "radius": 0.0, "health": 5, "seconds": 60.0 }"#,
+    ] {
+        let error = one_level(objective, "").unwrap_err();
+        assert!(error.contains("can't be met"), "{}", error);
+    }
+
+    let campaign = one_level(r#"{ "type": "DefeatBoss", "boss": "SpaceWhale" }"#, "").unwrap();
+    let error = campaign
+        .check_bosses(&BossDefinitions::built_in())
+        .unwrap_err();
+    assert!(error.contains("SpaceWhale"), "{}", error);
+}
+
+#[test]
+fn rejects_levels_defined_twice() {
+    let level = r#"{
+        "id": "twice",
+        "name": "Twice",
+        "waves": "training",
+        "objective": { "type": "Survive", "seconds": 30.0 },
+        "two_stars": { "score": 1000, "max_hits": 2 },
+        "three_stars": { "score": 2000, "max_hits": 0 }
+    }"#;
+    let error = Campaign::parse(&format!(r#"{{ "levels": [{}, {}] }}"#, level, level)).unwrap_err();
+    assert!(error.contains("twice"), "{}", error);
+}
+
+#[test]
+fn stars_need_both_the_score_and_few_enough_hits() {
+    let campaign = one_level(r#"{ "type": "Survive", "seconds": 30.0 }"#, "").unwrap();
+    let level = &campaign.levels()[0];
+    assert_eq!(level.stars(0, 10), 1);
+    assert_eq!(level.stars(1000, 2), 2);
+    assert_eq!(level.stars(1000, 3), 1);
+    assert_eq!(level.stars(5000, 1), 2);
+    assert_eq!(level.stars(2000, 0), 3);
+}
+
+#[test]
+fn survive_completes_once_the_time_is_up() {
+    let mut run = run_of(r#"{ "type": "Survive", "seconds": 30.0 }"#);
+    assert_eq!(run.update(29.9, None), ObjectiveStatus::InProgress);
+    assert_eq!(run.update(30.0, None), ObjectiveStatus::Complete);
+    // A decided attempt stays decided
+    assert_eq!(run.update(0.0, None), ObjectiveStatus::Complete);
+
+    run.reset();
+    assert_eq!(run.status, ObjectiveStatus::InProgress);
+}
+
+#[test]
+fn destroy_asteroids_counts_only_the_wanted_type() {
+    let mut run = run_of(r#"{ "type": "DestroyAsteroids", "count": 2, "asteroid_type": "Metal" }"#);
+    run.record_asteroid(&AsteroidType::Metal);
+    run.record_asteroid(&AsteroidType::Ice);
+    assert_eq!(run.update(100.0, None), ObjectiveStatus::InProgress);
+    run.record_asteroid(&AsteroidType::Metal);
+    assert_eq!(run.update(100.0, None), ObjectiveStatus::Complete);
+
+    let mut any = run_of(r#"{ "type": "DestroyAsteroids", "count": 2 }"#);
+    any.record_asteroid(&AsteroidType::Ice);
+    any.record_asteroid(&AsteroidType::Crystal);
+    assert_eq!(any.update(0.0, None), ObjectiveStatus::Complete);
+}
+
+#[test]
+fn defeat_boss_needs_that_boss() {
+    let mut run = run_of(r#"{ "type": "DefeatBoss", "boss": "GiantAsteroid" }"#);
+    run.record_boss(&BossType("AlienMothership".to_string()));
+    assert_eq!(run.update(10.0, None), ObjectiveStatus::InProgress);
+    run.record_boss(&BossType("GiantAsteroid".to_string()));
+    assert_eq!(run.update(10.0, None), ObjectiveStatus::Complete);
+}
+
+#[test]
+fn protect_fails_when_the_beacon_falls() {
+    let objective = r#"{ "type": "Protect", "position": [0.0, 0.0], "radius": 30.0, "health": 5, "seconds": 60.0 }"#;
+    let mut run = run_of(objective);
+    assert_eq!(run.update(10.0, Some(3)), ObjectiveStatus::InProgress);
+    assert_eq!(run.update(20.0, Some(0)), ObjectiveStatus::Failed);
+
+    let mut destroyed = run_of(objective);
+    assert_eq!(destroyed.update(10.0, None), ObjectiveStatus::Failed);
+
+    // Still standing when the time is up, however damaged
+    let mut held = run_of(objective);
+    assert_eq!(held.update(60.0, Some(1)), ObjectiveStatus::Complete);
+}
+
+#[test]
+fn levels_unlock_in_order() {
+    let campaign = Campaign::built_in();
+    let levels = campaign.levels();
+    let mut save_data = SaveData::default();
+    assert!(campaign.is_unlocked(0, &save_data));
+    assert!(!campaign.is_unlocked(1, &save_data));
+
+    save_data.record_level(&levels[0].id, 1, 500);
+    assert!(campaign.is_unlocked(1, &save_data));
+    assert!(!campaign.is_unlocked(2, &save_data));
+    // Past the last level there is nothing to unlock
+    assert!(!campaign.is_unlocked(levels.len() + 1, &save_data));
+
+    assert_eq!(
+        campaign.next_after(&levels[0].id).map(|level| &level.id),
+        Some(&levels[1].id)
+    );
+    assert!(campaign.next_after(&levels[levels.len() - 1].id).is_none());
+}
@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+use rusty_asteroid::components::*;
+use rusty_asteroid::resources::{SPATIAL_CELL_SIZE, SpatialGrid};
+use std::collections::BTreeSet;
+use std::f32::consts::FRAC_PI_2;
+
+fn at(x: f32, y: f32) -> Transform {
+    Transform::from_xyz(x, y, 0.0)
+}
+
+fn square(half_size: f32) -> Collider {
+    Collider::convex_polygon(vec![
+        Vec2::new(-half_size, -half_size),
+        Vec2::new(half_size, -half_size),
+        Vec2::new(half_size, half_size),
+        Vec2::new(-half_size, half_size),
+    ])
+}
+
+fn hits(a: &Collider, a_transform: Transform, b: &Collider, b_transform: Transform) -> bool {
+    let forward = a.intersects(&a_transform, b, &b_transform);
+    let backward = b.intersects(&b_transform, a, &a_transform);
+    assert_eq!(forward, backward, "collision test is not symmetric");
+    forward
+}
+
+#[test]
+fn circles_overlap_touch_and_miss() {
+    let circle = Collider::circle(10.0);
+    assert!(hits(&circle, at(0.0, 0.0), &circle, at(15.0, 0.0)));
+    assert!(hits(&circle, at(0.0, 0.0), &circle, at(20.0, 0.0)));
+    assert!(!hits(&circle, at(0.0, 0.0), &circle, at(20.5, 0.0)));
+}
+
+#[test]
+fn capsules_follow_their_rotation() {
+    // Upright from y -20 to 20, rounded by 2
+    let capsule = Collider::capsule(20.0, 2.0);
+    let circle = Collider::circle(3.0);
+    assert!(hits(&capsule, at(0.0, 0.0), &circle, at(0.0, 24.0)));
+    assert!(hits(&capsule, at(0.0, 0.0), &circle, at(0.0, 25.0)));
+    assert!(!hits(&capsule, at(0.0, 0.0), &circle, at(0.0, 25.5)));
+    assert!(!hits(&capsule, at(0.0, 0.0), &circle, at(10.0, 0.0)));
+
+    // Lying along X, the same circle now reaches it
+    let lying = at(0.0, 0.0).with_rotation(Quat::from_rotation_z(FRAC_PI_2));
+    assert!(hits(&capsule, lying, &circle, at(10.0, 0.0)));
+    assert!(!hits(&capsule, lying, &circle, at(0.0, 24.0)));
+}
+
+#[test]
+fn crossing_segments_hit_although_no_end_is_close() {
+    // Two thin capsules forming an X: every end is far from the other segment
+    let thin = Collider::capsule(20.0, 0.0);
+    let upright = at(0.0, 0.0);
+    let across = at(0.0, 0.0).with_rotation(Quat::from_rotation_z(FRAC_PI_2));
+    assert!(hits(&thin, upright, &thin, across));
+
+    // Shifted so they only form a T, touching at one end
+    assert!(hits(
+        &thin,
+        at(0.0, 0.0),
+        &thin,
+        at(20.0, 0.0).with_rotation(across.rotation)
+    ));
+    // And just apart
+    assert!(!hits(
+        &thin,
+        at(0.0, 0.0),
+        &thin,
+        at(20.5, 0.0).with_rotation(across.rotation)
+    ));
+}
+
+#[test]
+fn polygons_hit_shapes_inside_and_against_their_edges() {
+    let square = square(10.0);
+    let small = Collider::circle(2.0);
+
+    // Entirely inside, so no edge is near the circle
+    assert!(hits(&square, at(0.0, 0.0), &small, at(1.0, 1.0)));
+    assert!(hits(&square, at(0.0, 0.0), &small, at(12.0, 0.0)));
+    assert!(!hits(&square, at(0.0, 0.0), &small, at(12.5, 0.0)));
+    // Past the corner the distance is diagonal
+    assert!(!hits(&square, at(0.0, 0.0), &small, at(12.0, 12.0)));
+
+    assert!(hits(&square, at(0.0, 0.0), &square, at(15.0, 5.0)));
+    assert!(hits(&square, at(0.0, 0.0), &square, at(20.0, 0.0)));
+    assert!(!hits(&square, at(0.0, 0.0), &square, at(20.5, 0.0)));
+
+    // Turned 45 degrees, the corner pokes out to about 14.1
+    let diamond = at(0.0, 0.0).with_rotation(Quat::from_rotation_z(FRAC_PI_2 / 2.0));
+    assert!(hits(&square, diamond, &small, at(15.0, 0.0)));
+    assert!(!hits(&square, diamond, &small, at(16.5, 0.0)));
+}
+
+#[test]
+fn shapes_grow_with_their_scale() {
+    let circle = Collider::circle(10.0);
+    let doubled = at(0.0, 0.0).with_scale(Vec3::splat(2.0));
+    assert!(hits(&circle, doubled, &circle, at(30.0, 0.0)));
+    assert!(!hits(&circle, doubled, &circle, at(30.5, 0.0)));
+    assert_eq!(circle.bounding_radius(&doubled), 20.0);
+
+    let square = square(10.0);
+    let wide = at(0.0, 0.0).with_scale(Vec3::new(3.0, 1.0, 1.0));
+    let small = Collider::circle(2.0);
+    assert!(hits(&square, wide, &small, at(31.0, 0.0)));
+    assert!(!hits(&square, wide, &small, at(0.0, 13.0)));
+    assert!(square.bounding_radius(&wide) >= Vec2::new(30.0, 10.0).length());
+
+    let capsule = Collider::capsule(20.0, 2.0);
+    let halved = at(0.0, 0.0).with_scale(Vec3::splat(0.5));
+    assert!(hits(&capsule, halved, &small, at(0.0, 13.0)));
+    assert!(!hits(&capsule, halved, &small, at(0.0, 13.5)));
+}
+
+// A random collider of any shape, on one or two random layers; about one in
+// ten is bigger than a grid cell
+fn random_collider(rng: &mut fastrand::Rng) -> Collider {
+    let size = if rng.u8(..10) == 0 {
+        SPATIAL_CELL_SIZE * rng.f32().mul_add(2.0, 1.0)
+    } else {
+        rng.f32().mul_add(30.0, 1.0)
+    };
+    let collider = match rng.u8(..3) {
+        0 => Collider::circle(size),
+        1 => Collider::capsule(size, size * 0.2),
+        _ => square(size * 0.7),
+    };
+    let mut layer = || CollisionLayers(1 << rng.u32(..4));
+    collider.with_layers(layer() | layer(), layer() | layer())
+}
+
+fn random_transform(rng: &mut fastrand::Rng) -> Transform {
+    // Half the entities sit within a couple of units of a cell border
+    let mut coordinate = || {
+        let value = rng.f32().mul_add(1200.0, -600.0);
+        if rng.bool() {
+            (value / SPATIAL_CELL_SIZE).round() * SPATIAL_CELL_SIZE + rng.f32().mul_add(4.0, -2.0)
+        } else {
+            value
+        }
+    };
+    at(coordinate(), coordinate())
+        .with_rotation(Quat::from_rotation_z(rng.f32() * std::f32::consts::TAU))
+        .with_scale(Vec3::splat(rng.f32().mul_add(1.5, 0.5)))
+}
+
+#[test]
+fn grid_finds_the_same_hits_as_checking_every_pair() {
+    let mut rng = fastrand::Rng::with_seed(0x5EED);
+    let mut hits_found = 0;
+    for _ in 0..20 {
+        let bodies: Vec<(Entity, Transform, Collider)> = (0..200)
+            .map(|index| {
+                (
+                    Entity::from_raw(index),
+                    random_transform(&mut rng),
+                    random_collider(&mut rng),
+                )
+            })
+            .collect();
+
+        let mut grid = SpatialGrid::default();
+        for (entity, transform, collider) in &bodies {
+            grid.insert(*entity, transform, collider);
+        }
+        assert_eq!(grid.entry_count(), bodies.len());
+
+        for (entity, transform, collider) in &bodies {
+            let from_grid: BTreeSet<Entity> = grid
+                .query_collider(transform, collider, collider.mask)
+                .filter(|entry| entry.entity != *entity)
+                .map(|entry| {
+                    let (_, other_transform, other) = &bodies[entry.entity.index() as usize];
+                    (entry.entity, other_transform, other)
+                })
+                .filter(|(_, other_transform, other)| {
+                    collider.intersects(transform, other, other_transform)
+                })
+                .map(|(other_entity, _, _)| other_entity)
+                .collect();
+            let brute_force: BTreeSet<Entity> = bodies
+                .iter()
+                .filter(|(other_entity, other_transform, other)| {
+                    other_entity != entity
+                        && collider.mask.intersects(other.layers)
+                        && collider.intersects(transform, other, other_transform)
+                })
+                .map(|(other_entity, _, _)| *other_entity)
+                .collect();
+            assert_eq!(from_grid, brute_force);
+            hits_found += brute_force.len();
+        }
+    }
+    assert!(hits_found > 100, "only {} hits", hits_found);
+}
+
+#[test]
+fn grid_finds_neighbours_across_cell_borders() {
+    // Every pair straddles a border, several of them at negative coordinates
+    let circle =
+        Collider::circle(3.0).with_layers(CollisionLayers::ASTEROID, CollisionLayers::ASTEROID);
+    for border in [-2.0, -1.0, 0.0, 1.0, 3.0] {
+        let edge = border * SPATIAL_CELL_SIZE;
+        let left = at(edge - 2.0, edge - 2.0);
+        let right = at(edge + 2.0, edge + 2.0);
+
+        let mut grid = SpatialGrid::default();
+        grid.insert(Entity::from_raw(0), &left, &circle);
+        grid.insert(Entity::from_raw(1), &right, &circle);
+        let found: Vec<Entity> = grid
+            .query_collider(&left, &circle, CollisionLayers::ASTEROID)
+            .map(|entry| entry.entity)
+            .collect();
+        assert_eq!(
+            found,
+            [Entity::from_raw(0), Entity::from_raw(1)],
+            "border {}",
+            edge
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use bevy::input::gamepad::{
+    GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+    RawGamepadButtonChangedEvent, RawGamepadEvent,
+};
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::{ButtonState, InputPlugin, InputSystem};
+use bevy::prelude::*;
+use rusty_asteroid::components::{MenuAction, MenuButtonMesh};
+use rusty_asteroid::events::MenuButtonPressed;
+use rusty_asteroid::resources::*;
+use rusty_asteroid::systems::{menu_gamepad_system, read_player_input, update_action_state};
+use std::collections::BTreeMap;
+use std::f32::consts::FRAC_PI_2;
+
+// Just the input layer: key events in, ship controls out
+fn input_app(settings: GameSettings) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin))
+        .insert_resource(settings)
+        .init_resource::<ActionState>()
+        .init_resource::<PlayerInput>()
+        .add_systems(
+            PreUpdate,
+            (update_action_state, read_player_input)
+                .chain()
+                .after(InputSystem),
+        );
+    app
+}
+
+fn press(app: &mut App, key_code: KeyCode) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+// A gamepad as the platform backend would report it
+fn connect_gamepad(app: &mut App) -> Entity {
+    let gamepad = app.world_mut().spawn_empty().id();
+    app.world_mut().send_event(GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Connected {
+            name: "Test gamepad".to_string(),
+            vendor_id: None,
+            product_id: None,
+        },
+    ));
+    app.update();
+    gamepad
+}
+
+fn set_button(app: &mut App, gamepad: Entity, button: GamepadButton, value: f32) {
+    app.world_mut()
+        .send_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad, button, value,
+        )));
+}
+
+fn set_axis(app: &mut App, gamepad: Entity, axis: GamepadAxis, value: f32) {
+    app.world_mut()
+        .send_event(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+            gamepad, axis, value,
+        )));
+}
+
+#[test]
+fn rebound_keys_drive_the_ship() {
+    let mut settings = GameSettings::default();
+    settings
+        .controls
+        .bind(InputAction::Fire, KeyCode::KeyJ)
+        .unwrap();
+    let mut app = input_app(settings);
+
+    press(&mut app, KeyCode::KeyJ);
+    press(&mut app, KeyCode::KeyW);
+    app.update();
+    let input = *app.world().resource::<PlayerInput>();
+    assert!(input.fire);
+    assert!(input.thrust);
+    assert!(
+        app.world()
+            .resource::<ActionState>()
+            .just_pressed(InputAction::Fire)
+    );
+
+    // Held keys stay pressed without being newly pressed
+    app.update();
+    assert!(app.world().resource::<PlayerInput>().fire);
+    assert!(
+        !app.world()
+            .resource::<ActionState>()
+            .just_pressed(InputAction::Fire)
+    );
+}
+
+#[test]
+fn binding_a_taken_key_is_refused() {
+    let mut bindings = InputBindings::default();
+    assert_eq!(
+        bindings.bind(InputAction::Fire, KeyCode::KeyW),
+        Err(InputAction::Thrust)
+    );
+    assert_eq!(bindings.keys(InputAction::Fire), &[KeyCode::Space]);
+
+    // A new key goes first and the oldest one drops out
+    bindings.bind(InputAction::Thrust, KeyCode::KeyI).unwrap();
+    assert_eq!(
+        bindings.keys(InputAction::Thrust),
+        &[KeyCode::KeyI, KeyCode::ArrowUp]
+    );
+    bindings
+        .bind(InputAction::Thrust, KeyCode::ArrowUp)
+        .unwrap();
+    assert_eq!(
+        bindings.keys(InputAction::Thrust),
+        &[KeyCode::ArrowUp, KeyCode::KeyI]
+    );
+    assert!(bindings.conflicts().is_empty());
+}
+
+#[test]
+fn stored_bindings_survive_unknown_keys_and_missing_actions() {
+    let names: BTreeMap<InputAction, Vec<String>> = serde_json::from_str(
+        r#"{"Fire": ["KeyJ", "NoSuchKey"], "Thrust": ["KeyJ"], "Pause": ["NoSuchKey"]}"#,
+    )
+    .unwrap();
+    let bindings = InputBindings::from(names);
+    assert_eq!(bindings.keys(InputAction::Fire), &[KeyCode::KeyJ]);
+    // Nothing usable left, so the default stays
+    assert_eq!(bindings.keys(InputAction::Pause), &[KeyCode::Escape]);
+    assert_eq!(
+        bindings.keys(InputAction::RotateLeft),
+        &[KeyCode::ArrowLeft, KeyCode::KeyA]
+    );
+    assert_eq!(
+        bindings.conflicts(),
+        vec![(InputAction::Thrust, InputAction::Fire, KeyCode::KeyJ)]
+    );
+    assert_eq!(bindings.label(InputAction::RotateLeft), "Left / A");
+}
+
+#[test]
+fn gamepad_sticks_triggers_and_buttons_drive_the_ship() {
+    let mut app = input_app(GameSettings::default());
+    let gamepad = connect_gamepad(&mut app);
+
+    // Half left, past the deadzone; a light trigger pull; Start
+    set_axis(&mut app, gamepad, GamepadAxis::LeftStickX, -0.6);
+    set_button(&mut app, gamepad, GamepadButton::RightTrigger2, 0.3);
+    set_button(&mut app, gamepad, GamepadButton::South, 1.0);
+    set_button(&mut app, gamepad, GamepadButton::Start, 1.0);
+    app.update();
+    let input = *app.world().resource::<PlayerInput>();
+    assert_eq!(input.turn, PlayerInput::turn_from(0.5));
+    assert!(input.thrust);
+    assert!(input.fire);
+    assert!(!input.reverse);
+    assert_eq!(input.aim, None);
+    assert!(
+        app.world()
+            .resource::<ActionState>()
+            .just_pressed(InputAction::Pause)
+    );
+
+    // Inside the deadzones nothing happens
+    set_axis(&mut app, gamepad, GamepadAxis::LeftStickX, -0.15);
+    set_button(&mut app, gamepad, GamepadButton::RightTrigger2, 0.05);
+    app.update();
+    let input = *app.world().resource::<PlayerInput>();
+    assert_eq!(input.turn, 0);
+    assert!(!input.thrust);
+    assert!(
+        !app.world()
+            .resource::<ActionState>()
+            .just_pressed(InputAction::Pause)
+    );
+}
+
+#[test]
+fn gamepad_stick_can_aim_the_ship() {
+    let mut settings = GameSettings::default();
+    settings.gamepad.stick = StickMode::Aim;
+    let mut app = input_app(settings);
+    let gamepad = connect_gamepad(&mut app);
+
+    // Pointing left is a quarter turn counter-clockwise from straight up
+    set_axis(&mut app, gamepad, GamepadAxis::LeftStickX, -1.0);
+    app.update();
+    let input = *app.world().resource::<PlayerInput>();
+    assert_eq!(input.aim, Some(64));
+    assert_eq!(input.turn, 0);
+    assert!((PlayerInput::aim_heading(64) - FRAC_PI_2).abs() < 1e-6);
+}
+
+#[test]
+fn gamepad_moves_the_menu_focus_and_presses_buttons() {
+    let mut app = input_app(GameSettings::default());
+    app.add_event::<MenuButtonPressed>()
+        .init_resource::<MenuFocus>()
+        .add_systems(Update, menu_gamepad_system);
+    let gamepad = connect_gamepad(&mut app);
+
+    let mut spawn_button = |action: MenuAction, y: f32| {
+        app.world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, y, 0.0),
+                MenuButtonMesh::new(action, Vec2::new(200.0, 50.0), Color::WHITE),
+            ))
+            .id()
+    };
+    let resume = spawn_button(MenuAction::Resume, 20.0);
+    let settings = spawn_button(MenuAction::Settings, -30.0);
+
+    // The first push focuses the top button, the next one moves down
+    set_button(&mut app, gamepad, GamepadButton::DPadDown, 1.0);
+    app.update();
+    assert_eq!(app.world().resource::<MenuFocus>().0, Some(resume));
+    set_button(&mut app, gamepad, GamepadButton::DPadDown, 0.0);
+    app.update();
+    set_axis(&mut app, gamepad, GamepadAxis::LeftStickY, -1.0);
+    app.update();
+    assert_eq!(app.world().resource::<MenuFocus>().0, Some(settings));
+
+    // Holding the stick doesn't keep moving, and there's nothing further down
+    app.update();
+    assert_eq!(app.world().resource::<MenuFocus>().0, Some(settings));
+
+    set_button(&mut app, gamepad, GamepadButton::South, 1.0);
+    app.update();
+    let pressed: Vec<MenuButtonPressed> = app
+        .world_mut()
+        .resource_mut::<Events<MenuButtonPressed>>()
+        .drain()
+        .collect();
+    assert_eq!(pressed.len(), 1);
+    assert!(matches!(pressed[0].action, MenuAction::Settings));
+}
+
+#[test]
+fn analog_input_survives_a_replay_file() {
+    let inputs = vec![
+        PlayerInput {
+            thrust: true,
+            turn: -90,
+            ..default()
+        },
+        PlayerInput {
+            fire: true,
+            aim: Some(200),
+            ..default()
+        },
+        PlayerInput::default(),
+    ];
+    let replay = Replay {
+        game_version: "test".to_string(),
+        tuning: 0,
+        seed: 7,
+        timestep: std::time::Duration::from_secs_f64(1.0 / 60.0),
+        playfield: PlayfieldBounds::default(),
+        difficulty: DifficultySettings::custom(),
+        waves: None,
+        level: None,
+        final_score: 120,
+        inputs: inputs.clone(),
+    };
+    let decoded = Replay::decode(&replay.encode()).unwrap();
+    assert_eq!(decoded.inputs, inputs);
+}
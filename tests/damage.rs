@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use rusty_asteroid::components::*;
+use rusty_asteroid::events::*;
+use rusty_asteroid::resources::*;
+use rusty_asteroid::systems::{
+    apply_damage, handle_destroyed, laser_collision_system, rebuild_spatial_grid, update_combo,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+// The damage systems as `CombatPlugin` chains them, without the weapons
+fn damage_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .add_event::<DamageEvent>()
+        .add_event::<Destroyed>()
+        .add_event::<BossPhaseChanged>()
+        .add_event::<AsteroidDestroyed>()
+        .add_event::<EnemyKilled>()
+        .add_event::<BossDefeated>()
+        .add_event::<PlayerHit>()
+        .init_resource::<GameScore>()
+        .init_resource::<BossSpawnManager>()
+        .insert_resource(GameRng::new(11))
+        .add_systems(
+            Update,
+            (update_combo, apply_damage, handle_destroyed).chain(),
+        );
+    app
+}
+
+fn hit(app: &mut App, target: Entity, amount: u32, kind: DamageKind) {
+    app.world_mut().send_event(DamageEvent {
+        target,
+        amount,
+        source: None,
+        kind,
+    });
+}
+
+fn spawn_asteroid(app: &mut App, size: u32, asteroid_type: AsteroidType) -> Entity {
+    let asteroid = Asteroid::new(size, asteroid_type);
+    let health = Health::new(asteroid.max_health());
+    app.world_mut()
+        .spawn((asteroid, health, Transform::from_xyz(100.0, -50.0, 0.0)))
+        .id()
+}
+
+fn sent<E: Event + Clone>(app: &App) -> Vec<E> {
+    app.world()
+        .resource::<Events<E>>()
+        .iter_current_update_events()
+        .cloned()
+        .collect()
+}
+
+fn asteroid_sizes(app: &mut App) -> Vec<u32> {
+    app.world_mut()
+        .query::<&Asteroid>()
+        .iter(app.world())
+        .map(|asteroid| asteroid.size)
+        .collect()
+}
+
+#[test]
+fn hits_that_leave_health_score_chip_points() {
+    let mut app = damage_app();
+    let asteroid = spawn_asteroid(&mut app, 3, AsteroidType::Normal);
+
+    hit(&mut app, asteroid, 1, DamageKind::Bullet);
+    hit(&mut app, asteroid, 1, DamageKind::Laser);
+    app.update();
+
+    assert_eq!(app.world().get::<Health>(asteroid).unwrap().current, 1);
+    let score = app.world().resource::<GameScore>();
+    assert_eq!(
+        score.score,
+        DamageKind::Bullet.chip_score() + DamageKind::Laser.chip_score()
+    );
+    assert_eq!(score.combo, 0);
+    assert!(sent::<Destroyed>(&app).is_empty());
+}
+
+#[test]
+fn destroyed_asteroids_score_once_and_break_apart() {
+    let mut app = damage_app();
+    let asteroid = spawn_asteroid(&mut app, 3, AsteroidType::Normal);
+    let points = app
+        .world()
+        .get::<Asteroid>(asteroid)
+        .unwrap()
+        .points_value();
+
+    // The second hit lands on an asteroid that is already destroyed
+    hit(&mut app, asteroid, 5, DamageKind::Explosion);
+    hit(&mut app, asteroid, 5, DamageKind::Bullet);
+    app.update();
+
+    let destroyed = sent::<Destroyed>(&app);
+    assert_eq!(destroyed.len(), 1);
+    assert_eq!(destroyed[0].kind, DamageKind::Explosion);
+    assert_eq!(destroyed[0].position, Vec3::new(100.0, -50.0, 0.0));
+
+    let kills = sent::<AsteroidDestroyed>(&app);
+    assert_eq!(kills.len(), 1);
+    assert_eq!(kills[0].size, 3);
+    assert_eq!(kills[0].score_delta, points);
+    assert_eq!(kills[0].cause, DamageKind::Explosion);
+
+    let score = app.world().resource::<GameScore>();
+    assert_eq!(score.score, points);
+    assert_eq!(score.combo, 1);
+
+    assert!(app.world().get_entity(asteroid).is_err());
+    let fragments = asteroid_sizes(&mut app);
+    assert!(!fragments.is_empty());
+    assert!(fragments.iter().all(|&size| size == 2));
+}
+
+#[test]
+fn the_smallest_asteroids_leave_no_fragments() {
+    let mut app = damage_app();
+    let asteroid = spawn_asteroid(&mut app, 1, AsteroidType::Normal);
+
+    hit(&mut app, asteroid, 1, DamageKind::Bullet);
+    app.update();
+
+    assert_eq!(sent::<AsteroidDestroyed>(&app).len(), 1);
+    assert!(asteroid_sizes(&mut app).is_empty());
+}
+
+#[test]
+fn targets_left_with_no_health_die_on_their_first_hit() {
+    let mut app = damage_app();
+    let asteroid = spawn_asteroid(&mut app, 1, AsteroidType::Normal);
+    app.world_mut().get_mut::<Health>(asteroid).unwrap().current = 0;
+
+    hit(&mut app, asteroid, 1, DamageKind::Bullet);
+    hit(&mut app, asteroid, 1, DamageKind::Bullet);
+    app.update();
+
+    assert_eq!(sent::<Destroyed>(&app).len(), 1);
+    assert_eq!(sent::<AsteroidDestroyed>(&app).len(), 1);
+    assert!(app.world().get_entity(asteroid).is_err());
+}
+
+#[test]
+fn destroyed_enemies_are_reported_as_kills() {
+    let mut app = damage_app();
+    let archetypes = EnemyArchetypes::built_in();
+    let hunter = Arc::clone(archetypes.get(&EnemyType("Hunter".to_string())).unwrap());
+    let enemy = Enemy::new(Arc::clone(&hunter));
+    let health = Health::new(enemy.max_health());
+    let entity = app
+        .world_mut()
+        .spawn((enemy, health, Transform::default()))
+        .id();
+
+    hit(&mut app, entity, hunter.health, DamageKind::Laser);
+    app.update();
+
+    let kills = sent::<EnemyKilled>(&app);
+    assert_eq!(kills.len(), 1);
+    assert_eq!(kills[0].enemy_type, hunter.id);
+    assert_eq!(kills[0].score_delta, hunter.points);
+    assert!(sent::<AsteroidDestroyed>(&app).is_empty());
+    assert!(app.world().get_entity(entity).is_err());
+    assert_eq!(app.world().resource::<GameScore>().score, hunter.points);
+}
+
+#[test]
+fn enemies_at_the_lowest_health_multiplier_can_still_be_killed() {
+    let mut app = damage_app();
+    let archetypes = EnemyArchetypes::built_in();
+    let interceptor = archetypes
+        .get(&EnemyType("Interceptor".to_string()))
+        .unwrap();
+    let mut difficulty = DifficultySettings::custom();
+    let (lowest, _, _) = DifficultyField::EnemyHealth.range();
+    difficulty.set_value(DifficultyField::EnemyHealth, lowest);
+
+    let enemy = Enemy::new_with_difficulty(Arc::clone(interceptor), &difficulty);
+    let health = enemy.max_health_with_difficulty(&difficulty);
+    assert_eq!(health, 1);
+    let entity = app
+        .world_mut()
+        .spawn((enemy, Health::new(health), Transform::default()))
+        .id();
+
+    hit(&mut app, entity, 1, DamageKind::Bullet);
+    app.update();
+
+    assert_eq!(sent::<EnemyKilled>(&app).len(), 1);
+    assert!(app.world().get_entity(entity).is_err());
+}
+
+#[derive(Resource, Default)]
+struct LaserDamage(u32);
+
+fn count_laser_damage(mut damage_events: EventReader<DamageEvent>, mut total: ResMut<LaserDamage>) {
+    total.0 += damage_events
+        .read()
+        .map(|damage| damage.amount)
+        .sum::<u32>();
+}
+
+// Total damage a beam held on a tough asteroid deals over one second,
+// simulated at `tick_rate` updates per second
+fn laser_damage_over_a_second(tick_rate: u32) -> u32 {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / tick_rate as f64,
+        )))
+        .add_event::<DamageEvent>()
+        .init_resource::<SpatialGrid>()
+        .init_resource::<LaserDamage>()
+        .add_systems(
+            Update,
+            (
+                rebuild_spatial_grid,
+                laser_collision_system,
+                count_laser_damage,
+            )
+                .chain(),
+        );
+
+    let laser = LaserBeam::new(50.0, 400.0, 4.0);
+    let collider = laser.collider();
+    app.world_mut()
+        .spawn((laser, collider, Transform::default()));
+    let asteroid = Asteroid::new(3, AsteroidType::Metal);
+    let collider = asteroid.collider();
+    app.world_mut()
+        .spawn((asteroid, collider, Transform::from_xyz(0.0, 100.0, 0.0)));
+
+    // The first update only starts the clock
+    app.update();
+    for _ in 0..tick_rate {
+        app.update();
+    }
+    app.world().resource::<LaserDamage>().0
+}
+
+#[test]
+fn lasers_burn_for_the_same_damage_at_any_tick_rate() {
+    // Rates whose tick length is exact in binary, so no rounding creeps in
+    assert_eq!(laser_damage_over_a_second(32), 50);
+    assert_eq!(laser_damage_over_a_second(128), 50);
+}
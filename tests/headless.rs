@@ -0,0 +1,128 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::prelude::*;
+use rusty_asteroid::RustyAsteroidPlugin;
+use rusty_asteroid::headless::{HeadlessPlugin, HeadlessRun};
+use rusty_asteroid::resources::*;
+use rusty_asteroid::systems::start_replay_playback;
+use std::time::Duration;
+
+const SEED: u64 = 7;
+const TICKS: u32 = 1200;
+
+struct RunResult {
+    exit: AppExit,
+    ticks: u32,
+    score: u32,
+    entities: u32,
+    replay: Option<Replay>,
+}
+
+// Builds the app the way `--headless --seed` does, keeping its files out of
+// the real data directory
+fn headless_app(seed: u64, max_ticks: u32) -> App {
+    let dir = std::env::temp_dir().join(format!("rusty_asteroid_headless_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    set_data_dir(dir);
+
+    let mut game_rng = GameRng::default();
+    game_rng.requested_seed = Some(seed);
+    let settings = GameSettings::default();
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin {
+        max_ticks: Some(max_ticks),
+        timestep: settings.simulation.timestep(),
+    })
+    .insert_resource(game_rng)
+    .insert_resource(ReplayRecorder::default())
+    .insert_resource(settings)
+    .insert_resource(StorageErrors(Vec::new()))
+    .add_plugins(RustyAsteroidPlugin);
+    app
+}
+
+// Updates the app the way its runner would, until something asks it to
+// exit. The ship turns and fires the whole run, so it scores.
+fn run(mut app: App) -> RunResult {
+    app.finish();
+    app.cleanup();
+    for key_code in [KeyCode::ArrowLeft, KeyCode::Space] {
+        app.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    let started = std::time::Instant::now();
+    let exit = loop {
+        app.update();
+        if let Some(exit) = app.should_exit() {
+            break exit;
+        }
+        assert!(
+            started.elapsed() < Duration::from_secs(120),
+            "headless run never ended"
+        );
+    };
+
+    let world = app.world();
+    RunResult {
+        exit,
+        ticks: world.resource::<HeadlessRun>().ticks,
+        score: world.resource::<GameScore>().score,
+        entities: world.entities().len(),
+        replay: world.resource::<LastReplay>().0.clone(),
+    }
+}
+
+#[test]
+fn seeded_runs_are_deterministic_and_end_cleanly() {
+    let first = run(headless_app(SEED, TICKS));
+    let second = run(headless_app(SEED, TICKS));
+
+    assert_eq!(first.exit, AppExit::Success);
+    assert_eq!(second.exit, AppExit::Success);
+    assert!(first.ticks <= TICKS);
+    assert!(first.score > 0);
+    assert!(first.entities > 0);
+
+    assert_eq!(first.ticks, second.ticks);
+    assert_eq!(first.score, second.score);
+    assert_eq!(first.entities, second.entities);
+}
+
+#[test]
+fn stops_at_the_tick_limit() {
+    let result = run(headless_app(SEED, 100));
+    assert_eq!(result.exit, AppExit::Success);
+    assert_eq!(result.ticks, 100);
+}
+
+#[test]
+fn replays_keep_the_playfield_they_were_recorded_in() {
+    let recorded = run(headless_app(SEED, TICKS));
+    let replay = recorded.replay.expect("the run ended in a game over");
+    assert_eq!(replay.playfield, PlayfieldBounds::default());
+    assert_eq!(
+        replay.tuning,
+        tuning_fingerprint(
+            &EnemyArchetypes::load(),
+            Some(&BossDefinitions::load(&EnemyArchetypes::load())),
+            &SpawnTables::load()
+        )
+    );
+
+    // As if the replay were watched in a bigger window
+    let mut app = headless_app(SEED, TICKS);
+    app.insert_resource(PlayfieldBounds::new(1280.0, 720.0));
+    start_replay_playback(app.world_mut(), replay);
+    let replayed = run(app);
+
+    assert_eq!(replayed.ticks, recorded.ticks);
+    assert_eq!(replayed.score, recorded.score);
+}
@@ -0,0 +1,190 @@
+use rusty_asteroid::resources::*;
+use std::time::Duration;
+
+fn sample_replay() -> Replay {
+    let thrust = PlayerInput {
+        thrust: true,
+        ..Default::default()
+    };
+    let fire = PlayerInput {
+        fire: true,
+        rotate_left: true,
+        ..Default::default()
+    };
+    let mut inputs = vec![PlayerInput::default(); 3];
+    inputs.extend([thrust; 200]);
+    inputs.extend([fire, PlayerInput::default(), fire]);
+
+    Replay {
+        game_version: "1.2.3".to_string(),
+        tuning: 0x0123_4567_89AB_CDEF,
+        seed: 0xDEAD_BEEF_1234,
+        timestep: Duration::from_nanos(15_625_000),
+        playfield: PlayfieldBounds::new(1280.0, 720.0),
+        difficulty: DifficultySettings::custom(),
+        waves: Some("tuning/waves/classic.json".to_string()),
+        level: None,
+        final_score: 4200,
+        inputs,
+    }
+}
+
+// A replay whose inputs are one run of idle ticks, so the tick count sits
+// just before the last two bytes
+fn idle_replay_bytes(ticks: usize) -> Vec<u8> {
+    Replay {
+        inputs: vec![PlayerInput::default(); ticks],
+        ..sample_replay()
+    }
+    .encode()
+}
+
+fn set_tick_count(bytes: &mut [u8], tick_count: u32) {
+    let offset = bytes.len() - 6;
+    bytes[offset..offset + 4].copy_from_slice(&tick_count.to_le_bytes());
+}
+
+// Magic, format version, game version, tuning, seed and tick length come
+// before the playfield
+fn playfield_offset(replay: &Replay) -> usize {
+    4 + 2 + 1 + replay.game_version.len() + 8 + 8 + 8
+}
+
+#[test]
+fn replays_round_trip() {
+    let replay = sample_replay();
+    let decoded = Replay::decode(&replay.encode()).unwrap();
+
+    assert_eq!(decoded.game_version, replay.game_version);
+    assert_eq!(decoded.tuning, replay.tuning);
+    assert_eq!(decoded.seed, replay.seed);
+    assert_eq!(decoded.timestep, replay.timestep);
+    assert_eq!(decoded.playfield, replay.playfield);
+    assert_eq!(decoded.difficulty, replay.difficulty);
+    assert_eq!(decoded.waves, replay.waves);
+    assert_eq!(decoded.level, None);
+    assert_eq!(decoded.final_score, replay.final_score);
+    assert_eq!(decoded.inputs, replay.inputs);
+}
+
+#[test]
+fn rejects_files_that_are_not_replays() {
+    let mut bytes = sample_replay().encode();
+    bytes[0] = b'X';
+    assert!(matches!(Replay::decode(&bytes), Err(ReplayError::BadMagic)));
+    assert!(matches!(Replay::decode(b"{}"), Err(ReplayError::Truncated)));
+}
+
+#[test]
+fn rejects_unsupported_versions() {
+    for version in [0u16, REPLAY_FORMAT_VERSION + 1] {
+        let mut bytes = sample_replay().encode();
+        bytes[4..6].copy_from_slice(&version.to_le_bytes());
+        assert!(matches!(
+            Replay::decode(&bytes),
+            Err(ReplayError::UnsupportedVersion(found)) if found == version
+        ));
+    }
+}
+
+#[test]
+fn rejects_truncated_files() {
+    let bytes = sample_replay().encode();
+    for length in [5, 20, bytes.len() - 1] {
+        assert!(matches!(
+            Replay::decode(&bytes[..length]),
+            Err(ReplayError::Truncated)
+        ));
+    }
+}
+
+#[test]
+fn rejects_runs_that_do_not_match_the_tick_count() {
+    // The run holds more ticks than the header promises
+    let mut bytes = idle_replay_bytes(10);
+    set_tick_count(&mut bytes, 9);
+    assert!(matches!(
+        Replay::decode(&bytes),
+        Err(ReplayError::Corrupt(_))
+    ));
+
+    // The header promises more ticks than the runs hold
+    let mut bytes = idle_replay_bytes(10);
+    set_tick_count(&mut bytes, 11);
+    assert!(matches!(
+        Replay::decode(&bytes),
+        Err(ReplayError::Truncated)
+    ));
+}
+
+#[test]
+fn huge_tick_counts_fail_without_allocating_them() {
+    let mut bytes = idle_replay_bytes(10);
+    set_tick_count(&mut bytes, u32::MAX);
+    assert!(matches!(
+        Replay::decode(&bytes),
+        Err(ReplayError::Corrupt(_))
+    ));
+
+    // Allowed, but the file ends long before
+    set_tick_count(&mut bytes, 1 << 25);
+    assert!(matches!(
+        Replay::decode(&bytes),
+        Err(ReplayError::Truncated)
+    ));
+}
+
+#[test]
+fn only_replays_from_this_build_match_its_version() {
+    let replay = sample_replay();
+    assert!(!replay.matches_game_version());
+    let decoded = Replay::decode(
+        &Replay {
+            game_version: GAME_VERSION.to_string(),
+            ..replay
+        }
+        .encode(),
+    )
+    .unwrap();
+    assert!(decoded.matches_game_version());
+}
+
+#[test]
+fn rejects_difficulty_values_outside_their_range() {
+    let replay = sample_replay();
+    // The playfield, difficulty id and name come before the spawn rate
+    let offset = playfield_offset(&replay) + 8 + 1 + 1 + replay.difficulty.name.len();
+    let bytes = replay.encode();
+    assert_eq!(
+        bytes[offset..offset + 4],
+        replay.difficulty.asteroid_spawn_rate.to_le_bytes()
+    );
+
+    for spawn_rate in [0.0, -1.0, 100.0, f32::NAN, f32::INFINITY] {
+        let mut bytes = bytes.clone();
+        bytes[offset..offset + 4].copy_from_slice(&f32::to_le_bytes(spawn_rate));
+        assert!(
+            matches!(Replay::decode(&bytes), Err(ReplayError::Corrupt(_))),
+            "{}",
+            spawn_rate
+        );
+    }
+}
+
+#[test]
+fn rejects_broken_playfield_sizes() {
+    let replay = sample_replay();
+    let offset = playfield_offset(&replay);
+    let bytes = replay.encode();
+    assert_eq!(bytes[offset..offset + 4], 1280.0f32.to_le_bytes());
+
+    for width in [0.0, -800.0, f32::NAN, f32::INFINITY] {
+        let mut bytes = bytes.clone();
+        bytes[offset..offset + 4].copy_from_slice(&f32::to_le_bytes(width));
+        assert!(
+            matches!(Replay::decode(&bytes), Err(ReplayError::Corrupt(_))),
+            "{}",
+            width
+        );
+    }
+}
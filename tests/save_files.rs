@@ -0,0 +1,325 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use rusty_asteroid::RustyAsteroidPlugin;
+use rusty_asteroid::components::{AsteroidType, PowerUpType};
+use rusty_asteroid::events::Hazard;
+use rusty_asteroid::headless::HeadlessPlugin;
+use rusty_asteroid::resources::*;
+use rusty_asteroid::systems::{continue_suspended_run, suspend_run};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Written by the first release: no version, no combo, campaign or leaderboards
+const SAVE_DATA_V1: &str = include_str!("fixtures/save_data_v1.json");
+// Written by the first release: graphics and audio only
+const SETTINGS_V1: &str = include_str!("fixtures/settings_v1.json");
+
+// A fresh directory per test, so tests can run in parallel
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rusty_asteroid_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn loads_first_release_save_data() {
+    let save_data = SaveData::decode(SAVE_DATA_V1).unwrap();
+    assert_eq!(save_data.high_score, 3923132);
+    assert_eq!(save_data.games_played, 93);
+    assert_eq!(save_data.last_difficulty, "Normal");
+    assert_eq!(save_data.max_combo, 0);
+    assert!(save_data.campaign.is_empty());
+    assert!(save_data.leaderboards.is_empty());
+    assert_eq!(save_data.player_name, "");
+    assert_eq!(save_data.stats, LifetimeStats::default());
+    assert!(save_data.achievements.is_empty());
+}
+
+#[test]
+fn adds_run_stats_to_saves_without_them() {
+    let mut save_data = SaveData::decode(SAVE_DATA_V1).unwrap();
+    assert_eq!(save_data.asteroids_destroyed, 0);
+
+    let mut run_stats = RunStats::default();
+    run_stats.record(|totals| {
+        totals.shots_fired += 4;
+        totals.record_asteroid(&AsteroidType::Crystal);
+        totals.record_power_up(&PowerUpType::RapidFire);
+    });
+    let (totals, _) = run_stats.take_unsaved();
+    save_data.add_stats(&totals);
+    assert_eq!(save_data.asteroids_destroyed, 1);
+    assert_eq!(save_data.power_ups_collected, 1);
+    assert_eq!(save_data.stats.totals.asteroids["Crystal"], 1);
+    assert_eq!(save_data.stats.totals.accuracy(), Some(0.0));
+    assert_eq!(run_stats.totals.shots_fired, 4);
+    assert_eq!(run_stats.unsaved, StatTotals::default());
+}
+
+#[test]
+fn leaderboards_keep_their_scores_when_names_change() {
+    let board = LeaderboardId {
+        mode: RunMode::Campaign("ore_run".to_string()),
+        difficulty: DifficultyId::Hard,
+    };
+    assert_eq!(board.key(), "campaign/ore_run/Hard");
+    assert_eq!(LeaderboardId::parse(&board.key()), Some(board.clone()));
+    assert_eq!(
+        LeaderboardId::parse("waves/training/Easy"),
+        Some(LeaderboardId {
+            mode: RunMode::Waves("training".to_string()),
+            difficulty: DifficultyId::Easy,
+        })
+    );
+    assert_eq!(LeaderboardId::parse("Endless - Hard"), None);
+
+    let presets = DifficultyPresets::built_in();
+    let campaign = Campaign::built_in();
+    assert_eq!(board.title(&presets, &campaign), "Campaign: Ore Run - Hard");
+
+    let renamed_presets = DifficultyPresets::parse(
+        &include_str!("../assets/tuning/difficulties.json")
+            .replace("\"name\": \"Hard\"", "\"name\": \"Brutal\""),
+    )
+    .unwrap();
+    let renamed_campaign = Campaign::parse(
+        &include_str!("../assets/tuning/campaign.json").replace("Ore Run", "Ore Haul"),
+    )
+    .unwrap();
+    assert_eq!(
+        board.title(&renamed_presets, &renamed_campaign),
+        "Campaign: Ore Haul - Brutal"
+    );
+    assert_eq!(
+        leaderboard_title("Campaign: Lost Level - Normal", &presets, &campaign),
+        "Campaign: Lost Level - Normal"
+    );
+}
+
+#[test]
+fn loads_first_release_settings() {
+    let settings = GameSettings::decode(SETTINGS_V1).unwrap();
+    assert!(settings.graphics.bloom_enabled);
+    assert_eq!(settings.audio.music_volume, 1.0);
+    assert_eq!(settings.simulation.tick_rate, 60.0);
+    assert_eq!(settings.custom_difficulty, DifficultySettings::custom());
+}
+
+#[test]
+fn adds_default_controls_to_settings_without_them() {
+    let settings = GameSettings::decode(SETTINGS_V1).unwrap();
+    assert_eq!(settings.controls, InputBindings::default());
+    assert_eq!(settings.controls.keys(InputAction::Fire), &[KeyCode::Space]);
+    assert_eq!(settings.gamepad, GamepadControls::default());
+
+    let text = settings.encode().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(value["controls"]["Pause"][0], "Escape");
+}
+
+#[test]
+fn fixes_settings_edited_by_hand() {
+    let text = GameSettings::default().encode().unwrap();
+    let mut value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    value["custom_difficulty"]["id"] = "Hard".into();
+    value["custom_difficulty"]["asteroid_speed_multiplier"] = 9.0.into();
+
+    // Loading forces the id and clamps values edited by hand
+    let settings = GameSettings::decode(&value.to_string()).unwrap();
+    assert_eq!(settings.custom_difficulty.id, DifficultyId::Custom);
+    assert_eq!(settings.custom_difficulty.asteroid_speed_multiplier, 4.0);
+}
+
+#[test]
+fn writes_current_version() {
+    let mut save_data = SaveData::decode(SAVE_DATA_V1).unwrap();
+    save_data.update_max_combo(12);
+    let text = save_data.encode().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(value["version"], SaveData::VERSION);
+
+    let reloaded = SaveData::decode(&text).unwrap();
+    assert_eq!(reloaded.high_score, 3923132);
+    assert_eq!(reloaded.max_combo, 12);
+}
+
+#[test]
+fn rejects_newer_and_invalid_versions() {
+    let newer = format!(
+        r#"{{"version": {}, "high_score": 1}}"#,
+        SaveData::VERSION + 1
+    );
+    assert!(matches!(
+        SaveData::decode(&newer),
+        Err(StorageError::NewerVersion(_))
+    ));
+    assert!(matches!(
+        SaveData::decode(r#"{"version": 0}"#),
+        Err(StorageError::BadVersion)
+    ));
+    assert!(matches!(
+        SaveData::decode("[1, 2]"),
+        Err(StorageError::NotAnObject)
+    ));
+    assert!(matches!(
+        SaveData::decode("{\"high_score\": "),
+        Err(StorageError::Json(_))
+    ));
+}
+
+#[test]
+fn save_keeps_a_backup_and_no_temp_file() {
+    let dir = scratch_dir("backup");
+    let path = dir.join(SaveData::FILE_NAME);
+
+    let mut save_data = SaveData::default();
+    save_data.update_high_score(100);
+    save_data.save_to(&dir).unwrap();
+    assert!(!backup_path(&path).exists());
+
+    save_data.update_high_score(200);
+    save_data.save_to(&dir).unwrap();
+    let backup = SaveData::read_from(&backup_path(&path)).unwrap().unwrap();
+    assert_eq!(backup.high_score, 100);
+    assert_eq!(SaveData::load_from(&dir).0.high_score, 200);
+    assert!(!dir.join("save_data.json.tmp").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn corrupt_file_falls_back_to_backup() {
+    let dir = scratch_dir("corrupt");
+    let path = dir.join(SaveData::FILE_NAME);
+    std::fs::write(backup_path(&path), SAVE_DATA_V1).unwrap();
+    std::fs::write(&path, "{\"high_score\": 5").unwrap();
+
+    let (save_data, errors) = SaveData::load_from(&dir);
+    assert_eq!(save_data.high_score, 3923132);
+    assert_eq!(errors.len(), 2);
+    // The broken file is set aside rather than rotated over the backup
+    assert!(!path.exists());
+    assert!(dir.join("save_data.json.unreadable").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn missing_files_load_defaults_quietly() {
+    let dir = scratch_dir("missing");
+    let (settings, errors) = GameSettings::load_from(&dir);
+    assert!(errors.is_empty());
+    assert_eq!(settings.simulation.tick_rate, 60.0);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn exported_run_report_reads_back() {
+    let dir = scratch_dir("run_report");
+    let mut report = RunReport {
+        board: "Endless - Hard".to_string(),
+        score: 5000,
+        personal_best: Some(LeaderboardEntry {
+            name: "ACE".to_string(),
+            score: 4000,
+            date: 1760659200,
+            duration: 184.2,
+            seed: 12345,
+            max_combo: 23,
+        }),
+        ..Default::default()
+    };
+    report.stats.record_death(Hazard::Asteroid);
+    assert!(report.is_personal_best());
+
+    report.save_to(&dir).unwrap();
+    let exported = RunReport::read_from(&dir.join(RunReport::FILE_NAME))
+        .unwrap()
+        .unwrap();
+    assert_eq!(exported.board, "Endless - Hard");
+    assert_eq!(exported.stats.deaths["Asteroid"], 1);
+    assert!(exported.is_personal_best());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// The whole game, headless, with the ship turning and firing
+fn running_game(seed: u64) -> App {
+    let mut game_rng = GameRng::default();
+    game_rng.requested_seed = Some(seed);
+    let settings = GameSettings::default();
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin {
+        max_ticks: None,
+        timestep: settings.simulation.timestep(),
+    })
+    .insert_resource(game_rng)
+    .insert_resource(ReplayRecorder::default())
+    .insert_resource(settings)
+    .insert_resource(StorageErrors(Vec::new()))
+    .add_plugins(RustyAsteroidPlugin);
+    app.finish();
+    app.cleanup();
+
+    for key_code in [KeyCode::ArrowLeft, KeyCode::Space] {
+        app.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: ButtonState::Pressed,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+    app
+}
+
+fn game_state(app: &App) -> GameState {
+    app.world().resource::<State<GameState>>().get().clone()
+}
+
+// The snapshot as JSON, minus the cosmetic rng that spawning meshes uses up
+fn saved_run(path: &Path) -> serde_json::Value {
+    let snapshot = RunSnapshot::read_from(path).unwrap().unwrap();
+    let mut json = serde_json::to_value(snapshot).unwrap();
+    json.as_object_mut().unwrap().remove("cosmetic_rng");
+    json
+}
+
+#[test]
+fn suspended_runs_resume_as_they_were() {
+    let dir = scratch_dir("suspend");
+    set_data_dir(&dir);
+    let path = dir.join(RunSnapshot::FILE_NAME);
+
+    let mut app = running_game(3);
+    for _ in 0..240 {
+        app.update();
+    }
+    assert_eq!(game_state(&app), GameState::Playing);
+
+    suspend_run(app.world_mut());
+    let saved = saved_run(&path);
+    assert!(!saved["asteroids"].as_array().unwrap().is_empty());
+    assert!(!saved["bullets"].as_array().unwrap().is_empty());
+    assert!(saved["score"]["score"].as_u64().unwrap() > 0);
+
+    // No gameplay ticks from here on, so nothing moves between the snapshots
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    app.update();
+    assert_eq!(game_state(&app), GameState::MainMenu);
+
+    continue_suspended_run(app.world_mut());
+    assert!(!path.exists());
+    app.update();
+    assert_eq!(game_state(&app), GameState::Playing);
+    assert!(app.world().get_resource::<ResumeRun>().is_none());
+
+    suspend_run(app.world_mut());
+    assert_eq!(saved_run(&path), saved);
+}
@@ -0,0 +1,91 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use rusty_asteroid::constants::*;
+use rusty_asteroid::events::{Hazard, PlayerHit};
+use rusty_asteroid::resources::GameScore;
+use rusty_asteroid::systems::update_combo;
+use std::time::Duration;
+
+// A world holding just what `update_combo` reads, with `delta` as the last tick
+fn combo_world(score: GameScore, delta: f32) -> World {
+    let mut world = World::new();
+    let mut time = Time::<()>::default();
+    time.advance_by(Duration::from_secs_f32(delta));
+    world.insert_resource(time);
+    world.insert_resource(score);
+    world.init_resource::<Events<PlayerHit>>();
+    world
+}
+
+#[test]
+fn multiplier_steps_up_with_the_combo_and_caps() {
+    let mut score = GameScore::default();
+    for _ in 0..COMBO_KILLS_PER_STEP - 1 {
+        assert_eq!(score.add_kill(10), 10);
+    }
+    // The kill that completes a step is the first to earn it
+    assert_eq!(score.add_kill(10), 20);
+    assert_eq!(score.multiplier(), 2);
+
+    for _ in 0..COMBO_KILLS_PER_STEP * MAX_SCORE_MULTIPLIER {
+        score.add_kill(10);
+    }
+    assert_eq!(score.multiplier(), MAX_SCORE_MULTIPLIER);
+    assert_eq!(score.add_points(10), 10 * MAX_SCORE_MULTIPLIER);
+    assert_eq!(score.max_combo, score.combo);
+}
+
+#[test]
+fn points_saturate_instead_of_overflowing() {
+    let mut score = GameScore {
+        combo: COMBO_KILLS_PER_STEP,
+        ..Default::default()
+    };
+    assert_eq!(score.add_points(u32::MAX), u32::MAX);
+    assert_eq!(score.add_points(1), 2);
+    assert_eq!(score.score, u32::MAX);
+}
+
+#[test]
+fn combo_runs_out_after_the_window() {
+    let mut score = GameScore::default();
+    score.add_kill(10);
+    score.add_kill(10);
+
+    let mut world = combo_world(score, COMBO_WINDOW * 0.5);
+    world.run_system_once(update_combo).unwrap();
+    assert_eq!(world.resource::<GameScore>().combo, 2);
+
+    // A kill inside the window restarts it
+    world.resource_mut::<GameScore>().add_kill(10);
+    world.run_system_once(update_combo).unwrap();
+    assert_eq!(world.resource::<GameScore>().combo, 3);
+
+    world.run_system_once(update_combo).unwrap();
+    let score = world.resource::<GameScore>();
+    assert_eq!(score.combo, 0);
+    assert_eq!(score.max_combo, 3);
+    assert_eq!(score.multiplier(), 1);
+}
+
+#[test]
+fn getting_hit_breaks_the_combo() {
+    let mut score = GameScore::default();
+    for _ in 0..COMBO_KILLS_PER_STEP {
+        score.add_kill(10);
+    }
+    let mut world = combo_world(score, 0.0);
+    world.send_event(PlayerHit {
+        entity: Entity::PLACEHOLDER,
+        source: Entity::PLACEHOLDER,
+        hazard: Hazard::Asteroid,
+        position: Vec3::ZERO,
+        score_delta: 0,
+    });
+    world.run_system_once(update_combo).unwrap();
+
+    let score = world.resource::<GameScore>();
+    assert_eq!(score.combo, 0);
+    assert_eq!(score.combo_timer, 0.0);
+    assert_eq!(score.score, 10 * (COMBO_KILLS_PER_STEP - 1) + 20);
+}
@@ -0,0 +1,114 @@
+use rusty_asteroid::resources::*;
+
+const GIANT_ASTEROID: &str = include_str!("../assets/tuning/bosses/giant_asteroid.json");
+const ENEMIES: &str = include_str!("../assets/tuning/enemies.json");
+const SPAWNING: &str = include_str!("../assets/tuning/spawning.json");
+
+fn parse_boss_edited(from: &str, to: &str) -> Result<BossDefinitions, String> {
+    assert!(GIANT_ASTEROID.contains(from));
+    BossDefinitions::parse([GIANT_ASTEROID.replace(from, to).as_str()])
+}
+
+fn parse_enemies_edited(from: &str, to: &str) -> Result<EnemyArchetypes, String> {
+    assert!(ENEMIES.contains(from));
+    EnemyArchetypes::parse(&ENEMIES.replacen(from, to, 1))
+}
+
+#[test]
+fn built_in_bosses_parse() {
+    assert_eq!(BossDefinitions::built_in().iter().count(), 2);
+    assert!(parse_boss_edited("\"size\": 3.5", "\"size\": 1.0").is_ok());
+}
+
+#[test]
+fn rejects_negative_transition_times() {
+    let error =
+        parse_boss_edited("\"transition_time\": 2.5", "\"transition_time\": -1.0").unwrap_err();
+    assert!(error.contains("transition time"), "{}", error);
+    assert!(parse_boss_edited("\"transition_time\": 2.5", "\"transition_time\": 0.0").is_ok());
+}
+
+#[test]
+fn rejects_sizes_that_are_not_positive() {
+    let error = parse_boss_edited("\"size\": 3.5", "\"size\": 0.0").unwrap_err();
+    assert!(error.contains("size"), "{}", error);
+    assert!(parse_boss_edited("\"size\": 3.5", "\"size\": -2.0").is_err());
+}
+
+#[test]
+fn rejects_broken_enemy_numbers() {
+    for (from, to) in [
+        ("\"speed\": 80.0", "\"speed\": 0.0"),
+        ("\"shot_cooldown\": 1.5", "\"shot_cooldown\": -1.5"),
+        ("\"evasion\": 0.3", "\"evasion\": 1.5"),
+        ("\"collider_radius\": 25.0", "\"collider_radius\": 0.0"),
+    ] {
+        let error = parse_enemies_edited(from, to).unwrap_err();
+        assert!(error.contains("Hunter"), "{}", error);
+    }
+    assert!(parse_enemies_edited("\"evasion\": 0.3", "\"evasion\": 0.0").is_ok());
+}
+
+#[test]
+fn rejects_a_zero_enemy_score_interval() {
+    assert!(SpawnTables::parse(SPAWNING).is_ok());
+    let edited = SPAWNING.replace("\"score_interval\": 300", "\"score_interval\": 0");
+    assert_ne!(edited, SPAWNING);
+    assert!(SpawnTables::parse(&edited).is_err());
+}
+
+#[test]
+fn rejects_attack_intervals_that_never_end() {
+    // Too big for an f32, so it reads as infinity
+    let error =
+        parse_boss_edited("\"attack_interval\": 1.5", "\"attack_interval\": 1e39").unwrap_err();
+    assert!(error.contains("phase 1"), "{}", error);
+}
+
+#[test]
+fn rejects_minions_that_cannot_be_killed() {
+    let error = parse_boss_edited(
+        "\"spread\": 100.0, \"health\": 1",
+        "\"spread\": 100.0, \"health\": 0",
+    )
+    .unwrap_err();
+    assert!(error.contains("phase 3"), "{}", error);
+}
+
+#[test]
+fn rejects_minions_of_unknown_enemies() {
+    let archetypes = EnemyArchetypes::built_in();
+    let bosses = |enemy: &str| {
+        parse_boss_edited(
+            "\"type\": \"SpawnMinions\",",
+            &format!("\"type\": \"SpawnMinions\", \"enemy\": \"{}\",", enemy),
+        )
+        .unwrap()
+    };
+    assert!(bosses("Bomber").check_ids(&archetypes).is_ok());
+    let error = bosses("Dreadnought").check_ids(&archetypes).unwrap_err();
+    assert!(error.contains("Dreadnought"), "{}", error);
+    assert!(BossDefinitions::built_in().check_ids(&archetypes).is_ok());
+}
+
+#[test]
+fn tuning_fingerprints_change_with_the_tuning() {
+    let archetypes = EnemyArchetypes::built_in();
+    let bosses = BossDefinitions::built_in();
+    let spawn_tables = SpawnTables::parse(SPAWNING).unwrap();
+    let fingerprint = tuning_fingerprint(&archetypes, Some(&bosses), &spawn_tables);
+    assert_eq!(
+        fingerprint,
+        tuning_fingerprint(&EnemyArchetypes::built_in(), Some(&bosses), &spawn_tables)
+    );
+
+    let faster = parse_enemies_edited("\"speed\": 80.0", "\"speed\": 81.0").unwrap();
+    let bigger = parse_boss_edited("\"size\": 3.5", "\"size\": 4.0").unwrap();
+    for changed in [
+        tuning_fingerprint(&faster, Some(&bosses), &spawn_tables),
+        tuning_fingerprint(&archetypes, Some(&bigger), &spawn_tables),
+        tuning_fingerprint(&archetypes, None, &spawn_tables),
+    ] {
+        assert_ne!(changed, fingerprint);
+    }
+}
@@ -0,0 +1,174 @@
+use rusty_asteroid::resources::*;
+use std::sync::Arc;
+
+const TRAINING: &str = include_str!("../assets/tuning/waves/training.json");
+
+const BUILT_IN: [&str; 6] = [
+    include_str!("../assets/tuning/waves/beacon.json"),
+    include_str!("../assets/tuning/waves/first_contact.json"),
+    include_str!("../assets/tuning/waves/mothership.json"),
+    include_str!("../assets/tuning/waves/ore_run.json"),
+    include_str!("../assets/tuning/waves/the_giant.json"),
+    TRAINING,
+];
+
+fn one_wave(wave: &str) -> Result<WaveFile, String> {
+    WaveFile::parse(&format!(r#"{{ "name": "Test", "waves": [{}] }}"#, wave))
+}
+
+#[test]
+fn built_in_wave_files_parse_and_name_known_ids() {
+    let archetypes = EnemyArchetypes::built_in();
+    let bosses = BossDefinitions::built_in();
+    for data in BUILT_IN {
+        let file = WaveFile::parse(data).unwrap();
+        file.check_ids(&archetypes, &bosses)
+            .unwrap_or_else(|error| panic!("{}: {}", file.name, error));
+    }
+}
+
+#[test]
+fn fills_in_defaults_and_sorts_by_time() {
+    let file = one_wave(
+        r#"{
+            "asteroids": [
+                { "at": 5.0, "count": 1, "size": 2, "speed": 50.0 },
+                { "at": 1.0, "count": 2, "size": 3, "speed": 50.0, "spread": 0.0 }
+            ],
+            "enemies": [{ "at": 2.0, "enemy": "Hunter" }],
+            "clear": { "type": "Destroyed" }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(file.intermission, 3.0);
+
+    let wave = &file.waves[0];
+    assert_eq!(wave.name, None);
+    assert_eq!(wave.asteroids[0].at, 1.0);
+    assert_eq!(wave.asteroids[0].spread, 0.0);
+    assert_eq!(wave.asteroids[1].spread, 15.0);
+    assert_eq!(wave.asteroids[1].edge, SpawnEdge::default());
+    assert_eq!(wave.asteroids[1].aim, SpawnAim::Center);
+    assert_eq!(wave.enemies[0].count, 1);
+    assert!(wave.boss.is_none());
+}
+
+#[test]
+fn rejects_waves_that_cannot_be_played() {
+    let broken = [
+        r#"{ "name": "Empty", "waves": [] }"#.to_string(),
+        r#"{ "name": "Rushed", "intermission": -1.0, "waves": [{ "clear": { "type": "Destroyed" } }] }"#
+            .to_string(),
+    ];
+    for data in &broken {
+        assert!(WaveFile::parse(data).is_err(), "{}", data);
+    }
+
+    let error = one_wave(
+        r#"{ "asteroids": [{ "at": 0.0, "count": 1, "size": 0, "speed": 50.0 }], "clear": { "type": "Destroyed" } }"#,
+    )
+    .unwrap_err();
+    assert!(error.contains("size 0"), "{}", error);
+
+    let error = one_wave(r#"{ "clear": { "type": "BossDefeated" } }"#).unwrap_err();
+    assert!(error.contains("boss"), "{}", error);
+
+    for seconds in ["0.0", "-5.0"] {
+        let wave = format!(
+            r#"{{ "clear": {{ "type": "Survive", "seconds": {} }} }}"#,
+            seconds
+        );
+        assert!(one_wave(&wave).is_err(), "{}", wave);
+    }
+
+    // Unknown clear conditions and misspelled fields are JSON errors
+    assert!(one_wave(r#"{ "clear": { "type": "Pacifist" } }"#).is_err());
+    assert!(one_wave(r#"{ "clear": { "type": "Survive" } }"#).is_err());
+}
+
+#[test]
+fn check_ids_names_the_unknown_enemy_or_boss() {
+    let archetypes = EnemyArchetypes::built_in();
+    let bosses = BossDefinitions::built_in();
+
+    let file = one_wave(
+        r#"{ "enemies": [{ "at": 0.0, "enemy": "Dreadnought" }], "clear": { "type": "Destroyed" } }"#,
+    )
+    .unwrap();
+    let error = file.check_ids(&archetypes, &bosses).unwrap_err();
+    assert!(error.contains("Dreadnought"), "{}", error);
+
+    let file =
+        one_wave(r#"{ "boss": { "boss": "SpaceWhale" }, "clear": { "type": "BossDefeated" } }"#)
+            .unwrap();
+    let error = file.check_ids(&archetypes, &bosses).unwrap_err();
+    assert!(error.contains("SpaceWhale"), "{}", error);
+}
+
+#[test]
+fn runner_spawns_on_schedule_and_moves_through_the_waves() {
+    let file = Arc::new(WaveFile::parse(TRAINING).unwrap());
+    let mut runner = WaveRunner::new(file.clone());
+    assert_eq!(runner.banner().as_deref(), Some("Wave 1\nWarm-up"));
+    assert!(runner.take_due().is_empty());
+
+    runner.tick(file.intermission);
+    assert_eq!(runner.stage, WaveStage::Playing);
+    // Asteroids at 0 s only; the power-up waits until 2 s
+    assert_eq!(runner.take_due().len(), 1);
+    runner.tick(2.0);
+    assert!(matches!(runner.take_due()[..], [WaveSpawn::PowerUp(_)]));
+    assert!(!runner.is_cleared(0, 0));
+
+    runner.tick(10.0);
+    assert_eq!(runner.take_due().len(), 2);
+    assert!(runner.all_spawned());
+    assert!(!runner.is_cleared(1, 0));
+    assert!(runner.is_cleared(0, 0));
+
+    runner.finish_wave();
+    assert_eq!(runner.wave_number(), 2);
+    assert!(matches!(runner.stage, WaveStage::Intermission { .. }));
+
+    // Survive 30 s, however many hostiles are left
+    runner.tick(file.intermission);
+    runner.tick(29.0);
+    assert!(!runner.is_cleared(10, 0));
+    runner.tick(1.0);
+    assert!(runner.is_cleared(10, 0));
+
+    runner.finish_wave();
+    runner.tick(file.intermission);
+    runner.tick(2.0);
+    assert!(
+        runner
+            .take_due()
+            .iter()
+            .any(|spawn| matches!(spawn, WaveSpawn::Boss(_)))
+    );
+    assert!(!runner.is_cleared(0, 1));
+    assert!(runner.is_cleared(5, 0));
+
+    runner.finish_wave();
+    assert_eq!(runner.banner().as_deref(), Some("All waves cleared!"));
+    assert!(!runner.is_finished());
+    runner.tick(file.intermission);
+    assert!(runner.is_finished());
+}
+
+#[test]
+fn resumed_runners_pick_up_where_they_left_off() {
+    let file = Arc::new(WaveFile::parse(TRAINING).unwrap());
+    let mut runner = WaveRunner::new(file.clone());
+    runner.tick(file.intermission);
+    runner.tick(3.0);
+    runner.take_due();
+
+    let mut resumed = WaveRunner::new(file);
+    resumed.resume(&runner.progress());
+    assert_eq!(resumed.stage, WaveStage::Playing);
+    assert_eq!(resumed.elapsed, runner.elapsed);
+    resumed.tick(1.0);
+    // Only the batch at 4 s is new
+    assert_eq!(resumed.take_due().len(), 1);
+}